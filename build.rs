@@ -1,9 +1,26 @@
 // build.rs
 fn main() {
+    // Datum sestavení pro okno „O aplikaci“ (UTC, bez závislostí)
+    let days = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map_or(0, |d| d.as_secs() / 86400) as i64;
+    let (year, month, day) = civil_from_days(days);
+    println!("cargo:rustc-env=BUILD_DATE={:02}.{:02}.{}", day, month, year);
     if std::env::var("CARGO_CFG_TARGET_OS").unwrap() == "windows" {
         winres::WindowsResource::new()
             .set_icon("icon.ico") // Řekne kompilátoru, aby použil tento soubor jako ikonu
             .compile()
             .unwrap();
     }
-}
\ No newline at end of file
+}
+
+// Počet dní od 1. 1. 1970 na (rok, měsíc, den) podle gregoriánského kalendáře
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    (yoe + era * 400 + i64::from(month <= 2), month, day)
+}
@@ -0,0 +1,101 @@
+// --- Spouštění po přihlášení ---
+// Windows: klíč Run v registru aktuálního uživatele; Linux: soubor .desktop ve složce XDG autostart (~/.config/autostart).
+use log::{error, info};
+use std::io;
+use std::path::Path;
+use temp_monitor::config::custom_config_path;
+
+// Parametr, se kterým aplikaci spouští systém po přihlášení
+const AUTOSTART_ARG: &str = "--autostart";
+
+pub fn launched_by_autostart() -> bool { std::env::args().any(|arg| arg == AUTOSTART_ARG) }
+
+// Příkaz pro spuštění; instance s vlastní konfigurací se po přihlášení spustí znovu s ní
+fn command(exe: &Path) -> String {
+    let mut command = format!("\"{}\" {}", exe.display(), AUTOSTART_ARG);
+    if let Some(path) = custom_config_path() { command.push_str(&format!(" --config \"{}\"", path.display())); }
+    command
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::io;
+    use temp_monitor::config::custom_config_path;
+    use winreg::enums::HKEY_CURRENT_USER;
+    use winreg::RegKey;
+
+    const RUN_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Run";
+    const VALUE_NAME: &str = "TempMonitor";
+
+    // Každá konfigurace má vlastní položku, aby si instance navzájem nepřepisovaly automatické spuštění
+    fn value_name() -> String { custom_config_path().map_or_else(|| VALUE_NAME.to_string(), |path| format!("{} ({})", VALUE_NAME, path.display())) }
+
+    pub fn register(command: &str) -> io::Result<()> {
+        let (run, _) = RegKey::predef(HKEY_CURRENT_USER).create_subkey(RUN_KEY)?;
+        run.set_value(value_name(), &command.to_owned())
+    }
+
+    pub fn unregister() -> io::Result<()> {
+        let (run, _) = RegKey::predef(HKEY_CURRENT_USER).create_subkey(RUN_KEY)?;
+        run.delete_value(value_name())
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::fs;
+    use std::hash::{Hash, Hasher};
+    use std::io;
+    use std::path::PathBuf;
+    use temp_monitor::config::custom_config_path;
+
+    const DESKTOP_NAME: &str = "temp_monitor";
+
+    // $XDG_CONFIG_HOME/autostart, jinak ~/.config/autostart
+    fn autostart_dir() -> io::Result<PathBuf> {
+        let config_home = std::env::var_os("XDG_CONFIG_HOME").map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "HOME není nastaveno"))?;
+        Ok(config_home.join("autostart"))
+    }
+
+    // Každá konfigurace má vlastní soubor (podle otisku cesty), aby si instance navzájem nepřepisovaly automatické spuštění
+    fn desktop_file() -> io::Result<PathBuf> {
+        let name = match custom_config_path() {
+            Some(path) => { let mut hasher = std::collections::hash_map::DefaultHasher::new(); path.hash(&mut hasher); format!("{}-{:016x}.desktop", DESKTOP_NAME, hasher.finish()) },
+            None => format!("{}.desktop", DESKTOP_NAME),
+        };
+        Ok(autostart_dir()?.join(name))
+    }
+
+    pub fn register(command: &str) -> io::Result<()> {
+        let dir = autostart_dir()?;
+        fs::create_dir_all(&dir)?;
+        let entry = format!("[Desktop Entry]\nType=Application\nName=Teploměr\nComment=Záznam teploty a vlhkosti ze senzorů ThermoPro\nExec={}\nTerminal=false\nX-GNOME-Autostart-enabled=true\n", command);
+        fs::write(desktop_file()?, entry)
+    }
+
+    pub fn unregister() -> io::Result<()> { fs::remove_file(desktop_file()?) }
+}
+
+// Zapíše (nebo smaže) příkaz pro spuštění; při zapnutí se cesta obnoví, kdyby se program mezitím přesunul
+pub fn set_enabled(enabled: bool) -> io::Result<()> {
+    if enabled {
+        let command = command(&std::env::current_exe()?);
+        platform::register(&command)?;
+        info!("Automatické spuštění zaregistrováno: {}", command);
+        Ok(())
+    } else {
+        match platform::unregister() {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => { info!("Automatické spuštění zrušeno."); Ok(()) }
+        }
+    }
+}
+
+// Po přihlášení je pracovní adresář jiný než složka aplikace, config.json i logy jsou ale relativní
+pub fn enter_exe_dir() {
+    if let Some(dir) = std::env::current_exe().ok().and_then(|exe| exe.parent().map(|p| p.to_path_buf())) {
+        if let Err(e) = std::env::set_current_dir(&dir) { error!("Nelze přejít do složky aplikace {}: {}", dir.display(), e); }
+    }
+}
@@ -0,0 +1,38 @@
+// --- Teplota v ikoně okna na hlavním panelu (jen Windows) ---
+// Panel zobrazuje ikonu okna, takže stačí ji překreslit: barevný čtverec s číslem vykresleným bitmapovým písmem.
+use eframe::egui::IconData;
+
+const ICON_SIZE: usize = 32;
+// Znaky 3×5 bodů, každý řádek jako tři bity zleva doprava
+const GLYPHS: [(char, [u8; 5]); 12] = [
+    ('0', [0b111, 0b101, 0b101, 0b101, 0b111]), ('1', [0b010, 0b110, 0b010, 0b010, 0b111]),
+    ('2', [0b111, 0b001, 0b111, 0b100, 0b111]), ('3', [0b111, 0b001, 0b111, 0b001, 0b111]),
+    ('4', [0b101, 0b101, 0b111, 0b001, 0b001]), ('5', [0b111, 0b100, 0b111, 0b001, 0b111]),
+    ('6', [0b111, 0b100, 0b111, 0b101, 0b111]), ('7', [0b111, 0b001, 0b010, 0b010, 0b010]),
+    ('8', [0b111, 0b101, 0b111, 0b101, 0b111]), ('9', [0b111, 0b101, 0b111, 0b001, 0b111]),
+    ('-', [0b000, 0b000, 0b111, 0b000, 0b000]), ('°', [0b111, 0b101, 0b111, 0b000, 0b000]),
+];
+
+// Text (číslice, minus, stupeň) tmavě na podkladu v barvě stavu; širší text se kreslí menším písmem
+pub fn temperature_icon(text: &str, color: [u8; 3]) -> IconData {
+    let glyphs: Vec<[u8; 5]> = text.chars().filter_map(|c| GLYPHS.iter().find(|(g, _)| *g == c).map(|(_, rows)| *rows)).collect();
+    let text_width = |scale: usize| (glyphs.len() * 4 * scale).saturating_sub(scale);
+    let scale = if text_width(2) <= ICON_SIZE { 2 } else { 1 };
+    let (left, top) = ((ICON_SIZE - text_width(scale).min(ICON_SIZE)) / 2, (ICON_SIZE - 5 * scale) / 2);
+    let mut rgba = vec![0u8; ICON_SIZE * ICON_SIZE * 4];
+    for y in 0..ICON_SIZE {
+        for x in 0..ICON_SIZE {
+            // Zaoblené rohy: vynechá se pár bodů v každém rohu
+            let (dx, dy) = (x.min(ICON_SIZE - 1 - x), y.min(ICON_SIZE - 1 - y));
+            if dx + dy < 3 { continue; }
+            let inside_text = x >= left && y >= top && {
+                let (col, row) = ((x - left) / scale, (y - top) / scale);
+                let (glyph, bit) = (col / 4, col % 4);
+                row < 5 && bit < 3 && glyphs.get(glyph).is_some_and(|rows| rows[row] & (0b100 >> bit) != 0)
+            };
+            let pixel = if inside_text { [20, 20, 20, 255] } else { [color[0], color[1], color[2], 255] };
+            rgba[(y * ICON_SIZE + x) * 4..][..4].copy_from_slice(&pixel);
+        }
+    }
+    IconData { rgba, width: ICON_SIZE as u32, height: ICON_SIZE as u32 }
+}
@@ -0,0 +1,171 @@
+// --- Přístup k Bluetooth adaptéru ---
+// Skener potřebuje od adaptéru jen proud reklamních paketů. BleCentral ho odděluje od btleplug,
+// aby šlo v testech (feature `mock-ble`) podstrčit skriptovaný adaptér bez Bluetooth hardwaru.
+use crate::error::{Error, Result};
+use btleplug::api::bleuuid::uuid_from_u16;
+use btleplug::api::{BDAddr, Central, CentralEvent, CentralState, Manager as _, Peripheral as _, ScanFilter};
+use btleplug::platform::{Adapter, Manager, Peripheral, PeripheralId};
+use futures::stream::{self, BoxStream, StreamExt};
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::time::Duration;
+
+// Reklamní paket zařízení: adresa (velkými písmeny), název, síla signálu a data výrobce.
+// CoreBluetooth (macOS) MAC adresu neprozradí, adresou je tam UUID, které systém periferii přidělil.
+#[derive(Clone, Debug)]
+pub struct Advertisement { pub device_id: String, pub address: String, pub local_name: Option<String>, pub rssi: Option<i16>, pub manufacturer_data: HashMap<u16, Vec<u8>> }
+
+// Údaje ze služby Device Information (GATT 0x180A); senzor nemusí poskytovat všechny
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DeviceInfo { pub manufacturer: Option<String>, pub model: Option<String>, pub serial: Option<String>, pub hardware: Option<String>, pub firmware: Option<String>, pub software: Option<String> }
+
+impl DeviceInfo {
+    // (popisek, hodnota) v pořadí pro zobrazení
+    pub fn fields(&self) -> [(&'static str, &Option<String>); 6] {
+        [("Výrobce:", &self.manufacturer), ("Model:", &self.model), ("Sériové číslo:", &self.serial), ("Revize hardwaru:", &self.hardware), ("Firmware:", &self.firmware), ("Software:", &self.software)]
+    }
+}
+
+// Jak dlouho se senzor hledá skenováním a jak dlouho smí trvat připojení a čtení
+const DISCOVERY_TIMEOUT: Duration = Duration::from_secs(15);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(20);
+
+pub(crate) trait BleCentral: Sized {
+    // První dostupný adaptér
+    async fn open() -> Result<Self>;
+    async fn start_scan(&self) -> Result<()>;
+    // Pakety sledovaných zařízení (adresy jako v konfiguraci) během skenování; ostatní zařízení smí adaptér
+    // zahodit, ale každé jednou pošle, aby skener mohl ohlásit nesledované teploměry v dosahu
+    async fn advertisements(&self, watched: &[String]) -> Result<BoxStream<'static, Advertisement>>;
+    async fn stop_scan(&self);
+    // Připojí se k zařízení s danou adresou a přečte službu Device Information
+    async fn device_info(&self, address: &str) -> Result<DeviceInfo>;
+}
+
+// Adresa periferie ve tvaru z konfigurace; CoreBluetooth MAC adresu neprozradí (nulová adresa), adresou je tam UUID
+fn peripheral_address(mac: BDAddr, id: &PeripheralId) -> String {
+    if mac == BDAddr::default() { id.to_string().to_uppercase() } else { mac.to_string().to_uppercase() }
+}
+
+pub struct BtleplugCentral(Adapter);
+
+// Sledované zařízení viděné v tomto skenu: periferie a adresy připravené předem, aby se při každém paketu nevytvářely znovu
+struct Watched { peripheral: Peripheral, device_id: String, address: String }
+
+// Stav proudu paketů. Sledované adresy se porovnávají jako bajty (BDAddr), na macOS jako UUID periferie.
+// `known` pamatuje zařízení viděná v tomto skenu: pakety cizích zařízení pak stojí jen vyhledání v tabulce,
+// bez dotazu na adaptér a bez alokací. Tabulka zaniká s koncem skenu, takže neroste do nekonečna.
+struct EventState {
+    central: Adapter,
+    events: Pin<Box<dyn futures::Stream<Item = CentralEvent> + Send>>,
+    watched_macs: Vec<BDAddr>,
+    watched_ids: Vec<String>,
+    known: HashMap<PeripheralId, Option<Watched>>,
+}
+
+impl EventState {
+    async fn next(&mut self) -> Option<Advertisement> {
+        loop {
+            let (CentralEvent::DeviceDiscovered(id) | CentralEvent::DeviceUpdated(id)) = self.events.next().await? else { continue; };
+            if !self.known.contains_key(&id) {
+                if let Some(stranger) = self.first_sight(&id).await { return Some(stranger); }
+            }
+            let Some(Some(watched)) = self.known.get(&id) else { continue; };
+            let Ok(Some(props)) = watched.peripheral.properties().await else { continue; };
+            return Some(Advertisement { device_id: watched.device_id.clone(), address: watched.address.clone(), local_name: props.local_name, rssi: props.rssi, manufacturer_data: props.manufacturer_data });
+        }
+    }
+
+    // Zařadí nově viděné zařízení; cizí vrátí jako jediný paket, který od něj skener dostane (kvůli názvu
+    // pro ohlášení nesledovaných teploměrů). Nedostupné zařízení se nezařadí a zkusí se u dalšího paketu.
+    async fn first_sight(&mut self, id: &PeripheralId) -> Option<Advertisement> {
+        let peripheral = self.central.peripheral(id).await.ok()?;
+        let mac = peripheral.address();
+        let address = peripheral_address(mac, id);
+        let is_watched = if mac == BDAddr::default() { self.watched_ids.contains(&address) } else { self.watched_macs.contains(&mac) };
+        if is_watched {
+            self.known.insert(id.clone(), Some(Watched { peripheral, device_id: id.to_string(), address }));
+            return None;
+        }
+        self.known.insert(id.clone(), None);
+        let props = peripheral.properties().await.ok()??;
+        Some(Advertisement { device_id: id.to_string(), address, local_name: props.local_name, rssi: props.rssi, manufacturer_data: props.manufacturer_data })
+    }
+}
+
+// Chyby BlueZ přicházejí jako text z D-Bus; převedou se na varianty s radou, co udělat, ostatní zůstanou obecnou chybou
+#[cfg(target_os = "linux")]
+fn platform_error(e: btleplug::Error) -> Error {
+    let text = e.to_string();
+    let has = |names: &[&str]| names.iter().any(|name| text.contains(name));
+    if matches!(e, btleplug::Error::PermissionDenied) || has(&["AccessDenied", "NotPermitted", "NotAuthorized"]) { Error::PermissionDenied }
+    else if has(&["NotReady", "org.bluez.Error.Failed: Not Powered"]) { Error::AdapterOff }
+    else if has(&["system_bus_socket", "ServiceUnknown", "org.bluez was not provided"]) { Error::ServiceUnavailable }
+    else { Error::Bluetooth(e) }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn platform_error(e: btleplug::Error) -> Error {
+    match e { btleplug::Error::PermissionDenied => Error::PermissionDenied, e => Error::Bluetooth(e) }
+}
+
+impl BleCentral for BtleplugCentral {
+    // Vypnutý adaptér se hlásí hned, ne až neúspěšným skenováním
+    async fn open() -> Result<Self> {
+        let manager = Manager::new().await.map_err(platform_error)?;
+        let adapter = manager.adapters().await.map_err(platform_error)?.into_iter().next().ok_or(Error::NoAdapter)?;
+        if let Ok(CentralState::PoweredOff) = adapter.adapter_state().await { return Err(Error::AdapterOff); }
+        Ok(Self(adapter))
+    }
+
+    async fn start_scan(&self) -> Result<()> { self.0.start_scan(ScanFilter::default()).await.map_err(platform_error) }
+
+    async fn advertisements(&self, watched: &[String]) -> Result<BoxStream<'static, Advertisement>> {
+        let events = self.0.events().await.map_err(platform_error)?;
+        let state = EventState {
+            central: self.0.clone(),
+            events,
+            watched_macs: watched.iter().filter_map(|address| BDAddr::from_str_delim(address).ok()).collect(),
+            watched_ids: watched.iter().map(|address| address.to_uppercase()).collect(),
+            known: HashMap::new(),
+        };
+        Ok(stream::unfold(state, |mut state| async move { state.next().await.map(|ad| (ad, state)) }).boxed())
+    }
+
+    async fn stop_scan(&self) { let _ = self.0.stop_scan().await; }
+
+    // Periferie je k dispozici až poté, co ji adaptér při skenování uviděl; spojení se po přečtení hned ukončí,
+    // aby senzor mohl dál vysílat reklamní pakety
+    async fn device_info(&self, address: &str) -> Result<DeviceInfo> {
+        let address = address.to_uppercase();
+        self.0.start_scan(ScanFilter::default()).await.map_err(platform_error)?;
+        let found = tokio::time::timeout(DISCOVERY_TIMEOUT, async {
+            loop {
+                let peripherals = self.0.peripherals().await.map_err(platform_error)?;
+                if let Some(peripheral) = peripherals.into_iter().find(|p| peripheral_address(p.address(), &p.id()) == address) { return Ok::<_, Error>(peripheral); }
+                tokio::time::sleep(Duration::from_millis(500)).await;
+            }
+        }).await;
+        let _ = self.0.stop_scan().await;
+        let peripheral = found.map_err(|_| Error::DeviceNotFound)??;
+        let read = tokio::time::timeout(CONNECT_TIMEOUT, async {
+            peripheral.connect().await?;
+            peripheral.discover_services().await?;
+            let characteristics = peripheral.characteristics();
+            let read = |uuid| {
+                let characteristic = characteristics.iter().find(|c| c.service_uuid == uuid_from_u16(0x180A) && c.uuid == uuid_from_u16(uuid)).cloned();
+                let peripheral = &peripheral;
+                async move {
+                    let value = peripheral.read(&characteristic?).await.ok()?;
+                    Some(String::from_utf8_lossy(&value).trim_end_matches('\0').trim().to_string()).filter(|value| !value.is_empty())
+                }
+            };
+            Ok::<_, btleplug::Error>(DeviceInfo {
+                manufacturer: read(0x2A29).await, model: read(0x2A24).await, serial: read(0x2A25).await,
+                hardware: read(0x2A27).await, firmware: read(0x2A26).await, software: read(0x2A28).await,
+            })
+        }).await;
+        let _ = peripheral.disconnect().await;
+        read.map_err(|_| Error::DeviceTimeout)?.map_err(platform_error)
+    }
+}
@@ -0,0 +1,131 @@
+// --- Příkazy pro příkazovou řádku ---
+// Práce s uloženými denními logy bez spuštění okna (skripty, cron): export, denní statistiky a seznam senzorů.
+use crate::stats::{mean_kinetic_temperature, summarize_points};
+use chrono::{Local, NaiveDate};
+use std::fs;
+use std::io::Write;
+use temp_monitor::config::{normalize_mac, Config};
+use temp_monitor::i18n::{tr, trf};
+use temp_monitor::storage::{list_log_dates_with, read_history_file, read_history_files, HistoryPoint};
+use temp_monitor::units::format_temp;
+
+const COMMANDS: [&str; 6] = ["export", "stats", "devices", "ctl", "help", "--help"];
+
+const USAGE: &str = "Použití:
+  temp_monitor export [--from RRRR-MM-DD] [--to RRRR-MM-DD] [--format csv|json] [--device MAC] [--output SOUBOR]
+  temp_monitor stats [--day RRRR-MM-DD] [--device MAC]
+  temp_monitor devices
+  temp_monitor ctl show|current|pause|resume|test-alert|export [--from …] [--to …] [--device MAC]
+Bez data se použije dnešní den, bez --device hlavní senzor. Bez příkazu se spustí okno aplikace.
+Příkaz ctl řídí aplikaci běžící nad stejnou složkou s logy a vypíše její odpověď (JSON).";
+
+pub fn is_command(arg: Option<&String>) -> bool { arg.is_some_and(|arg| COMMANDS.contains(&arg.as_str())) }
+
+// Provede příkaz (první parametr za názvem programu) a vrátí návratový kód procesu
+pub fn run(args: &[String], config: &Config) -> i32 {
+    let options = &args[1..];
+    let result = match args[0].as_str() {
+        "export" => export(options, config),
+        "stats" => stats(options, config),
+        "devices" => { devices(config); Ok(()) },
+        "ctl" => ctl(options),
+        _ => { println!("{}", tr(USAGE)); Ok(()) },
+    };
+    match result {
+        Ok(()) => 0,
+        Err(e) => { eprintln!("{} {}\n\n{}", tr("Chyba:"), e, tr(USAGE)); 1 }
+    }
+}
+
+// Hodnota přepínače ve tvaru "--název hodnota"
+fn option<'a>(options: &'a [String], name: &str) -> Option<&'a str> {
+    options.iter().position(|o| o == name).and_then(|i| options.get(i + 1)).map(String::as_str)
+}
+
+fn date_option(options: &[String], name: &str) -> Result<NaiveDate, String> {
+    match option(options, name) {
+        None => Ok(Local::now().date_naive()),
+        Some(value) => NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| trf("neplatné datum '{}' (očekáváno RRRR-MM-DD)", &[&value])),
+    }
+}
+
+// Senzor z --device (MAC v libovolném zápisu), jinak hlavní
+fn device_option(options: &[String], config: &Config) -> Result<String, String> {
+    match option(options, "--device") {
+        None => Ok(config.target_mac.to_uppercase()),
+        Some(value) => normalize_mac(value).ok_or_else(|| trf("neplatná MAC adresa '{}'", &[&value])),
+    }
+}
+
+fn read_days(config: &Config, mac: &str, from: NaiveDate, to: NaiveDate) -> Vec<HistoryPoint> {
+    let filenames: Vec<String> = from.iter_days().take_while(|date| *date <= to).map(|date| config.log_filename(mac, date)).collect();
+    read_history_files(&filenames)
+}
+
+// Uložená měření podle --from, --to a --device (sdílí export a místní řízení)
+pub fn range_points(options: &[String], config: &Config) -> Result<Vec<HistoryPoint>, String> {
+    let (from, to, mac) = (date_option(options, "--from")?, date_option(options, "--to")?, device_option(options, config)?);
+    if from > to { return Err(tr("počáteční datum je až po koncovém").to_string()); }
+    Ok(read_days(config, &mac, from, to))
+}
+
+pub fn json_records(points: &[HistoryPoint]) -> Vec<serde_json::Value> {
+    points.iter().map(|p| serde_json::json!({ "timestamp": p.timestamp.to_rfc3339(), "temp": p.temp, "hum": p.hum })).collect()
+}
+
+// CSV ve formátu denního logu (°C), nebo JSON pole; bez --output na standardní výstup
+fn export(options: &[String], config: &Config) -> Result<(), String> {
+    let points = range_points(options, config)?;
+    let output = match option(options, "--format").unwrap_or("csv") {
+        "csv" => {
+            let mut wtr = csv::WriterBuilder::new().delimiter(b';').from_writer(Vec::new());
+            wtr.write_record(["Datum", "Cas", "Teplota", "Vlhkost"]).map_err(|e| e.to_string())?;
+            for p in &points {
+                wtr.write_record([p.timestamp.format("%Y.%m.%d").to_string(), p.timestamp.format("%H:%M:%S").to_string(), format!("{:.1}", p.temp).replace('.', ","), p.hum.to_string()]).map_err(|e| e.to_string())?;
+            }
+            wtr.into_inner().map_err(|e| e.to_string())?
+        },
+        "json" => {
+            let mut json = serde_json::to_vec_pretty(&json_records(&points)).map_err(|e| e.to_string())?;
+            json.push(b'\n');
+            json
+        },
+        format => return Err(trf("neznámý formát '{}' (csv nebo json)", &[&format])),
+    };
+    match option(options, "--output") {
+        Some(path) => { fs::write(path, output).map_err(|e| e.to_string())?; println!("{}", trf("Exportováno {} měření do '{}'.", &[&points.len(), &path])); },
+        None => std::io::stdout().write_all(&output).map_err(|e| e.to_string())?,
+    }
+    Ok(())
+}
+
+fn stats(options: &[String], config: &Config) -> Result<(), String> {
+    let (day, mac) = (date_option(options, "--day")?, device_option(options, config)?);
+    let points = read_days(config, &mac, day, day);
+    let Some(summary) = summarize_points(&points) else { return Err(trf("pro {} nejsou žádná měření", &[&day.format("%d.%m.%Y")])); };
+    println!("{} – {}", config.device_name(&mac), day.format("%d.%m.%Y"));
+    println!("{}", trf("Počet měření: {}", &[&summary.count]));
+    println!("{} {} / {} / {}", tr("Teplota (min / ø / max):"), format_temp(summary.min_temp, 1), format_temp(summary.avg_temp, 1), format_temp(summary.max_temp, 1));
+    println!("{} {:.0} / {:.1} / {:.0} %", tr("Vlhkost (min / ø / max):"), summary.min_hum, summary.avg_hum, summary.max_hum);
+    if let Some(mkt) = mean_kinetic_temperature(&points, config.mkt_activation_energy as f64) { println!("{} {}", tr("Střední kinetická teplota:"), format_temp(mkt, 1)); }
+    Ok(())
+}
+
+// Sledované senzory s počtem dní v logu a posledním uloženým měřením
+fn devices(config: &Config) {
+    for (name, mac) in config.all_devices() {
+        let dates = list_log_dates_with(&config.log_file_format(&mac));
+        let last = dates.last().and_then(|date| read_history_file(&config.log_filename(&mac, *date))?.pop());
+        let last = last.map_or_else(|| tr("Bez dat").to_string(), |p| format!("{} {}, {} %", p.timestamp.format("%d.%m.%Y %H:%M:%S"), format_temp(p.temp as f64, 1), p.hum));
+        println!("{}\t{}\t{}\t{}", mac, name, trf("dní v logu: {}", &[&dates.len()]), last);
+    }
+}
+
+// Pošle příkaz běžící aplikaci a vypíše odpověď; neúspěšný příkaz vrací chybový návratový kód
+fn ctl(options: &[String]) -> Result<(), String> {
+    if options.is_empty() { return Err(tr("chybí příkaz pro běžící aplikaci").to_string()); }
+    let reply = crate::instance::send(&options.join(" ")).map_err(|e| trf("aplikace neběží nebo neodpovídá ({})", &[&e]))?;
+    println!("{}", reply);
+    let ok = serde_json::from_str::<serde_json::Value>(&reply).ok().and_then(|reply| reply["ok"].as_bool()).unwrap_or(false);
+    if ok { Ok(()) } else { std::process::exit(1) }
+}
@@ -0,0 +1,43 @@
+// --- Kopírování obrázku do schránky Windows (formát CF_DIB) ---
+// egui umí do schránky vložit jen text, obrázek se proto předává přímo přes Win32 API.
+use eframe::egui::ColorImage;
+use std::io;
+use std::mem::size_of;
+use windows_sys::Win32::Foundation::GlobalFree;
+use windows_sys::Win32::Graphics::Gdi::{BITMAPINFOHEADER, BI_RGB};
+use windows_sys::Win32::System::DataExchange::{CloseClipboard, EmptyClipboard, OpenClipboard, SetClipboardData};
+use windows_sys::Win32::System::Memory::{GlobalAlloc, GlobalLock, GlobalUnlock, GMEM_MOVEABLE};
+use windows_sys::Win32::System::Ole::CF_DIB;
+
+pub fn copy_image(image: &ColorImage) -> io::Result<()> {
+    let [width, height] = image.size;
+    let header = BITMAPINFOHEADER {
+        biSize: size_of::<BITMAPINFOHEADER>() as u32, biWidth: width as i32, biHeight: height as i32, biPlanes: 1, biBitCount: 32,
+        biCompression: BI_RGB, biSizeImage: (width * height * 4) as u32, biXPelsPerMeter: 0, biYPelsPerMeter: 0, biClrUsed: 0, biClrImportant: 0,
+    };
+    let mut dib = Vec::with_capacity(size_of::<BITMAPINFOHEADER>() + width * height * 4);
+    dib.extend_from_slice(unsafe { std::slice::from_raw_parts(&header as *const BITMAPINFOHEADER as *const u8, size_of::<BITMAPINFOHEADER>()) });
+    // Řádky DIB jdou odspodu nahoru a pixely v pořadí BGRA
+    for row in image.pixels.chunks(width).rev() {
+        for pixel in row { dib.extend_from_slice(&[pixel.b(), pixel.g(), pixel.r(), pixel.a()]); }
+    }
+    unsafe {
+        if OpenClipboard(std::ptr::null_mut()) == 0 { return Err(io::Error::last_os_error()); }
+        let result = set_clipboard_dib(&dib);
+        CloseClipboard();
+        result
+    }
+}
+
+// Volá se s otevřenou schránkou; po úspěšném SetClipboardData paměť patří systému
+unsafe fn set_clipboard_dib(dib: &[u8]) -> io::Result<()> {
+    EmptyClipboard();
+    let memory = GlobalAlloc(GMEM_MOVEABLE, dib.len());
+    if memory.is_null() { return Err(io::Error::last_os_error()); }
+    let target = GlobalLock(memory) as *mut u8;
+    if target.is_null() { GlobalFree(memory); return Err(io::Error::last_os_error()); }
+    std::ptr::copy_nonoverlapping(dib.as_ptr(), target, dib.len());
+    GlobalUnlock(memory);
+    if SetClipboardData(CF_DIB as u32, memory).is_null() { GlobalFree(memory); return Err(io::Error::last_os_error()); }
+    Ok(())
+}
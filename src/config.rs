@@ -0,0 +1,528 @@
+// --- Konfigurace ---
+// Nastavení aplikace uložené v config.json vedle programu, včetně vzhledu řad v grafech.
+use crate::error::{Error, Result};
+use crate::i18n::{tr, Language};
+use crate::sinks::{default_outputs, OutputConfig};
+use crate::storage::LOG_FILE_FORMAT;
+use crate::units::TempUnit;
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime};
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime};
+
+pub const CONFIG_FILE: &str = "config.json";
+// Obnova okna v úsporném režimu (s); při práci s oknem se překresluje hned
+pub const LOW_POWER_REFRESH_SECS: u64 = 60;
+// Kolikrát delší pauza mezi skeny při úspoře na baterii (kontinuální režim se na baterii nepoužívá)
+pub const BATTERY_SCAN_PAUSE_FACTOR: u64 = 4;
+// Jak často se kontroluje, zda se config.json nezměnil zvenku
+pub const CONFIG_POLL_INTERVAL: Duration = Duration::from_secs(2);
+// Verze tvaru config.json; při přejmenování nebo přestavbě položek se zvýší a do MIGRATIONS přibude převod ze staré verze
+pub const CONFIG_VERSION: u32 = 2;
+// Sdílitelný balíček nastavení pro přenos na další počítač (Soubor → Exportovat nastavení, import přetažením do okna)
+pub const SETTINGS_EXPORT_FILE: &str = "nastaveni_export.json";
+const SETTINGS_BUNDLE_KIND: &str = "temp_monitor-settings";
+// Položky vázané na konkrétní počítač (cesty k souborům, spuštění po přihlášení); export je vynechá a import ponechá místní
+const MACHINE_SETTINGS: [&str; 4] = ["outputs", "json_log_file", "autostart", "start_minimized"];
+
+// Další senzor vedle hlavního (target_mac); jeho měření se ukládají do vlastních denních logů
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct DeviceConfig { pub name: String, pub mac: String }
+
+// Denní časové okno, kdy se skenuje (např. 06:00–22:00); okno může přecházet přes půlnoc, stejný začátek a konec
+// znamená celý den
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub struct ScanWindow { pub start: NaiveTime, pub end: NaiveTime }
+
+impl ScanWindow {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end { self.start == self.end || (self.start..self.end).contains(&time) } else { time >= self.start || time < self.end }
+    }
+}
+
+impl Default for ScanWindow {
+    fn default() -> Self { Self { start: NaiveTime::from_hms_opt(6, 0, 0).unwrap(), end: NaiveTime::from_hms_opt(22, 0, 0).unwrap() } }
+}
+
+// Pojmenovaná sada mezí a intervalů skenování (např. Léto, Zima, Dovolená); přepnutím se hodnoty zkopírují do nastavení
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct ConfigProfile {
+    pub name: String,
+    pub temp_warn_high: f32,
+    pub temp_warn_low: f32,
+    pub hum_warn_high: u8,
+    pub hum_warn_low: u8,
+    pub scan_timeout_secs: u64,
+    pub scan_pause_secs: u64,
+    pub continuous_mode: bool,
+}
+
+impl ConfigProfile {
+    // Profil s hodnotami z aktuálního nastavení
+    pub fn capture(name: &str, config: &Config) -> Self {
+        Self {
+            name: name.to_string(),
+            temp_warn_high: config.temp_warn_high,
+            temp_warn_low: config.temp_warn_low,
+            hum_warn_high: config.hum_warn_high,
+            hum_warn_low: config.hum_warn_low,
+            scan_timeout_secs: config.scan_timeout_secs,
+            scan_pause_secs: config.scan_pause_secs,
+            continuous_mode: config.continuous_mode,
+        }
+    }
+
+    fn apply(&self, config: &mut Config) {
+        config.temp_warn_high = self.temp_warn_high;
+        config.temp_warn_low = self.temp_warn_low;
+        config.hum_warn_high = self.hum_warn_high;
+        config.hum_warn_low = self.hum_warn_low;
+        config.scan_timeout_secs = self.scan_timeout_secs;
+        config.scan_pause_secs = self.scan_pause_secs;
+        config.continuous_mode = self.continuous_mode;
+    }
+}
+
+// Chybějící položky profilu (ručně psaný config.json) dostanou výchozí hodnoty nastavení, ne nuly
+impl Default for ConfigProfile {
+    fn default() -> Self { Self::capture("", &Config::default()) }
+}
+
+// Tvar bodů v grafech (vlastní výčet kvůli ukládání do config.json)
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum MarkerStyle { #[default] Circle, Square, Diamond, Cross, Hidden }
+
+impl MarkerStyle {
+    pub const ALL: [MarkerStyle; 5] = [MarkerStyle::Circle, MarkerStyle::Square, MarkerStyle::Diamond, MarkerStyle::Cross, MarkerStyle::Hidden];
+    pub fn label(self) -> &'static str {
+        tr(match self { MarkerStyle::Circle => "● Kolečko", MarkerStyle::Square => "■ Čtverec", MarkerStyle::Diamond => "◆ Kosočtverec", MarkerStyle::Cross => "✖ Křížek", MarkerStyle::Hidden => "Bez bodů" })
+    }
+    pub fn shape(self) -> Option<egui_plot::MarkerShape> {
+        use egui_plot::MarkerShape;
+        match self { MarkerStyle::Circle => Some(MarkerShape::Circle), MarkerStyle::Square => Some(MarkerShape::Square), MarkerStyle::Diamond => Some(MarkerShape::Diamond), MarkerStyle::Cross => Some(MarkerShape::Cross), MarkerStyle::Hidden => None }
+    }
+}
+
+// Vzhled jedné řady v grafech; bez barvy bodů se body barví podle naměřené hodnoty
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct SeriesStyle { pub line: [u8; 3], pub points: Option<[u8; 3]>, pub marker: MarkerStyle }
+
+impl SeriesStyle {
+    pub fn line_color(&self) -> egui::Color32 { egui::Color32::from_rgb(self.line[0], self.line[1], self.line[2]) }
+    pub fn point_color(&self) -> Option<egui::Color32> { self.points.map(|[r, g, b]| egui::Color32::from_rgb(r, g, b)) }
+}
+
+// Předvolby barev; kromě výchozí jsou z palety Okabe–Ito, rozlišitelné i při poruchách barvocitu
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum StylePreset { Default, OkabeIto, BlueOrange }
+
+impl StylePreset {
+    pub const ALL: [StylePreset; 3] = [StylePreset::Default, StylePreset::OkabeIto, StylePreset::BlueOrange];
+    pub fn label(self) -> &'static str {
+        tr(match self { StylePreset::Default => "Výchozí", StylePreset::OkabeIto => "Pro barvoslepé (rumělková / modrá)", StylePreset::BlueOrange => "Pro barvoslepé (oranžová / nebeská)" })
+    }
+    // (teplota, vlhkost)
+    pub fn styles(self) -> (SeriesStyle, SeriesStyle) {
+        match self {
+            StylePreset::Default => (SeriesStyle { line: [255, 100, 100], points: None, marker: MarkerStyle::Circle }, SeriesStyle { line: [100, 100, 255], points: None, marker: MarkerStyle::Circle }),
+            StylePreset::OkabeIto => (SeriesStyle { line: [213, 94, 0], points: Some([213, 94, 0]), marker: MarkerStyle::Circle }, SeriesStyle { line: [0, 114, 178], points: Some([0, 114, 178]), marker: MarkerStyle::Square }),
+            StylePreset::BlueOrange => (SeriesStyle { line: [230, 159, 0], points: Some([230, 159, 0]), marker: MarkerStyle::Diamond }, SeriesStyle { line: [86, 180, 233], points: Some([86, 180, 233]), marker: MarkerStyle::Circle }),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct Config {
+    // Verze tvaru souboru, viz CONFIG_VERSION; soubory bez ní jsou verze 0
+    pub version: u32,
+    pub language: Language,
+    pub temp_unit: TempUnit,
+    pub target_mac: String,
+    pub scan_timeout_secs: u64,
+    pub scan_pause_secs: u64,
+    pub duplicate_threshold_secs: u64,
+    pub temp_warn_high: f32,
+    pub temp_warn_low: f32,
+    // Meze vlhkosti (%) pro obarvení hodnoty a čáry v grafu, obdobně jako u teploty
+    pub hum_warn_high: u8,
+    pub hum_warn_low: u8,
+    pub continuous_mode: bool,
+    // Kompletní historie = celý dnešní log; s `load_older_logs` i všechny starší denní logy (vícedenní přehled)
+    pub load_all_history: bool,
+    pub load_older_logs: bool,
+    // Se staršími logy drží v paměti jen tolik posledních dní (0 = vše), starší se při posunu grafu čtou z disku
+    pub history_memory_days: u32,
+    // Filtr nesmyslných hodnot (poškozené pakety)
+    pub spike_filter_enabled: bool,
+    pub temp_valid_min: f32,
+    pub temp_valid_max: f32,
+    pub max_temp_jump: f32,
+    pub max_hum_jump: u8,
+    // Krátkodobá předpověď z trendu posledních minut
+    pub forecast_enabled: bool,
+    pub forecast_minutes: u32,
+    pub forecast_window_minutes: u32,
+    // Základní teplota pro výpočet denostupňů (vytápění / chlazení)
+    pub degree_day_base: f32,
+    // Pásmo komfortu vykreslené za křivkami
+    pub show_comfort_band: bool,
+    pub comfort_temp_min: f32,
+    pub comfort_temp_max: f32,
+    pub comfort_hum_min: u8,
+    pub comfort_hum_max: u8,
+    // Aktivační energie pro střední kinetickou teplotu (MKT), kJ/mol
+    pub mkt_activation_energy: f32,
+    // Detekce anomálií pomocí klouzavého z-skóre
+    pub anomaly_detection: bool,
+    pub anomaly_window: usize,
+    pub anomaly_z_threshold: f32,
+    pub anomaly_alert: bool,
+    // Rovnovážná vlhkost dřeva (EMC) v záhlaví a jako sloupec v nových denních logech
+    pub emc_enabled: bool,
+    // Ikona v oznamovací oblasti s posledním měřením (jen Windows)
+    pub tray_icon: bool,
+    // Minimalizace a zavření okna jen schovají aplikaci do oznamovací oblasti, skenování a logování běží dál
+    pub minimize_to_tray: bool,
+    pub close_to_tray: bool,
+    // Aktuální teplota v ikoně okna na hlavním panelu (jen Windows)
+    pub taskbar_badge: bool,
+    // Okno zůstává nad ostatními okny (malý odečet vedle jiné práce)
+    pub always_on_top: bool,
+    // Spuštění po přihlášení do Windows, volitelně rovnou schované v oznamovací oblasti
+    pub autostart: bool,
+    pub start_minimized: bool,
+    // Jak často se okno bez nových měření samo překreslí (s); úsporný režim ho obnovuje jen jednou za minutu
+    pub refresh_secs: u64,
+    pub low_power: bool,
+    // Na baterii (notebook odpojený od sítě) skenovat řidčeji, obnovovat okno jako v úsporném režimu a neobnovovat
+    // teplotu v ikoně na hlavním panelu; po připojení k síti se vše vrátí
+    pub battery_saver: bool,
+    // Zda počítač právě běží z baterie; neukládá se, zjišťuje ho power::monitor a rozesílá sdílenou konfigurací
+    #[serde(skip)]
+    pub on_battery: bool,
+    // Vysoký kontrast: neprůhledné černé pozadí, bílý text, silnější čáry a větší body v grafech
+    pub high_contrast: bool,
+    // Doba zobrazení oznámení (s); varování a chyby mají vlastní, delší dobu
+    pub toast_secs: f32,
+    pub error_toast_secs: f32,
+    // Od těchto hodnot RSSI (dBm) svítí 2., 3. a 4. dílek ukazatele signálu; první svítí vždy
+    pub rssi_bar_thresholds: [i16; 3],
+    // Pod těmito úrovněmi baterie (%) se ukazatel zbarví žlutě, resp. červeně
+    pub battery_warn_level: u8,
+    pub battery_critical_level: u8,
+    // Kam se zapisují uložená měření a upozornění (denní CSV, JSON Lines, …), viz modul sinks
+    pub outputs: Vec<OutputConfig>,
+    // Protokol aplikace navíc jako JSON Lines do tohoto souboru (prázdné = vypnuto), pro sběr do Loki/Elastic
+    pub json_log_file: String,
+    // Barvy a tvary bodů hlavních řad v grafech
+    pub temp_style: SeriesStyle,
+    pub hum_style: SeriesStyle,
+    pub extra_devices: Vec<DeviceConfig>,
+    // Časová okna skenování; mimo ně skener nepracuje (prázdné = skenuje se nepřetržitě)
+    pub scan_windows: Vec<ScanWindow>,
+    // Pojmenované profily a název naposledy zapnutého (prázdné = žádný)
+    pub profiles: Vec<ConfigProfile>,
+    pub active_profile: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            version: CONFIG_VERSION,
+            language: Language::default(),
+            temp_unit: TempUnit::default(),
+            target_mac: "B8:59:CE:33:0F:93".to_string(),
+            scan_timeout_secs: 20,
+            scan_pause_secs: 20,
+            duplicate_threshold_secs: 30,
+            temp_warn_high: 30.0,
+            temp_warn_low: 10.0,
+            hum_warn_high: 70,
+            hum_warn_low: 30,
+            continuous_mode: true,
+            load_all_history: true,
+            load_older_logs: false,
+            history_memory_days: 31,
+            spike_filter_enabled: true,
+            temp_valid_min: -40.0,
+            temp_valid_max: 70.0,
+            max_temp_jump: 5.0,
+            max_hum_jump: 25,
+            forecast_enabled: false,
+            forecast_minutes: 60,
+            forecast_window_minutes: 30,
+            degree_day_base: 18.0,
+            show_comfort_band: false,
+            comfort_temp_min: 20.0,
+            comfort_temp_max: 24.0,
+            comfort_hum_min: 40,
+            comfort_hum_max: 60,
+            mkt_activation_energy: 83.144,
+            anomaly_detection: false,
+            anomaly_window: 30,
+            anomaly_z_threshold: 4.0,
+            anomaly_alert: false,
+            emc_enabled: false,
+            tray_icon: true,
+            taskbar_badge: true,
+            minimize_to_tray: false,
+            close_to_tray: false,
+            always_on_top: false,
+            autostart: false,
+            start_minimized: false,
+            rssi_bar_thresholds: [-85, -75, -65],
+            battery_warn_level: 60,
+            battery_critical_level: 20,
+            refresh_secs: 10,
+            low_power: false,
+            battery_saver: true,
+            on_battery: false,
+            high_contrast: false,
+            toast_secs: 3.0,
+            error_toast_secs: 10.0,
+            outputs: default_outputs(),
+            json_log_file: String::new(),
+            temp_style: StylePreset::Default.styles().0,
+            hum_style: StylePreset::Default.styles().1,
+            extra_devices: Vec::new(),
+            scan_windows: Vec::new(),
+            profiles: Vec::new(),
+            active_profile: String::new(),
+        }
+    }
+}
+
+impl Config {
+    // Násobek tloušťky čar a velikosti bodů v grafech
+    pub fn plot_scale(&self) -> f32 { if self.high_contrast { 2.0 } else { 1.0 } }
+    // Barva hodnoty vlhkosti: nad horní mezí zlatá, pod spodní světle modrá (stejně jako u teploty)
+    pub fn hum_color(&self, hum: u8) -> egui::Color32 {
+        if hum > self.hum_warn_high { egui::Color32::GOLD } else if hum < self.hum_warn_low { egui::Color32::from_rgb(120, 180, 255) } else { egui::Color32::from_rgb(100, 100, 255) }
+    }
+    pub fn loads_older_logs(&self) -> bool { self.load_all_history && self.load_older_logs }
+    pub fn is_primary(&self, mac: &str) -> bool { mac.eq_ignore_ascii_case(&self.target_mac) }
+    // Všechny sledované senzory jako (název, MAC), hlavní první
+    pub fn all_devices(&self) -> Vec<(String, String)> {
+        let mut devices = vec![(tr("Hlavní senzor").to_string(), self.target_mac.to_uppercase())];
+        for device in self.extra_devices.iter().filter(|d| !d.mac.trim().is_empty()) {
+            let mac = device.mac.trim().to_uppercase();
+            if devices.iter().any(|(_, m)| *m == mac) { continue; }
+            devices.push((if device.name.trim().is_empty() { mac.clone() } else { device.name.clone() }, mac));
+        }
+        devices
+    }
+    pub fn device_name(&self, mac: &str) -> String {
+        self.all_devices().into_iter().find(|(_, m)| m.eq_ignore_ascii_case(mac)).map_or_else(|| mac.to_string(), |(name, _)| name)
+    }
+    // Hlavní senzor zapisuje do původních logů, ostatní do log_<MAC>_<datum>.csv
+    pub fn log_filename(&self, mac: &str, date: NaiveDate) -> String { date.format(&self.log_file_format(mac)).to_string() }
+    pub fn log_file_format(&self, mac: &str) -> String {
+        if self.is_primary(mac) { LOG_FILE_FORMAT.to_string() } else { format!("log_{}_%Y-%m-%d.csv", mac.replace([':', '-'], "").to_uppercase()) }
+    }
+    // Interval překreslování GUI; v úsporném režimu se překresluje zřídka
+    pub fn refresh_interval(&self) -> Duration {
+        Duration::from_secs(if self.low_power || self.battery_saving() { LOW_POWER_REFRESH_SECS } else { self.refresh_secs.max(1) })
+    }
+    // Úspora na baterii: zapnutá volba a počítač odpojený od sítě
+    pub fn battery_saving(&self) -> bool { self.battery_saver && self.on_battery }
+    // Očekávaný rozestup zapsaných měření: v kontinuálním režimu určuje interval pro duplikáty, jinak i pauza mezi skeny
+    pub fn expected_interval_secs(&self) -> u64 {
+        let (continuous, _, pause) = self.scan_timing();
+        if continuous { self.duplicate_threshold_secs.max(1) } else { self.duplicate_threshold_secs.max(pause).max(1) }
+    }
+    // Skutečný režim skenování: (kontinuální, délka skenu, pauza mezi skeny) v s; kontinuální režim skenuje po minutách
+    // s vteřinovou pauzou, úspora na baterii ho vypne a pauzu prodlouží
+    pub fn scan_timing(&self) -> (bool, u64, u64) {
+        if self.battery_saving() { (false, self.scan_timeout_secs, self.scan_pause_secs.max(1) * BATTERY_SCAN_PAUSE_FACTOR) }
+        else if self.continuous_mode { (true, 60, 1) } else { (false, self.scan_timeout_secs, self.scan_pause_secs) }
+    }
+    pub fn in_scan_window(&self, now: NaiveDateTime) -> bool { self.scan_windows.is_empty() || self.scan_windows.iter().any(|w| w.contains(now.time())) }
+
+    // Nejbližší začátek nebo konec některého okna, kdy se skener má znovu rozhodnout, zda skenovat (None bez oken).
+    // Počítá se v místním čase na hodinách, po změně času na letní/zimní se skener nejvýš jednou probudí navíc.
+    pub fn next_scan_window_change(&self, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        self.scan_windows.iter().flat_map(|w| [w.start, w.end]).map(|time| {
+            let at = now.date().and_time(time);
+            if at > now { at } else { at + chrono::Duration::days(1) }
+        }).min()
+    }
+
+    // Přepne na profil podle názvu; false, pokud takový profil není
+    pub fn switch_profile(&mut self, name: &str) -> bool {
+        let Some(profile) = self.profiles.iter().find(|p| p.name == name).cloned() else { return false; };
+        profile.apply(self);
+        self.active_profile = profile.name;
+        true
+    }
+
+    // Zapnutý profil, jehož hodnoty uživatel od přepnutí změnil (v menu se označí, změny lze do profilu uložit)
+    pub fn profile_modified(&self) -> bool {
+        self.profiles.iter().find(|p| p.name == self.active_profile).is_some_and(|p| *p != ConfigProfile::capture(&p.name, self))
+    }
+
+    // Adresy z ručně upraveného config.json převede na tvar, ve kterém je hlásí skener
+    pub fn normalize_macs(&mut self) {
+        if let Some(mac) = normalize_mac(&self.target_mac) { self.target_mac = mac; }
+        for device in &mut self.extra_devices { if let Some(mac) = normalize_mac(&device.mac) { device.mac = mac; } }
+    }
+}
+
+// Přijme „b8:59:ce:33:0f:93“, „B8-59-CE-33-0F-93“ i „B859CE330F93“; vrací velká písmena s dvojtečkami.
+// Na macOS se senzor zadává UUID periferie („1B2C3D4E-5F60-7182-93A4-B5C6D7E8F901“), to se vrací velkými písmeny.
+pub fn normalize_mac(input: &str) -> Option<String> {
+    let input = input.trim();
+    let uuid_groups: Vec<&str> = input.split('-').collect();
+    if uuid_groups.iter().map(|g| g.len()).eq([8, 4, 4, 4, 12]) && uuid_groups.iter().all(|g| g.chars().all(|c| c.is_ascii_hexdigit())) { return Some(input.to_uppercase()); }
+    let groups: Vec<&str> = if input.contains([':', '-']) { input.split([':', '-']).collect() } else { (0..input.len()).step_by(2).filter_map(|i| input.get(i..i + 2)).collect() };
+    let valid = input.len() == if input.contains([':', '-']) { 17 } else { 12 } && groups.len() == 6 && groups.iter().all(|g| g.len() == 2 && g.chars().all(|c| c.is_ascii_hexdigit()));
+    valid.then(|| groups.join(":").to_uppercase())
+}
+
+// Konfigurace jinde než config.json v pracovním adresáři (parametr --config nebo přenosný režim), vždy absolutní cesta
+static CUSTOM_CONFIG_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+pub fn set_custom_config_path(path: PathBuf) { let _ = CUSTOM_CONFIG_PATH.set(path); }
+
+pub fn custom_config_path() -> Option<&'static Path> { CUSTOM_CONFIG_PATH.get().map(PathBuf::as_path) }
+
+pub fn config_path() -> &'static Path { custom_config_path().unwrap_or(Path::new(CONFIG_FILE)) }
+
+// Převody tvaru konfigurace: položka i převádí soubor verze i na verzi i + 1 (přejmenování, přesuny položek apod.)
+const MIGRATIONS: [fn(&mut Map<String, Value>); CONFIG_VERSION as usize] = [
+    // 0 → 1: soubory z doby před číslováním verzí mají stejný tvar, jen se doplní číslo verze
+    |_| {},
+    // 1 → 2: výchozí obnovení okna se změnilo z 1 s na 10 s (okno se po novém měření překreslí samo); uložená
+    // hodnota je volba uživatele, proto zůstává a jen se připomene v protokolu
+    |fields| if fields.get("refresh_secs").and_then(Value::as_u64) == Some(1) { info!("Okno se obnovuje každou sekundu; výchozí je nově 10 s (nastavení Zobrazení)."); },
+];
+
+fn backup_path() -> PathBuf { PathBuf::from(format!("{}.bak", config_path().display())) }
+
+// Záloha původního souboru před tím, než ho aplikace přepíše převedeným nebo výchozím nastavením
+fn backup_config(reason: &str) {
+    match fs::copy(config_path(), backup_path()) {
+        Ok(_) => warn!("{}; původní konfigurace zůstává v '{}'.", reason, backup_path().display()),
+        Err(e) => error!("{}; zálohu '{}' nelze vytvořit: {}", reason, backup_path().display(), e),
+    }
+}
+
+pub fn load_config() -> Config {
+    info!("Načítám konfiguraci z '{}'.", config_path().display());
+    match read_config() {
+        Ok(config) => config,
+        Err(Error::Io { source, .. }) if source.kind() == std::io::ErrorKind::NotFound => Config::default(),
+        Err(e) => { backup_config(&format!("Konfiguraci nelze načíst ({}), používám výchozí nastavení", e)); Config::default() },
+    }
+}
+
+// Na rozdíl od load_config hlásí chybu (poškozený nebo rozepsaný soubor) místo návratu k výchozímu nastavení
+pub fn read_config() -> Result<Config> {
+    let content = fs::read_to_string(config_path()).map_err(|e| Error::io(config_path(), e))?;
+    let (config, changed) = parse_config(&content)?;
+    if let Some(reason) = changed {
+        backup_config(&reason);
+        // Převedený soubor se uloží hned, aby se převod neopakoval při každém spuštění; soubor s neplatnými
+        // položkami se nechá být (uživatel ho možná právě upravuje), přepíše se až při změně nastavení
+        if config_version(&content) < CONFIG_VERSION {
+            if let Err(e) = save_config(&config) { error!("Převedenou konfiguraci nelze uložit: {}", e); }
+        }
+    }
+    Ok(config)
+}
+
+fn config_version(content: &str) -> u32 {
+    serde_json::from_str::<Value>(content).ok().and_then(|value| value.get("version")?.as_u64()).unwrap_or(0) as u32
+}
+
+// Obsah config.json převedený na aktuální verzi. Položky, které ani po převodu nejdou přečíst (změněný typ, překlep
+// v hodnotě), dostanou výchozí hodnotu místo zahození celého souboru. Druhá hodnota popisuje, co se změnilo.
+pub fn parse_config(content: &str) -> Result<(Config, Option<String>)> {
+    let Value::Object(mut fields) = serde_json::from_str(content)? else { return Ok((serde_json::from_str(content)?, None)); };
+    let version = config_version(content);
+    let mut changes = Vec::new();
+    if version > CONFIG_VERSION {
+        warn!("Konfigurace je z novější verze aplikace ({} > {}), neznámá nastavení se při uložení ztratí.", version, CONFIG_VERSION);
+    } else if version < CONFIG_VERSION {
+        for migrate in &MIGRATIONS[version as usize..] { migrate(&mut fields); }
+        changes.push(format!("Konfigurace převedena z verze {} na {}", version, CONFIG_VERSION));
+    }
+    fields.insert("version".into(), CONFIG_VERSION.into());
+    let mut config: Config = match serde_json::from_value(Value::Object(fields.clone())) {
+        Ok(config) => config,
+        Err(_) => {
+            let Value::Object(mut merged) = serde_json::to_value(Config::default())? else { unreachable!("Config se serializuje jako objekt") };
+            for (key, value) in fields {
+                let mut candidate = merged.clone();
+                candidate.insert(key.clone(), value);
+                if serde_json::from_value::<Config>(Value::Object(candidate.clone())).is_ok() { merged = candidate; } else {
+                    warn!("Položka '{}' v konfiguraci je neplatná, použije se výchozí hodnota.", key);
+                    changes.push(format!("neplatná položka '{}' nahrazena výchozí hodnotou", key));
+                }
+            }
+            serde_json::from_value(Value::Object(merged))?
+        }
+    };
+    config.normalize_macs();
+    Ok((config, (!changes.is_empty()).then(|| changes.join(", "))))
+}
+
+// Balíček: druh souboru, čas exportu a nastavení bez položek vázaných na počítač (verze zůstává kvůli převodu při importu)
+pub fn export_settings(config: &Config, path: &Path) -> Result<()> {
+    let Value::Object(mut settings) = serde_json::to_value(config)? else { unreachable!("Config se serializuje jako objekt") };
+    for key in MACHINE_SETTINGS { settings.remove(key); }
+    let bundle = serde_json::json!({ "kind": SETTINGS_BUNDLE_KIND, "exported": Local::now().to_rfc3339(), "settings": settings });
+    fs::write(path, serde_json::to_string_pretty(&bundle)?).map_err(|e| Error::io(path, e))
+}
+
+// Rozpozná balíček nastavení (přetažený JSON může být i něco jiného, třeba export měření)
+pub fn is_settings_bundle(content: &str) -> bool {
+    serde_json::from_str::<Value>(content).is_ok_and(|value| value.get("kind").and_then(Value::as_str) == Some(SETTINGS_BUNDLE_KIND))
+}
+
+// Nastavení z balíčku převedené stejně jako config.json (starší verze, neplatné položky); místní položky zůstanou
+// z `current`. Původní config.json se před přepsáním zazálohuje. Druhá hodnota popisuje, co se při převodu změnilo.
+pub fn import_settings(content: &str, current: &Config) -> Result<(Config, Option<String>)> {
+    if !is_settings_bundle(content) { return Err(Error::Import(tr("soubor není export nastavení této aplikace").to_string())); }
+    let bundle: Value = serde_json::from_str(content)?;
+    let Some(Value::Object(mut settings)) = bundle.get("settings").cloned() else { return Err(Error::Import(tr("v exportu chybí nastavení").to_string())); };
+    let Value::Object(local) = serde_json::to_value(current)? else { unreachable!("Config se serializuje jako objekt") };
+    for key in MACHINE_SETTINGS {
+        if let Some(value) = local.get(key) { settings.insert(key.into(), value.clone()); }
+    }
+    let imported = parse_config(&Value::Object(settings).to_string())?;
+    if config_path().exists() { backup_config("Nastavení nahrazeno importem"); }
+    Ok(imported)
+}
+
+fn config_modified() -> Option<SystemTime> { fs::metadata(config_path()).and_then(|m| m.modified()).ok() }
+
+// Hlídá úpravy config.json mimo aplikaci (ručně, nástrojem pro správu konfigurace) podle času poslední změny souboru
+pub struct ConfigWatcher { modified: Option<SystemTime> }
+
+impl ConfigWatcher {
+    pub fn new() -> Self { Self { modified: config_modified() } }
+    // Vlastní zápis se pak nehlásí jako změna zvenku
+    pub fn mark_saved(&mut self) { self.modified = config_modified(); }
+    // Obsah souboru, pokud se od minula změnil; chyba čtení se hlásí jen jednou za změnu
+    pub fn poll(&mut self) -> Option<Result<Config>> {
+        let modified = config_modified();
+        if modified.is_none() || modified == self.modified { return None; }
+        self.modified = modified;
+        Some(read_config())
+    }
+}
+
+impl Default for ConfigWatcher {
+    fn default() -> Self { Self::new() }
+}
+pub fn save_config(config: &Config) -> Result<()> {
+    let content = serde_json::to_string_pretty(config)?;
+    fs::write(config_path(), content).map_err(|e| Error::io(config_path(), e))
+}
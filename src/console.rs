@@ -0,0 +1,125 @@
+// --- Konzole varování a chyb ---
+// Logger, který si varování a chyby (aplikace a Bluetooth knihovny) drží v paměti pro panel v okně
+// a informační zprávy navíc pro okno s protokolem aplikace.
+// V debug buildu navíc vše předává env_loggeru na stderr jako dřív.
+// Volitelně zapisuje tytéž záznamy i jako JSON Lines do souboru (json_log_file v konfiguraci) pro Loki, Elastic apod.
+use chrono::{DateTime, Local, SecondsFormat};
+use log::kv::{Key, Value, VisitSource};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use serde_json::{json, Map};
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::sync::Mutex;
+
+// Nejstarší záznamy nad tento počet se zahazují
+const MAX_ENTRIES: usize = 500;
+// Protokol (včetně informací) je delší, aby pokryl celé skenování a zápis od startu
+const MAX_LOG_ENTRIES: usize = 2000;
+// Zdroje, jejichž varování patří do konzole (ostatní knihovny, např. grafika, by ji zahltily)
+const CAPTURED_TARGETS: [&str; 2] = ["temp_monitor", "btleplug"];
+
+#[derive(Clone)]
+pub struct ConsoleEntry { pub timestamp: DateTime<Local>, pub level: Level, pub message: String }
+
+static ENTRIES: Mutex<VecDeque<ConsoleEntry>> = Mutex::new(VecDeque::new());
+static LOG: Mutex<VecDeque<ConsoleEntry>> = Mutex::new(VecDeque::new());
+// Počet všech dosud zachycených záznamů, aby UI poznalo nové i po zahození starých
+static TOTAL: Mutex<usize> = Mutex::new(0);
+// Otevřený soubor JSON Lines a jeho cesta z konfigurace
+static JSON_LOG: Mutex<Option<(String, File)>> = Mutex::new(None);
+
+struct ConsoleLogger { inner: Option<env_logger::Logger> }
+
+impl Log for ConsoleLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Info || self.inner.as_ref().is_some_and(|inner| inner.enabled(metadata))
+    }
+
+    fn log(&self, record: &Record) {
+        if record.level() <= Level::Info && CAPTURED_TARGETS.iter().any(|target| record.target().starts_with(target)) {
+            let entry = ConsoleEntry { timestamp: Local::now(), level: record.level(), message: record.args().to_string() };
+            if record.level() <= Level::Warn {
+                let mut entries = ENTRIES.lock().unwrap();
+                if entries.len() == MAX_ENTRIES { entries.pop_front(); }
+                entries.push_back(entry.clone());
+                *TOTAL.lock().unwrap() += 1;
+            }
+            write_json(&entry, record);
+            let mut log = LOG.lock().unwrap();
+            if log.len() == MAX_LOG_ENTRIES { log.pop_front(); }
+            log.push_back(entry);
+        }
+        if let Some(inner) = &self.inner { if inner.matches(record) { inner.log(record); } }
+    }
+
+    fn flush(&self) {
+        if let Some(inner) = &self.inner { inner.flush(); }
+    }
+}
+
+pub fn init(inner: Option<env_logger::Logger>) {
+    let max_level = inner.as_ref().map_or(LevelFilter::Info, |inner| inner.filter().max(LevelFilter::Info));
+    if log::set_boxed_logger(Box::new(ConsoleLogger { inner })).is_ok() { log::set_max_level(max_level); }
+}
+
+// Páry klíč = hodnota předané makru (např. `info!(points = n; "…")`) jako objekt "fields"; čísla a pravdivostní
+// hodnoty zůstanou v JSON čísly, aby šly v Lokim či Elasticu filtrovat a sčítat
+struct JsonFields(Map<String, serde_json::Value>);
+
+impl<'kvs> VisitSource<'kvs> for JsonFields {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), log::kv::Error> {
+        let value = if let Some(value) = value.to_bool() { value.into() } else if let Some(value) = value.to_i64() { value.into() } else if let Some(value) = value.to_u64() { value.into() }
+            // Přes text, aby f32 21,3 nezůstalo v JSON jako 21.299999237060547
+            else if value.to_f64().is_some() { value.to_string().parse::<f64>().map_or_else(|_| value.to_string().into(), |value| json!(value)) }
+            else { value.to_string().into() };
+        self.0.insert(key.to_string(), value);
+        Ok(())
+    }
+}
+
+fn write_json(entry: &ConsoleEntry, record: &Record) {
+    let mut json_log = JSON_LOG.lock().unwrap();
+    let Some((path, file)) = json_log.as_mut() else { return; };
+    let mut fields = JsonFields(Map::new());
+    let _ = record.key_values().visit(&mut fields);
+    let line = json!({
+        "timestamp": entry.timestamp.to_rfc3339_opts(SecondsFormat::Millis, false),
+        "level": entry.level.as_str(),
+        "module": record.module_path().unwrap_or(record.target()),
+        "message": entry.message,
+        "fields": fields.0,
+    });
+    // Chybu zápisu nejde zalogovat (zacyklilo by se to), soubor se proto zavře a ohlásí jen na stderr
+    if let Err(e) = writeln!(file, "{}", line) {
+        eprintln!("Zápis protokolu JSON do '{}' selhal, vypínám ho: {}", path, e);
+        *json_log = None;
+    }
+}
+
+// Zapne zápis protokolu jako JSON Lines do souboru (připisuje na konec), prázdná cesta ho vypne
+pub fn set_json_log(path: &str) -> io::Result<()> {
+    let mut json_log = JSON_LOG.lock().unwrap();
+    if json_log.as_ref().map_or("", |(current, _)| current.as_str()) == path { return Ok(()); }
+    *json_log = None;
+    if !path.is_empty() { *json_log = Some((path.to_string(), OpenOptions::new().append(true).create(true).open(path)?)); }
+    Ok(())
+}
+
+pub fn entries() -> Vec<ConsoleEntry> { ENTRIES.lock().unwrap().iter().cloned().collect() }
+
+pub fn total() -> usize { *TOTAL.lock().unwrap() }
+
+pub fn clear() { ENTRIES.lock().unwrap().clear(); }
+
+// Posledních `count` záznamů protokolu pro hlášení o pádu; při panice uvnitř loggeru by čekání na zámek zablokovalo hook
+pub fn log_tail(count: usize) -> Vec<ConsoleEntry> {
+    let Ok(log) = LOG.try_lock() else { return Vec::new(); };
+    log.iter().skip(log.len().saturating_sub(count)).cloned().collect()
+}
+
+// Záznamy protokolu do zadané úrovně, jejichž text obsahuje hledaný výraz (bez ohledu na velikost písmen)
+pub fn log_entries(max_level: Level, search: &str) -> Vec<ConsoleEntry> {
+    let search = search.to_lowercase();
+    LOG.lock().unwrap().iter().filter(|e| e.level <= max_level && (search.is_empty() || e.message.to_lowercase().contains(&search))).cloned().collect()
+}
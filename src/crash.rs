@@ -0,0 +1,50 @@
+// --- Hlášení o pádu ---
+// Při panice zapíše report (zpráva, backtrace, verze, konec protokolu) do složky dat a okno ho nabídne při příštím startu;
+// release build na Windows nemá konzoli, takže by jinak jen beze stopy zmizel.
+use crate::console;
+use chrono::Local;
+use std::backtrace::Backtrace;
+use std::fs;
+use std::panic::PanicHookInfo;
+use std::path::PathBuf;
+
+const CRASH_DIR: &str = "pady";
+// Obsahuje cestu k reportu, který se uživateli ještě nenabídl
+const PENDING_FILE: &str = "pady/nezobrazeny.txt";
+// Kolik posledních záznamů protokolu se přiloží
+const LOG_TAIL: usize = 200;
+
+// Původní hook se volá i nadále, takže zpráva o panice dál jde na stderr
+pub fn install_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        match write_report(info) {
+            Ok(path) => eprintln!("Hlášení o pádu uloženo do '{}'.", path.display()),
+            Err(e) => eprintln!("Hlášení o pádu nelze uložit: {}", e),
+        }
+        default_hook(info);
+    }));
+}
+
+fn write_report(info: &PanicHookInfo) -> std::io::Result<PathBuf> {
+    let now = Local::now();
+    let message = info.payload().downcast_ref::<&str>().copied().or_else(|| info.payload().downcast_ref::<String>().map(String::as_str)).unwrap_or("(neznámá chyba)");
+    let location = info.location().map_or(String::new(), |l| format!("{}:{}:{}", l.file(), l.line(), l.column()));
+    let thread = std::thread::current().name().unwrap_or("(bez názvu)").to_owned();
+    let mut report = format!("Teploměr – hlášení o pádu\nČas: {}\nVerze: {} (sestaveno {})\nSystém: {} {}\nVlákno: {}\nChyba: {}\nMísto: {}\n\nBacktrace:\n{}\n\nPosledních {} záznamů protokolu:\n",
+        now.format("%d.%m.%Y %H:%M:%S"), env!("CARGO_PKG_VERSION"), env!("BUILD_DATE"), std::env::consts::OS, std::env::consts::ARCH, thread, message, location, Backtrace::force_capture(), LOG_TAIL);
+    for entry in console::log_tail(LOG_TAIL) { report += &format!("{} [{}] {}\n", entry.timestamp.format("%d.%m.%Y %H:%M:%S"), entry.level, entry.message); }
+    fs::create_dir_all(CRASH_DIR)?;
+    let path = std::path::absolute(PathBuf::from(CRASH_DIR).join(format!("pad_{}.txt", now.format("%Y-%m-%d_%H%M%S"))))?;
+    fs::write(&path, report)?;
+    fs::write(PENDING_FILE, path.display().to_string())?;
+    Ok(path)
+}
+
+// Report z minulého běhu, který se ještě nenabídl; nabídne se jen jednou
+pub fn take_pending_report() -> Option<PathBuf> {
+    let path = fs::read_to_string(PENDING_FILE).ok()?;
+    let _ = fs::remove_file(PENDING_FILE);
+    let path = PathBuf::from(path.trim());
+    path.exists().then_some(path)
+}
@@ -0,0 +1,54 @@
+// --- Společný typ chyby ---
+// Chyby skeneru, ukládání a konfigurace se předávají až do okna (stavový řádek, konzole) místo tichého zahození.
+use crate::i18n::tr;
+use thiserror::Error;
+
+// Rady, co s Bluetooth udělat, podle systému (na Linuxu konkrétní příkazy pro BlueZ)
+#[cfg(target_os = "linux")]
+const ADAPTER_OFF_HINT: &str = "Bluetooth je vypnutý. Zapněte ho v nastavení systému nebo příkazem 'bluetoothctl power on'.";
+#[cfg(not(target_os = "linux"))]
+const ADAPTER_OFF_HINT: &str = "Bluetooth je vypnutý. Zapněte ho v nastavení systému.";
+#[cfg(target_os = "linux")]
+const PERMISSION_HINT: &str = "Chybí oprávnění k Bluetooth. Přidejte uživatele do skupiny bluetooth ('sudo usermod -aG bluetooth $USER') a přihlaste se znovu.";
+// macOS se na oprávnění ptá jen jednou, zamítnutí se pak dá změnit jen v nastavení soukromí
+#[cfg(target_os = "macos")]
+const PERMISSION_HINT: &str = "Aplikace nemá oprávnění k Bluetooth. Povolte ho v Nastavení systému → Soukromí a zabezpečení → Bluetooth a aplikaci spusťte znovu.";
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+const PERMISSION_HINT: &str = "Aplikace nemá oprávnění k Bluetooth. Povolte ho v nastavení systému.";
+#[cfg(target_os = "linux")]
+const SERVICE_HINT: &str = "Služba Bluetooth (BlueZ) neběží. Spusťte ji příkazem 'sudo systemctl start bluetooth'.";
+#[cfg(not(target_os = "linux"))]
+const SERVICE_HINT: &str = "Služba Bluetooth není dostupná.";
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("{} {0}", tr("Chyba Bluetooth:"))]
+    Bluetooth(#[from] btleplug::Error),
+    #[error("{}", tr("Bluetooth adaptér nenalezen"))]
+    NoAdapter,
+    #[error("{}", tr(ADAPTER_OFF_HINT))]
+    AdapterOff,
+    #[error("{}", tr(PERMISSION_HINT))]
+    PermissionDenied,
+    #[error("{}", tr(SERVICE_HINT))]
+    ServiceUnavailable,
+    #[error("{}", tr("Senzor nebyl během skenování nalezen, zkontrolujte, zda je v dosahu."))]
+    DeviceNotFound,
+    #[error("{}", tr("Senzor na připojení neodpověděl včas."))]
+    DeviceTimeout,
+    #[error("{} '{path}': {source}", tr("Chyba souboru"))]
+    Io { path: String, source: std::io::Error },
+    #[error("{} {0}", tr("Chyba CSV:"))]
+    Csv(#[from] csv::Error),
+    #[error("{} {0}", tr("Neplatný JSON:"))]
+    Json(#[from] serde_json::Error),
+    // Soubor je čitelný, ale neobsahuje použitelná data (import)
+    #[error("{0}")]
+    Import(String),
+}
+
+impl Error {
+    pub fn io(path: impl AsRef<std::path::Path>, source: std::io::Error) -> Self { Error::Io { path: path.as_ref().display().to_string(), source } }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
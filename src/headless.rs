@@ -0,0 +1,87 @@
+// --- Režim bez okna ---
+// Jen skener, procesor a zápis do denních logů; měření a stav se vypisují na standardní výstup.
+// Hodí se pro sběr dat např. na Raspberry Pi přes ssh, grafy pak ukáže aplikace na jiném počítači.
+use crate::console;
+use crate::instance::{Control, Listener};
+use chrono::Local;
+use log::{info, warn};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use temp_monitor::config::{Config, ConfigWatcher, CONFIG_POLL_INTERVAL};
+use temp_monitor::i18n::{set_language, tr, trf};
+use temp_monitor::power;
+use temp_monitor::processing::{background_data_processor, AppMessage};
+use temp_monitor::scanner::supervised_scanner;
+use temp_monitor::units::{format_temp, set_temp_unit};
+use tokio::sync::{mpsc, watch};
+
+pub const HEADLESS_ARG: &str = "--headless";
+
+// Běží do Ctrl+C: skener se zastaví, procesor dopíše přijatá měření a teprve pak se proces ukončí
+pub fn run(config: Config, instance: Option<Listener>) {
+    info!("Spouštím režim bez okna.");
+    let (out_tx, mut out_rx) = mpsc::unbounded_channel(); let (scanner_tx, processor_rx) = mpsc::unbounded_channel();
+    let (config_tx, config_rx) = watch::channel(config.clone());
+    let processor_config = config_rx.clone();
+    let processor = thread::spawn(move || { background_data_processor(processor_rx, out_tx.into(), processor_config, Arc::new(Mutex::new(None))); });
+    let rt = tokio::runtime::Runtime::new().expect("Nelze vytvořit Tokio runtime");
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let (paused_tx, paused_rx) = watch::channel(false);
+    // Okno tu není, další spuštění se jen odmítne (samo skončí s hlášením); ostatní příkazy `temp_monitor ctl` fungují
+    if let Some(listener) = instance {
+        let on_show = Box::new(|| println!("{}", tr("Další spuštění aplikace bylo odmítnuto, měření běží v tomto procesu.")));
+        listener.serve(Control { on_show, config: config_rx.clone(), processor: scanner_tx.downgrade(), paused: paused_tx });
+    }
+    rt.spawn(supervised_scanner(scanner_tx, config_rx.clone(), shutdown_rx, paused_rx));
+    rt.spawn(power::monitor(config_tx.clone()));
+    rt.spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            println!("{}", tr("Ukončuji, dopisuji přijatá měření…"));
+            let _ = shutdown_tx.send(true);
+        }
+    });
+    // Úpravy config.json (nové MAC, meze, intervaly) se projeví bez restartu, stejně jako v okně
+    rt.spawn(async move {
+        let mut watcher = ConfigWatcher::new();
+        let mut interval = tokio::time::interval(CONFIG_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            match watcher.poll() {
+                Some(Ok(mut config)) => {
+                    // Stav napájení patří monitoru, soubor ho neobsahuje
+                    config.on_battery = config_tx.borrow().on_battery;
+                    if config == *config_tx.borrow() { continue; }
+                    println!("{}", tr("Konfigurace byla změněna v souboru a znovu načtena."));
+                    set_language(config.language);
+                    set_temp_unit(config.temp_unit);
+                    if let Err(e) = console::set_json_log(&config.json_log_file) { warn!("Soubor protokolu JSON '{}' nelze otevřít: {}", config.json_log_file, e); }
+                    config_tx.send_replace(config);
+                },
+                Some(Err(e)) => warn!("Změněnou konfiguraci nelze načíst: {}", e),
+                _ => {}
+            }
+        }
+    });
+    let devices: Vec<String> = config.all_devices().into_iter().map(|(name, mac)| format!("{} ({})", name, mac)).collect();
+    println!("{}", trf("Sleduji senzory: {}", &[&devices.join(", ")]));
+    // Stav i opakovaná chyba se vypíšou jen při změně, aby se v kontinuálním režimu neopakoval každou minutu dokola
+    let mut last_status = String::new();
+    while let Some(message) = out_rx.blocking_recv() {
+        let now = Local::now().format("%Y-%m-%d %H:%M:%S");
+        match message {
+            AppMessage::NewData(data) => {
+                let rssi = data.rssi.map_or(String::new(), |rssi| format!(", RSSI {} dBm", rssi));
+                let battery = data.battery.map_or(String::new(), |battery| format!(", 🔋 {} %", battery));
+                println!("[{}] {}: {}, {} %{}{}", now, config_rx.borrow().device_name(&data.mac), format_temp(data.temp as f64, 1), data.hum, rssi, battery);
+            },
+            AppMessage::StatusUpdate(status) if status != last_status => { println!("[{}] {}", now, status); last_status = status; },
+            AppMessage::CsvWriteStatus(false) => println!("[{}] {} {}", now, tr("Zápis CSV:"), tr("Chyba")),
+            AppMessage::Error(e) if e.to_string() != last_status => { last_status = e.to_string(); println!("[{}] {}", now, last_status); },
+            AppMessage::ReadingRejected(reason) => println!("[{}] {} {}", now, tr("Odmítnuto:"), reason),
+            AppMessage::TestAlert => println!("[{}] {}", now, tr("Zkušební upozornění")),
+            _ => {}
+        }
+    }
+    let _ = processor.join();
+    info!("Režim bez okna ukončen.");
+}
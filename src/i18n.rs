@@ -0,0 +1,598 @@
+// Překlady uživatelského rozhraní: klíčem je původní český text, ostatní jazyky se hledají v tabulkách níže.
+// Chybějící překlad se zobrazí česky, takže nový text v UI nic nerozbije.
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum Language { #[default] Czech, English }
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::Czech, Language::English];
+    pub fn label(self) -> &'static str {
+        match self { Language::Czech => "Čeština", Language::English => "English" }
+    }
+    // Kód jazyka pro HTML report
+    pub fn code(self) -> &'static str {
+        match self { Language::Czech => "cs", Language::English => "en" }
+    }
+}
+
+// Jazyk je globální, aby jej mohla používat i vlákna skeneru a procesoru (stavové zprávy, důvody odmítnutí)
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_language(language: Language) { CURRENT.store(language as u8, Ordering::Relaxed); }
+
+pub fn language() -> Language {
+    match CURRENT.load(Ordering::Relaxed) { 1 => Language::English, _ => Language::Czech }
+}
+
+pub fn tr(text: &'static str) -> &'static str {
+    match language() {
+        Language::Czech => text,
+        Language::English => {
+            static TABLE: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+            TABLE.get_or_init(|| ENGLISH.iter().copied().collect()).get(text).copied().unwrap_or(text)
+        }
+    }
+}
+
+// Přeloží šablonu a postupně dosadí argumenty za `{}` (formátování čísel si volající připraví sám)
+pub fn trf(template: &'static str, args: &[&dyn Display]) -> String {
+    let mut parts = tr(template).split("{}");
+    let mut result = parts.next().unwrap_or_default().to_string();
+    for (i, part) in parts.enumerate() {
+        if let Some(arg) = args.get(i) { result += &arg.to_string(); }
+        result += part;
+    }
+    result
+}
+
+const ENGLISH: &[(&str, &str)] = &[
+    // Hlavní okno a menu
+    ("Teploměr", "Thermometer"),
+    ("Inicializace...", "Initializing..."),
+    ("Soubor", "File"),
+    ("Nastavení", "Settings"),
+    ("Vytvořit report…", "Create report…"),
+    ("Exportovat denní souhrn", "Export daily summary"),
+    ("Denní souhrn ({} dní) uložen do '{}'.", "Daily summary ({} days) saved to '{}'."),
+    ("Export se nezdařil: {}", "Export failed: {}"),
+    ("Ukončit", "Quit"),
+    ("Oddálit", "Zoom out"),
+    ("Přiblížit", "Zoom in"),
+    ("Vycentrovat graf", "Reset plot view"),
+    ("Vždy navrchu", "Always on top"),
+    ("Vše", "All"),
+    ("Zobrazit v grafech tento úsek do posledního měření", "Show this span up to the latest reading in the plots"),
+    ("Mini widget (dvojklikem zpět)", "Mini widget (double-click to restore)"),
+    ("Dvojklikem obnovíte plné okno, tažením přesunete", "Double-click to restore the full window, drag to move"),
+    ("📋 Statistiky", "📋 Statistics"),
+    ("📐 Rychlost změny", "📐 Rate of change"),
+    ("🗠 Společný graf", "🗠 Combined plot"),
+    ("Teplota i vlhkost v jednom grafu s osou vlevo a vpravo", "Temperature and humidity in one plot with left and right axes"),
+    ("Teplota a vlhkost", "Temperature and humidity"),
+    ("Zobrazit graf změny teploty ({}/h) a vlhkosti (%/h)", "Show the rate of change of temperature ({}/h) and humidity (%/h)"),
+    ("Autorem aplikace je {}", "Application author: {}"),
+    ("Zkopírováno do schránky!", "Copied to clipboard!"),
+    ("Neobvyklé měření: {} (z = {})", "Unusual reading: {} (z = {})"),
+    // Ikona v oznamovací oblasti
+    ("Otevřít", "Open"),
+    ("Teploměr\n{}, {} %\nMěřeno {}", "Thermometer\n{}, {} %\nMeasured {}"),
+    ("Teploměr\n{}", "Thermometer\n{}"),
+    // Pohledy a režimy
+    ("Surová data", "Raw data"),
+    ("Hodinový přehled", "Hourly overview"),
+    ("Denní přehled", "Daily overview"),
+    ("📈 Grafy", "📈 Charts"),
+    ("📊 Rozložení", "📊 Distribution"),
+    ("📅 Kalendář", "📅 Calendar"),
+    ("⚖ Porovnání dnů", "⚖ Compare days"),
+    ("📏 Odchylka od reference", "📏 Deviation from reference"),
+    ("🕑 Denní profil", "🕑 Daily profile"),
+    ("⁘ Korelace", "⁘ Correlation"),
+    ("Δ Rozdíl senzorů", "Δ Sensor difference"),
+    ("Průměrná teplota", "Average temperature"),
+    ("Maximální teplota", "Maximum temperature"),
+    ("Průměrná vlhkost", "Average humidity"),
+    ("Maximální vlhkost", "Maximum humidity"),
+    ("Posledních 24 h", "Last 24 h"),
+    ("Posledních 7 dní", "Last 7 days"),
+    ("Posledních 30 dní", "Last 30 days"),
+    ("Vše načtené", "All loaded"),
+    // Záhlaví
+    ("Teplota", "Temperature"),
+    ("Vlhkost", "Humidity"),
+    ("Čas", "Time"),
+    ("Měření", "Readings"),
+    ("Dnes: {}", "Today: {}"),
+    ("Klouzavých posledních 24 hodin\nNačtená historie: {}", "Rolling last 24 hours\nLoaded history: {}"),
+    ("Od dnešní půlnoci", "Since midnight"),
+    ("{} za ~{}", "{} in ~{}"),
+    ("Odhad z trendu za posledních {} min ({}/h)", "Estimated from the trend of the last {} min ({}/h)"),
+    ("EMC dřeva: {} %", "Wood EMC: {} %"),
+    ("Rovnovážná vlhkost dřeva při aktuální teplotě a vlhkosti vzduchu", "Equilibrium moisture content of wood at the current air temperature and humidity"),
+    ("Stav:", "Status:"),
+    ("Aktualizace:", "Updated:"),
+    ("ID Zařízení:", "Device ID:"),
+    ("Zápis CSV:", "CSV write:"),
+    ("Chyba", "Error"),
+    ("Odmítnuto:", "Rejected:"),
+    ("Poslední: {}", "Last: {}"),
+    ("Hlavní senzor", "Main sensor"),
+    // Stav skeneru a filtr měření
+    ("Skenuji (kontinuální režim)...", "Scanning (continuous mode)..."),
+    ("Skenuji...", "Scanning..."),
+    ("Čekám...", "Waiting..."),
+    ("teplota {} mimo platný rozsah", "temperature {} out of valid range"),
+    ("vlhkost {}% mimo platný rozsah", "humidity {}% out of valid range"),
+    ("skok teploty {} → {}", "temperature jump {} → {}"),
+    ("skok vlhkosti {}% → {}%", "humidity jump {}% → {}%"),
+    // Grafy
+    ("Rychlost změny", "Rate of change"),
+    ("Teplota {}/h", "Temperature {}/h"),
+    ("Vlhkost %/h", "Humidity %/h"),
+    ("Průměr", "Average"),
+    ("Pásmo komfortu", "Comfort band"),
+    ("Anomálie", "Anomaly"),
+    ("Předpověď", "Forecast"),
+    // Panel statistik
+    ("Statistiky", "Statistics"),
+    ("Střední kinetická teplota:", "Mean kinetic temperature:"),
+    ("MKT podle Haynese, ΔH = {} kJ/mol, váženo dobou mezi měřeními", "MKT after Haynes, ΔH = {} kJ/mol, weighted by time between readings"),
+    ("Čas v mezích ({} – {})", "Time in range ({} – {})"),
+    ("Nad horní mezí", "Above upper limit"),
+    ("V mezích", "In range"),
+    ("Pod spodní mezí", "Below lower limit"),
+    ("Pokryto {} h měření", "{} h of readings covered"),
+    ("Žádná data pro zvolené období.", "No data for the selected period."),
+    ("Kvalita dat", "Data quality"),
+    ("Dnes se očekává jedno měření každých {} s mezi prvním a posledním měřením, u minulých dnů podle obvyklého rozestupu jejich měření", "Today one reading is expected every {} s between the first and last reading, past days use their usual reading interval"),
+    ("Den", "Day"),
+    ("Přijato", "Received"),
+    ("ø interval", "ø interval"),
+    ("Max. mezera", "Max. gap"),
+    ("Chyby", "Errors"),
+    ("{} % očekávaných měření (jedno každých {} s)", "{} % of expected readings (one every {} s)"),
+    ("{} nečitelných řádků v logu", "{} unreadable lines in the log"),
+    ("Od spuštění odmítnuto {} podezřelých měření.", "{} suspicious readings rejected since start."),
+    ("Denostupně (základ {})", "Degree days (base {})"),
+    ("Vytápění", "Heating"),
+    ("Chlazení", "Cooling"),
+    ("Celkem vytápění: {} {}·d", "Total heating: {} {}·d"),
+    ("Celkem chlazení: {} {}·d", "Total cooling: {} {}·d"),
+    // Report
+    ("Vytvořit report", "Create report"),
+    ("Od:", "From:"),
+    ("Do:", "To:"),
+    ("Report se uloží jako samostatný HTML soubor (grafy, statistiky, překročení mezí).", "The report is saved as a standalone HTML file (charts, statistics, limit excursions)."),
+    ("Do PDF jej lze převést tiskem z prohlížeče.", "It can be converted to PDF by printing from the browser."),
+    ("Vytvořit", "Create"),
+    ("Report uložen do '{}'.", "Report saved to '{}'."),
+    ("Report se nepodařilo uložit: {}", "Failed to save the report: {}"),
+    ("Report teploty a vlhkosti", "Temperature and humidity report"),
+    ("Období", "Period"),
+    ("Senzor", "Sensor"),
+    ("Vytvořeno", "Created"),
+    ("Počet měření", "Number of readings"),
+    ("Střední kinetická teplota (MKT, ΔH = {} kJ/mol)", "Mean kinetic temperature (MKT, ΔH = {} kJ/mol)"),
+    ("Pro zvolené období nejsou k dispozici žádná data.", "No data is available for the selected period."),
+    ("Nedostatek dat pro graf.", "Not enough data for a chart."),
+    ("Překročení mezí ({} – {})", "Limit excursions ({} – {})"),
+    ("Teplota se po celé období držela v mezích.", "The temperature stayed within limits for the whole period."),
+    ("Začátek", "Start"),
+    ("Konec", "End"),
+    ("Trvání", "Duration"),
+    ("Typ", "Type"),
+    ("Extrém", "Extreme"),
+    ("nad horní mezí", "above upper limit"),
+    ("pod spodní mezí", "below lower limit"),
+    // Detail dne, porovnání a reference
+    ("Den {}", "Day {}"),
+    ("Pro tento den nejsou k dispozici žádná data.", "No data is available for this day."),
+    ("📏 Použít jako referenční den", "📏 Use as reference day"),
+    ("Referenční den nastaven na {}.", "Reference day set to {}."),
+    ("Den A:", "Day A:"),
+    ("Den B:", "Day B:"),
+    ("Dnes × včera", "Today × yesterday"),
+    ("Dnes × před týdnem", "Today × a week ago"),
+    ("Den:", "Day:"),
+    ("Dnes", "Today"),
+    ("Referenční den:", "Reference day:"),
+    ("Pro referenční den nejsou k dispozici žádná data.", "No data is available for the reference day."),
+    ("Denní rozkmit teploty:", "Daily temperature swing:"),
+    ("{}{} (reference {}{}, rozdíl {})", "{}{} (reference {}{}, difference {})"),
+    ("Rozkmit vlhkosti:", "Humidity swing:"),
+    ("{}% (reference {}%, rozdíl {}%)", "{}% (reference {}%, difference {}%)"),
+    ("Průměr:", "Average:"),
+    ("{}, {}% oproti referenci", "{}, {}% compared to the reference"),
+    ("Odchylka teploty", "Temperature deviation"),
+    ("Odchylka vlhkosti", "Humidity deviation"),
+    // Profil, korelace, histogram, kalendář
+    ("Počítá se jen z historie načtené v paměti.", "Computed only from the history loaded in memory."),
+    ("Profil z {} měření ({} dní)", "Profile from {} readings ({} days)"),
+    ("Barva bodu = hodina dne (modrá = noc, žlutá = poledne)", "Point colour = hour of day (blue = night, yellow = noon)"),
+    ("Riziko kondenzace (≥ {} %)", "Condensation risk (≥ {} %)"),
+    ("Třída teploty ({}): ", "Temperature bin ({}): "),
+    ("Třída vlhkosti (%): ", "Humidity bin (%): "),
+    ("Pokryto {} h z {} měření", "{} h covered by {} readings"),
+    ("Teplota – doba v hodinách", "Temperature – time in hours"),
+    ("Vlhkost – doba v hodinách", "Humidity – time in hours"),
+    ("Po", "Mo"), ("Út", "Tu"), ("St", "We"), ("Čt", "Th"), ("Pá", "Fr"), ("So", "Sa"), ("Ne", "Su"),
+    // Rozdíl senzorů
+    ("Senzor A:", "Sensor A:"),
+    ("Senzor B:", "Sensor B:"),
+    ("Prohodit senzory", "Swap sensors"),
+    ("Pro zobrazení rozdílu je potřeba nastavit alespoň dva senzory (Nastavení → Další senzory).", "At least two sensors are needed to show a difference (Settings → Additional sensors)."),
+    ("Zatím nejsou k dispozici měření obou senzorů.", "Readings from both sensors are not available yet."),
+    ("Měření A {}, B {} (rozestup {} s)", "Reading A {}, B {} ({} s apart)"),
+    ("Rozdíl teploty (A − B)", "Temperature difference (A − B)"),
+    ("Rozdíl vlhkosti (A − B)", "Humidity difference (A − B)"),
+    // Nastavení
+    ("Jazyk:", "Language:"),
+    ("Jednotka teploty:", "Temperature unit:"),
+    ("Cílová MAC adresa:", "Target MAC address:"),
+    ("Další senzory", "Additional sensors"),
+    ("Název", "Name"),
+    ("MAC adresa", "MAC address"),
+    ("Odebrat senzor", "Remove sensor"),
+    ("➕ Přidat senzor", "➕ Add sensor"),
+    ("Měření dalších senzorů se ukládají do souborů log_<MAC>_<datum>.csv.", "Readings of additional sensors are saved to log_<MAC>_<date>.csv files."),
+    ("Timeout skenování (s): ", "Scan timeout (s): "),
+    ("Pauza mezi skeny (s): ", "Pause between scans (s): "),
+    ("Interval pro duplikáty (s): ", "Duplicate interval (s): "),
+    ("Záznamy ze stejného zařízení budou ignorovány po tuto dobu.", "Readings from the same device are ignored for this long."),
+    ("Kontinuální režim", "Continuous mode"),
+    ("⚠️ Kontinuální režim pouze zrychluje skenování, stále platí interval pro duplikáty.", "⚠️ Continuous mode only speeds up scanning, the duplicate interval still applies."),
+    ("Načíst kompletní historii z CSV při startu", "Load the complete CSV history at startup"),
+    ("POZOR: Může zpomalit start.", "WARNING: May slow down startup."),
+    ("Mez pro varování", "Warning limit"),
+    ("Spodní mez", "Lower limit"),
+    ("Filtrovat nesmyslné hodnoty (poškozené pakety)", "Filter implausible values (corrupted packets)"),
+    ("Platná teplota od", "Valid temperature from"),
+    ("Platná teplota do", "Valid temperature to"),
+    ("Max. skok teploty", "Max. temperature jump"),
+    ("Max. skok vlhkosti (%): ", "Max. humidity jump (%): "),
+    ("Označovat neobvyklá měření (z-skóre)", "Mark unusual readings (z-score)"),
+    ("Porovnávat s posledními (měření): ", "Compare with the last (readings): "),
+    ("Práh z-skóre: ", "Z-score threshold: "),
+    ("Upozornit na anomálii", "Notify about anomalies"),
+    ("Zobrazit předpověď z trendu", "Show trend forecast"),
+    ("Předpověď na (min): ", "Forecast for (min): "),
+    ("Trend z posledních (min): ", "Trend from the last (min): "),
+    ("Základ denostupňů", "Degree-day base"),
+    ("Aktivační energie MKT (kJ/mol): ", "MKT activation energy (kJ/mol): "),
+    ("Zobrazovat a zapisovat rovnovážnou vlhkost dřeva (EMC)", "Show and log wood equilibrium moisture content (EMC)"),
+    ("Sloupec EMC se do logu přidá od nového denního souboru.", "The EMC column is added to the log starting with the next daily file."),
+    ("Zobrazit pásmo komfortu v grafech", "Show the comfort band in charts"),
+    ("Teplota od", "Temperature from"),
+    ("do: ", "to: "),
+    ("do", "to"),
+    ("Vlhkost od (%): ", "Humidity from (%): "),
+    ("Zobrazit ikonu v oznamovací oblasti", "Show icon in the notification area"),
+    ("Minimalizovat do oznamovací oblasti", "Minimize to the notification area"),
+    ("Zavřením okna jen schovat do oznamovací oblasti", "Closing the window only hides it to the notification area"),
+    ("Aplikaci pak ukončíte přes Soubor → Ukončit nebo menu ikony.", "Quit the application via File → Quit or the icon menu."),
+    ("Spouštět po přihlášení do Windows", "Start when signing in to Windows"),
+    ("Po přihlášení spustit schované v oznamovací oblasti", "Start hidden in the notification area after sign-in"),
+    ("Automatické spuštění se nepodařilo nastavit: {}", "Could not change automatic start: {}"),
+    // Poznámky na časové ose
+    ("📝 Poznámky", "📝 Notes"),
+    ("Poznámky", "Notes"),
+    ("Poznámka", "Note"),
+    ("např. otevřené okno", "e.g. opened window"),
+    ("Přidat", "Add"),
+    ("Smazat poznámku", "Delete note"),
+    ("Poznámky se nepodařilo uložit: {}", "Failed to save notes: {}"),
+    // Export grafu
+    ("Exportovat graf do PNG", "Export plot to PNG"),
+    ("Graf uložen do '{}'.", "Plot saved to '{}'."),
+    // Snímek okna
+    ("Snímek okna do schránky a do souboru (F12)", "Window screenshot to clipboard and file (F12)"),
+    ("Snímek okna zkopírován do schránky a uložen do '{}'.", "Window screenshot copied to the clipboard and saved to '{}'."),
+    ("Snímek okna uložen do '{}'.", "Window screenshot saved to '{}'."),
+    ("Snímek okna se nepodařilo uložit: {}", "Failed to save the window screenshot: {}"),
+    // Barvy grafů
+    ("Barvy grafů", "Plot colors"),
+    ("Předvolba:", "Preset:"),
+    ("Vyberte…", "Choose…"),
+    ("Výchozí", "Default"),
+    ("Pro barvoslepé (rumělková / modrá)", "Colorblind-safe (vermillion / blue)"),
+    ("Pro barvoslepé (oranžová / nebeská)", "Colorblind-safe (orange / sky blue)"),
+    ("Barva čáry", "Line color"),
+    ("Barva bodů", "Point color"),
+    ("Body podle hodnoty", "Points by value"),
+    ("● Kolečko", "● Circle"),
+    ("■ Čtverec", "■ Square"),
+    ("◆ Kosočtverec", "◆ Diamond"),
+    ("✖ Křížek", "✖ Cross"),
+    ("Bez bodů", "No points"),
+    // Tabulka měření
+    ("☰ Tabulka", "☰ Table"),
+    ("RSSI (dBm)", "RSSI (dBm)"),
+    ("Počet měření: {}", "Readings: {}"),
+    ("např. hum > 70 a temp < 18", "e.g. hum > 70 and temp < 18"),
+    ("Veličiny temp, hum a rssi; operátory < <= > >= = !=; podmínky spojte „a“. Teplota se zadává v zobrazené jednotce.", "Fields temp, hum and rssi; operators < <= > >= = !=; join conditions with “and”. Temperature is entered in the displayed unit."),
+    ("Zvýraznit v grafech", "Highlight in plots"),
+    ("Exportovat výběr do CSV", "Export selection to CSV"),
+    ("Výběr uložen do '{}'.", "Selection saved to '{}'."),
+    ("Výběr z tabulky", "Table selection"),
+    ("Neplatná podmínka „{}“", "Invalid condition “{}”"),
+    ("Neznámá veličina „{}“ (použijte temp, hum nebo rssi)", "Unknown field “{}” (use temp, hum or rssi)"),
+    // Kopírování úseku jako TSV
+    ("Kopírovat jako TSV", "Copy as TSV"),
+    ("Datum", "Date"),
+    ("Tip: Shift + tažení v grafu ukáže souhrn úseku a zkopíruje ho jako TSV", "Tip: Shift + drag in a plot shows a summary of the span and copies it as TSV"),
+    ("Zkopíruje zobrazené řádky pro vložení do tabulky", "Copies the shown rows for pasting into a spreadsheet"),
+    ("Zkopírováno {} měření (TSV).", "Copied {} readings (TSV)."),
+    // Stavový řádek
+    ("Běží {}", "Running {}"),
+    ("Dnes měření: {}", "Readings today: {}"),
+    ("V paměti: {} bodů", "In memory: {} points"),
+    ("nevytvořen", "not created"),
+    ("Skener: {}", "Scanner: {}"),
+    // Oznámení
+    ("Kliknutím zavřete", "Click to dismiss"),
+    ("Oznámení zobrazit (s): ", "Show notifications for (s): "),
+    ("Varování a chyby (s): ", "Warnings and errors (s): "),
+    // Kontrola MAC adresy
+    ("Neplatná MAC adresa (např. B8:59:CE:33:0F:93)", "Invalid MAC address (e.g. B8:59:CE:33:0F:93)"),
+    // Velikost historie
+    ("Načítám historii…", "Loading history…"),
+    ("Načtena kompletní historie ({} měření).", "Full history loaded ({} readings)."),
+    // Záložky senzorů
+    ("🏠 Vše", "🏠 All"),
+    ("Posledních 24 h", "Last 24 h"),
+    ("Průměr: {}", "Average: {}"),
+    ("Průměrná vlhkost: {} %", "Average humidity: {} %"),
+    // Přehled senzorů
+    ("▦ Karty", "▦ Cards"),
+    ("🗠 Grafy", "🗠 Plots"),
+    ("Šířka karty", "Card width"),
+    ("Bez dat", "No data"),
+    ("Kliknutím otevřete záložku senzoru", "Click to open the sensor's tab"),
+    // Kioskový režim
+    ("Měřeno {}", "Measured {}"),
+    ("F11 nebo Esc ukončí kioskový režim", "F11 or Esc leaves kiosk mode"),
+    ("Celá obrazovka jen s aktuálními hodnotami (F11)", "Fullscreen with current values only (F11)"),
+    // Baterie
+    ("Baterie:", "Battery:"),
+    ("Baterie – varování pod (%): ", "Battery – warn below (%): "),
+    ("kriticky pod (%): ", "critical below (%): "),
+    // Síla signálu
+    ("Signál:", "Signal:"),
+    ("Signál – dílky od (dBm):", "Signal – bars from (dBm):"),
+    // Stáří měření
+    ("před {} s", "{} s ago"),
+    ("před {}", "{} ago"),
+    ("Očekávaný interval měření: {} s", "Expected reading interval: {} s"),
+    // Souhrn vybraného úseku
+    ("Vybraný úsek", "Selected range"),
+    ("Trvání: {}, měření: {}", "Duration: {}, readings: {}"),
+    // Potvrzení nastavení
+    ("Použít", "Apply"),
+    ("Zrušit", "Cancel"),
+    ("Výchozí hodnoty", "Defaults"),
+    ("Vrátí všechna nastavení kromě adres senzorů; uloží se až tlačítkem OK nebo Použít", "Resets all settings except sensor addresses; saved only with OK or Apply"),
+    ("Neuložené změny", "Unsaved changes"),
+    // Ukončení aplikace
+    ("Ukončení", "Exit"),
+    ("Zápis přijatých měření do CSV ještě neskončil.", "Writing received readings to CSV has not finished yet."),
+    ("Ukončit přesto", "Exit anyway"),
+    ("Nezapsaná měření se ztratí", "Unwritten readings will be lost"),
+    // O aplikaci
+    ("O aplikaci", "About"),
+    ("Verze {} (sestaveno {})", "Version {} (built {})"),
+    ("Složka dat:", "Data folder:"),
+    ("Konfigurace:", "Configuration:"),
+    ("Dnešní log:", "Today's log:"),
+    ("Poznámky:", "Notes:"),
+    ("📂 Otevřít složku s daty", "📂 Open data folder"),
+    ("Licence Apache 2.0. Postaveno na knihovnách egui, btleplug a tokio.", "Apache 2.0 license. Built with egui, btleplug and tokio."),
+    // Konzole varování a chyb
+    ("Varování a chyby", "Warnings and errors"),
+    ("Vymazat", "Clear"),
+    ("Kopírovat", "Copy"),
+    ("Žádná varování ani chyby.", "No warnings or errors."),
+    // Obnova okna
+    ("Obnovit okno každých (s): ", "Refresh window every (s): "),
+    ("Úsporný režim", "Power saving"),
+    ("Okno se samo obnoví jen jednou za {} s (šetří baterii notebooku)", "The window refreshes itself only once every {} s (saves laptop battery)"),
+    // Ikona na hlavním panelu
+    ("Zobrazit teplotu v ikoně na hlavním panelu", "Show the temperature in the taskbar icon"),
+    // Kategorie nastavení
+    ("📡 Zařízení", "📡 Device"),
+    ("💾 Záznam", "💾 Logging"),
+    ("🔔 Upozornění", "🔔 Alerts"),
+    ("🖥 Zobrazení", "🖥 Display"),
+    ("🔗 Integrace", "🔗 Integrations"),
+    ("Hledat nastavení…", "Search settings…"),
+    ("Hledanému textu neodpovídá žádné nastavení.", "No setting matches the search."),
+    ("Vrátit výchozí hodnotu", "Reset to default"),
+    ("Senzory", "Sensors"),
+    ("Skenování", "Scanning"),
+    ("Signál a baterie", "Signal and battery"),
+    ("Duplicitní záznamy", "Duplicate readings"),
+    ("Historie", "History"),
+    ("Filtr hodnot", "Value filter"),
+    ("Vlhkost dřeva", "Wood moisture"),
+    ("Meze teploty", "Temperature limits"),
+    ("Oznámení", "Notifications"),
+    ("Jazyk a jednotky", "Language and units"),
+    ("Obnovování okna", "Window refresh"),
+    ("Výpočty", "Calculations"),
+    ("Oznamovací oblast a hlavní panel", "Notification area and taskbar"),
+    ("Spouštění", "Startup"),
+    ("V tomto systému nejsou k dispozici žádné integrace.", "No integrations are available on this system."),
+    // Import přetaženého CSV
+    ("Pusťte CSV soubor pro zobrazení jeho dat nebo export nastavení", "Drop a CSV file to view its data, or a settings export"),
+    ("📂 Zobrazena importovaná data: {}", "📂 Viewing imported data: {}"),
+    ("{} měření", "{} readings"),
+    ("Živá měření se dál zaznamenávají.", "Live readings are still being recorded."),
+    ("Zpět na živá data", "Back to live data"),
+    ("Importováno {} měření.", "Imported {} readings."),
+    ("Importováno {} měření, {} řádků nešlo přečíst.", "Imported {} readings, {} rows could not be read."),
+    ("Soubor {} nelze importovat: {}", "Cannot import file {}: {}"),
+    ("v hlavičce chybí sloupce s časem, teplotou nebo vlhkostí", "the header lacks time, temperature or humidity columns"),
+    ("soubor neobsahuje žádná čitelná měření", "the file contains no readable readings"),
+    // Nabídka grafu
+    ("Kopírovat měření z {}", "Copy reading from {}"),
+    ("Přidat poznámku v {}", "Add note at {}"),
+    ("Přiblížit na dnešek", "Zoom to today"),
+    ("Exportovat viditelný úsek do CSV", "Export visible range to CSV"),
+    ("Ve viditelném úseku nejsou žádná měření.", "There are no readings in the visible range."),
+    // Minimum a maximum v grafu
+    ("⇕ Min/max", "⇕ Min/max"),
+    ("Vyznačit v grafech nejnižší a nejvyšší hodnotu viditelného úseku", "Mark the lowest and highest value of the visible range in the plots"),
+    // Meze vlhkosti
+    ("Meze vlhkosti", "Humidity limits"),
+    ("Vlhkost – horní mez (%): ", "Humidity – upper limit (%): "),
+    ("spodní mez (%): ", "lower limit (%): "),
+    // Kolečko myši v grafech
+    ("Shift + kolečko posouvá časovou osu, Ctrl + kolečko ji přibližuje", "Shift + wheel pans the time axis, Ctrl + wheel zooms it"),
+    // Vysoký kontrast
+    ("Přístupnost", "Accessibility"),
+    ("Vysoký kontrast", "High contrast"),
+    ("Černé neprůhledné pozadí, bílý text, silnější čáry a větší body v grafech", "Opaque black background, white text, thicker lines and larger points in plots"),
+    // Režim bez okna
+    ("Sleduji senzory: {}", "Watching sensors: {}"),
+    ("Ukončuji, dopisuji přijatá měření…", "Shutting down, writing received readings…"),
+    // Příkazová řádka
+    ("Použití:
+  temp_monitor export [--from RRRR-MM-DD] [--to RRRR-MM-DD] [--format csv|json] [--device MAC] [--output SOUBOR]
+  temp_monitor stats [--day RRRR-MM-DD] [--device MAC]
+  temp_monitor devices
+  temp_monitor ctl show|current|pause|resume|test-alert|export [--from …] [--to …] [--device MAC]
+Bez data se použije dnešní den, bez --device hlavní senzor. Bez příkazu se spustí okno aplikace.
+Příkaz ctl řídí aplikaci běžící nad stejnou složkou s logy a vypíše její odpověď (JSON).", "Usage:
+  temp_monitor export [--from YYYY-MM-DD] [--to YYYY-MM-DD] [--format csv|json] [--device MAC] [--output FILE]
+  temp_monitor stats [--day YYYY-MM-DD] [--device MAC]
+  temp_monitor devices
+  temp_monitor ctl show|current|pause|resume|test-alert|export [--from …] [--to …] [--device MAC]
+Without a date today is used, without --device the primary sensor. Without a command the application window opens.
+The ctl command controls the application running over the same log folder and prints its reply (JSON)."),
+    ("Chyba:", "Error:"),
+    ("neplatné datum '{}' (očekáváno RRRR-MM-DD)", "invalid date '{}' (expected YYYY-MM-DD)"),
+    ("neplatná MAC adresa '{}'", "invalid MAC address '{}'"),
+    ("počáteční datum je až po koncovém", "the start date is after the end date"),
+    ("neznámý formát '{}' (csv nebo json)", "unknown format '{}' (csv or json)"),
+    ("Exportováno {} měření do '{}'.", "Exported {} readings to '{}'."),
+    ("pro {} nejsou žádná měření", "there are no readings for {}"),
+    ("Teplota (min / ø / max):", "Temperature (min / avg / max):"),
+    ("Vlhkost (min / ø / max):", "Humidity (min / avg / max):"),
+    ("dní v logu: {}", "days logged: {}"),
+    // Vlastní umístění konfigurace
+    ("za --config chybí cesta ke konfiguraci", "--config is missing the path to the configuration file"),
+    // Změny config.json zvenku
+    ("Konfigurace byla změněna v souboru a znovu načtena.", "The configuration file was changed and has been reloaded."),
+    ("Změněný config.json nelze načíst, platí dosavadní nastavení: {}", "The changed config.json cannot be loaded, keeping the current settings: {}"),
+    // Chyby předávané do okna
+    ("Chyba Bluetooth:", "Bluetooth error:"),
+    ("Bluetooth adaptér nenalezen", "Bluetooth adapter not found"),
+    ("Chyba souboru", "File error"),
+    ("Chyba CSV:", "CSV error:"),
+    ("Neplatný JSON:", "Invalid JSON:"),
+    ("Nastavení nelze uložit: {}", "Settings cannot be saved: {}"),
+    // Stránkování historie z disku
+    ("Dní v paměti (0 = vše): ", "Days kept in memory (0 = all): "),
+    ("Starší dny se při posunu grafu načítají z denních logů.", "Older days are loaded from the daily logs as you scroll the plot."),
+    ("V paměti: {} bodů (+{} z disku)", "In memory: {} points (+{} from disk)"),
+    // Protokol aplikace
+    ("Protokol aplikace", "Application log"),
+    ("Hledat…", "Search…"),
+    ("Celý protokol…", "Full log…"),
+    ("{} záznamů", "{} entries"),
+    // Hlášení o pádu
+    ("Aplikace minule neočekávaně skončila", "The application closed unexpectedly last time"),
+    ("Hlášení o pádu bylo uloženo. Přiložte ho prosím k hlášení chyby.", "A crash report was saved. Please attach it when reporting the problem."),
+    ("📄 Otevřít hlášení", "📄 Open report"),
+    ("Zavřít", "Close"),
+    // Bluetooth na Linuxu a dalších systémech
+    ("Spouštět po přihlášení", "Start when signing in"),
+    ("Bluetooth je vypnutý. Zapněte ho v nastavení systému nebo příkazem 'bluetoothctl power on'.", "Bluetooth is turned off. Turn it on in the system settings or with 'bluetoothctl power on'."),
+    ("Bluetooth je vypnutý. Zapněte ho v nastavení systému.", "Bluetooth is turned off. Turn it on in the system settings."),
+    ("Chybí oprávnění k Bluetooth. Přidejte uživatele do skupiny bluetooth ('sudo usermod -aG bluetooth $USER') a přihlaste se znovu.", "Missing Bluetooth permission. Add the user to the bluetooth group ('sudo usermod -aG bluetooth $USER') and sign in again."),
+    ("Aplikace nemá oprávnění k Bluetooth. Povolte ho v nastavení systému.", "The application has no Bluetooth permission. Allow it in the system settings."),
+    ("Služba Bluetooth (BlueZ) neběží. Spusťte ji příkazem 'sudo systemctl start bluetooth'.", "The Bluetooth service (BlueZ) is not running. Start it with 'sudo systemctl start bluetooth'."),
+    ("Služba Bluetooth není dostupná.", "The Bluetooth service is not available."),
+    // Bluetooth na macOS
+    ("Aplikace nemá oprávnění k Bluetooth. Povolte ho v Nastavení systému → Soukromí a zabezpečení → Bluetooth a aplikaci spusťte znovu.", "The application has no Bluetooth permission. Allow it in System Settings → Privacy & Security → Bluetooth and start the application again."),
+    ("Otevřít nastavení soukromí", "Open privacy settings"),
+    ("macOS adresu MAC neprozradí: zadejte UUID senzoru, které aplikace po jeho nalezení zapíše do protokolu.", "macOS does not reveal the MAC address: enter the sensor's UUID, which the application writes to the log once the sensor is found."),
+    ("Neplatné UUID (např. 1B2C3D4E-5F60-7182-93A4-B5C6D7E8F901)", "Invalid UUID (e.g. 1B2C3D4E-5F60-7182-93A4-B5C6D7E8F901)"),
+    // Jediná instance
+    ("Aplikace nad touto složkou s logy už běží.", "The application is already running over this log folder."),
+    ("Další spuštění aplikace bylo odmítnuto, měření běží v tomto procesu.", "Another launch of the application was refused, measuring runs in this process."),
+    // Místní řízení
+    ("Zkušební upozornění", "Test alert"),
+    ("Skenování pozastaveno", "Scanning paused"),
+    ("prázdný příkaz", "empty command"),
+    ("neznámý příkaz", "unknown command"),
+    ("měření se už nezpracovávají", "readings are no longer being processed"),
+    ("chybí příkaz pro běžící aplikaci", "missing command for the running application"),
+    ("aplikace neběží nebo neodpovídá ({})", "the application is not running or not responding ({})"),
+    // Překreslování okna
+    ("Nová měření se zobrazí hned; toto obnovení posouvá jen údaje závislé na čase (stáří měření, doba běhu).", "New readings appear immediately; this refresh only advances time-dependent values (age of the reading, uptime)."),
+    // Protokol JSON
+    ("Protokol JSON nelze zapisovat: {}", "The JSON log cannot be written: {}"),
+    ("Protokol aplikace", "Application log"),
+    ("Soubor JSON Lines:", "JSON Lines file:"),
+    ("vypnuto", "off"),
+    ("Protokol aplikace (informace, varování a chyby) se navíc připisuje do tohoto souboru jako JSON na řádek, např. pro Loki nebo Elastic", "The application log (information, warnings and errors) is also appended to this file as one JSON object per line, e.g. for Loki or Elastic"),
+    // Údaje o zařízení (GATT Device Information)
+    ("Senzor nebyl během skenování nalezen, zkontrolujte, zda je v dosahu.", "The sensor was not found while scanning; check that it is in range."),
+    ("Senzor na připojení neodpověděl včas.", "The sensor did not respond to the connection in time."),
+    ("Výrobce:", "Manufacturer:"),
+    ("Model:", "Model:"),
+    ("Sériové číslo:", "Serial number:"),
+    ("Revize hardwaru:", "Hardware revision:"),
+    ("Firmware:", "Firmware:"),
+    ("Software:", "Software:"),
+    ("Údaje o zařízení nelze přečíst: {}", "Device information cannot be read: {}"),
+    ("Připojuji se k senzoru…", "Connecting to the sensor…"),
+    ("ℹ Údaje o zařízení", "ℹ Device information"),
+    ("Připojí se k senzoru a přečte model, firmware a sériové číslo (služba Device Information); hodí se při hlášení rozdílů mezi revizemi hardwaru", "Connects to the sensor and reads the model, firmware and serial number (Device Information service); useful when reporting differences between hardware revisions"),
+    ("Senzor službu Device Information neposkytuje.", "The sensor does not provide the Device Information service."),
+    // Export a import nastavení
+    ("Exportovat nastavení", "Export settings"),
+    ("Meze, senzory, upozornění a vzhled do souboru pro další počítač", "Thresholds, sensors, alerts and appearance into a file for another computer"),
+    ("Nastavení uloženo do '{}'.", "Settings saved to '{}'."),
+    ("Importovat nastavení", "Import settings"),
+    ("Načte '{}' z pracovní složky; jiný export stačí přetáhnout do okna", "Loads '{}' from the working folder; any other export can be dropped onto the window"),
+    ("Nastavení importováno ({}).", "Settings imported ({})."),
+    ("Nastavení importováno.", "Settings imported."),
+    ("Nastavení nelze importovat: {}", "Settings cannot be imported: {}"),
+    ("soubor není export nastavení této aplikace", "the file is not a settings export of this application"),
+    ("v exportu chybí nastavení", "the export contains no settings"),
+    // Profily nastavení
+    ("Profil", "Profile"),
+    ("Profily", "Profiles"),
+    ("Profil {}", "Profile {}"),
+    ("Název profilu", "Profile name"),
+    ("Profil si pamatuje meze teploty a vlhkosti a intervaly skenování; přepíná se v menu nahoře nebo v menu ikony.", "A profile remembers the temperature and humidity limits and scan intervals; switch it from the menu bar or the tray icon menu."),
+    ("Přepnout", "Switch"),
+    ("Uložit do profilu aktuální meze a intervaly", "Store the current limits and intervals in the profile"),
+    ("Odebrat profil", "Remove profile"),
+    ("➕ Nový profil z aktuálních hodnot", "➕ New profile from current values"),
+    ("Uložit změny do profilu {}", "Save changes to profile {}"),
+    ("Upravit profily…", "Edit profiles…"),
+    ("Zapnut profil {}.", "Profile {} switched on."),
+    // Časová okna skenování
+    ("Časová okna skenování", "Scan time windows"),
+    ("Odebrat okno", "Remove window"),
+    ("➕ Přidat okno", "➕ Add window"),
+    ("Mimo okna skener nepracuje a Bluetooth nepoužívá; bez oken se skenuje nepřetržitě. Okno může přecházet přes půlnoc (22:00–06:00).", "Outside the windows the scanner is idle and does not use Bluetooth; without windows it scans all the time. A window may span midnight (22:00–06:00)."),
+    ("Mimo časové okno skenování, pokračuji v {}", "Outside the scan time window, resuming at {}"),
+    // Úspora na baterii
+    ("Skenuji (úspora baterie)...", "Scanning (battery saver)..."),
+    ("Šetřit energii při napájení z baterie", "Save energy when running on battery"),
+    ("Notebook odpojený od sítě: skenování bez kontinuálního režimu a s {}× delší pauzou, obnova okna jako v úsporném režimu, bez obnovování teploty v ikoně na hlavním panelu; do jiných výstupů než denního CSV logu se měření zapíšou až po připojení k síti (upozornění hned). Po připojení k síti se vše vrátí.", "Laptop unplugged: scanning without continuous mode and with a {}× longer pause, window refresh as in power saving mode, no temperature updates in the taskbar icon; outputs other than the daily CSV log receive readings only once back on AC power (alerts right away). Everything returns to normal on AC power."),
+    ("🔋 Počítač teď běží z baterie, úspora je zapnutá.", "🔋 The computer is running on battery now, energy saving is active."),
+    // Starší denní logy v historii
+    ("Načíst i starší denní logy", "Also load older daily logs"),
+    ("Bez toho se načte jen dnešní log; se staršími logy ukážou grafy i vícedenní přehled.", "Otherwise only today's log is loaded; with older logs the plots can show a multi-day overview."),
+    // Hlídač skeneru
+    ("Skener nereagoval, restartuji ho…", "The scanner stopped responding, restarting it…"),
+];
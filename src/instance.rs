@@ -0,0 +1,188 @@
+// --- Jediná instance aplikace a místní řízení ---
+// Druhé spuštění nad stejnou složkou s logy by zapisovalo do stejných CSV souborů jako první. Běžící instance
+// proto poslouchá na lokálním soketu (na Windows pojmenované rouře); nové spuštění jí jen pošle žádost
+// o zobrazení okna a skončí.
+// Stejným soketem ji mohou řídit skripty na témže počítači (`temp_monitor ctl …`): na každý řádek s příkazem
+// odpoví jedním řádkem JSON s polem "ok", případně "error".
+use crate::cli::{json_records, range_points};
+use log::{info, warn};
+use serde_json::{json, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::sync::Arc;
+use std::thread;
+use temp_monitor::config::Config;
+use temp_monitor::i18n::tr;
+use temp_monitor::processing::AppMessage;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::runtime::Runtime;
+use tokio::sync::{mpsc, oneshot, watch};
+
+const SHOW_REQUEST: &str = "show";
+
+// Název soketu podle pracovní složky (tam se zapisují logy); instance s jinou konfigurací ve vlastní složce si nepřekáží
+fn endpoint_name() -> String {
+    let dir = std::env::current_dir().and_then(std::fs::canonicalize).unwrap_or_default();
+    let mut hasher = DefaultHasher::new();
+    dir.hash(&mut hasher);
+    format!("temp_monitor-{:016x}", hasher.finish())
+}
+
+#[cfg(unix)]
+mod platform {
+    use std::io;
+    use std::path::PathBuf;
+    use tokio::net::{UnixListener, UnixStream};
+
+    pub type Server = UnixListener;
+
+    fn path(name: &str) -> PathBuf {
+        std::env::var_os("XDG_RUNTIME_DIR").map(PathBuf::from).unwrap_or_else(std::env::temp_dir).join(format!("{}.sock", name))
+    }
+
+    pub async fn connect(name: &str) -> io::Result<UnixStream> { UnixStream::connect(path(name)).await }
+
+    // Soket po spadlé instanci zůstává na disku; volá se až po neúspěšném připojení, nikdo na něm tedy neposlouchá
+    pub fn bind(name: &str) -> io::Result<Server> {
+        let path = path(name);
+        let _ = std::fs::remove_file(&path);
+        UnixListener::bind(path)
+    }
+
+    pub async fn accept(server: &mut Server, _name: &str) -> io::Result<UnixStream> { Ok(server.accept().await?.0) }
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::io;
+    use std::time::Duration;
+    use tokio::net::windows::named_pipe::{ClientOptions, NamedPipeClient, NamedPipeServer, ServerOptions};
+
+    pub type Server = NamedPipeServer;
+    // Všechny instance roury jsou obsazené (server právě vytváří další), stačí chvíli počkat
+    const ERROR_PIPE_BUSY: i32 = 231;
+
+    fn path(name: &str) -> String { format!(r"\\.\pipe\{}", name) }
+
+    pub async fn connect(name: &str) -> io::Result<NamedPipeClient> {
+        for _ in 0..20 {
+            match ClientOptions::new().open(path(name)) {
+                Err(e) if e.raw_os_error() == Some(ERROR_PIPE_BUSY) => tokio::time::sleep(Duration::from_millis(50)).await,
+                result => return result,
+            }
+        }
+        ClientOptions::new().open(path(name))
+    }
+
+    // first_pipe_instance: vytvoření selže, pokud rouru už drží jiný proces
+    pub fn bind(name: &str) -> io::Result<Server> { ServerOptions::new().first_pipe_instance(true).create(path(name)) }
+
+    // Připojený klient si odnese aktuální instanci roury, pro dalšího se hned vytvoří nová
+    pub async fn accept(server: &mut Server, name: &str) -> io::Result<NamedPipeServer> {
+        server.connect().await?;
+        let next = ServerOptions::new().create(path(name))?;
+        Ok(std::mem::replace(server, next))
+    }
+}
+
+pub struct Listener { runtime: Runtime, server: platform::Server, name: String }
+
+// Co příkazy potřebují od běžící aplikace: zobrazení okna, konfiguraci, procesor měření a pozastavení skeneru.
+// Kanál procesoru je jen slabý odkaz, aby procesor po zastavení skeneru skončil jako dřív.
+pub struct Control {
+    pub on_show: Box<dyn Fn() + Send + Sync>,
+    pub config: watch::Receiver<Config>,
+    pub processor: mpsc::WeakUnboundedSender<AppMessage>,
+    pub paused: watch::Sender<bool>,
+}
+
+// Zabere soket této složky; pokud už ho drží běžící instance, pošle jí žádost o zobrazení okna a vrátí None
+pub fn claim() -> io::Result<Option<Listener>> {
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+    let name = endpoint_name();
+    let notified = runtime.block_on(async {
+        let Ok(mut stream) = platform::connect(&name).await else { return false; };
+        stream.write_all(format!("{}\n", SHOW_REQUEST).as_bytes()).await.is_ok()
+    });
+    if notified { return Ok(None); }
+    let server = runtime.block_on(async { platform::bind(&name) })?;
+    Ok(Some(Listener { runtime, server, name }))
+}
+
+impl Listener {
+    // Žádosti dalších spuštění a skriptů vyřizuje ve vlastním vlákně až do konce procesu, každé spojení zvlášť
+    pub fn serve(self, control: Control) {
+        let Listener { runtime, mut server, name } = self;
+        let control = Arc::new(control);
+        thread::spawn(move || runtime.block_on(async move {
+            loop {
+                let stream = match platform::accept(&mut server, &name).await {
+                    Ok(stream) => stream,
+                    Err(e) => { warn!("Chyba při čekání na místní řízení: {}", e); return; }
+                };
+                let control = control.clone();
+                tokio::spawn(async move {
+                    let (reader, mut writer) = tokio::io::split(stream);
+                    let mut lines = BufReader::new(reader).lines();
+                    while let Ok(Some(line)) = lines.next_line().await {
+                        let reply = match handle(&line, &control).await { Ok(reply) => reply, Err(e) => json!({ "ok": false, "error": e }) };
+                        // Nové spuštění aplikace na odpověď nečeká, spojení už může být zavřené
+                        if writer.write_all(format!("{}\n", reply).as_bytes()).await.is_err() { break; }
+                    }
+                });
+            }
+        }));
+    }
+}
+
+// Jeden příkaz: název a parametry oddělené mezerami (export má stejné přepínače jako příkaz na příkazové řádce)
+async fn handle(line: &str, control: &Control) -> Result<Value, String> {
+    let words: Vec<String> = line.split_whitespace().map(String::from).collect();
+    let Some(command) = words.first() else { return Err(tr("prázdný příkaz").to_string()); };
+    info!("Místní řízení: {}", line.trim());
+    match command.as_str() {
+        SHOW_REQUEST => { (control.on_show)(); Ok(json!({ "ok": true })) },
+        "current" => {
+            let (reply_tx, reply_rx) = oneshot::channel();
+            send_to_processor(control, AppMessage::CurrentReadings(reply_tx))?;
+            let readings = reply_rx.await.map_err(|_| tr("měření se už nezpracovávají").to_string())?;
+            let config = control.config.borrow().clone();
+            let readings: Vec<Value> = readings.iter().map(|r| json!({
+                "mac": r.mac, "name": config.device_name(&r.mac), "timestamp": r.timestamp.to_rfc3339(), "temp": r.temp, "hum": r.hum, "battery": r.battery, "rssi": r.rssi,
+            })).collect();
+            Ok(json!({ "ok": true, "readings": readings }))
+        },
+        "export" => {
+            let config = control.config.borrow().clone();
+            let points = range_points(&words[1..], &config)?;
+            Ok(json!({ "ok": true, "readings": json_records(&points) }))
+        },
+        "pause" | "resume" => {
+            control.paused.send_replace(command == "pause");
+            Ok(json!({ "ok": true, "paused": command == "pause" }))
+        },
+        "test-alert" => {
+            send_to_processor(control, AppMessage::TestAlert)?;
+            Ok(json!({ "ok": true }))
+        },
+        _ => Err(format!("{} '{}'", tr("neznámý příkaz"), command)),
+    }
+}
+
+fn send_to_processor(control: &Control, message: AppMessage) -> Result<(), String> {
+    let processor = control.processor.upgrade().ok_or_else(|| tr("měření se už nezpracovávají").to_string())?;
+    processor.send(message).map_err(|_| tr("měření se už nezpracovávají").to_string())
+}
+
+// Pošle jeden příkaz instanci běžící nad touto složkou a vrátí její odpověď
+pub fn send(request: &str) -> io::Result<String> {
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+    runtime.block_on(async {
+        let (reader, mut writer) = tokio::io::split(platform::connect(&endpoint_name()).await?);
+        writer.write_all(format!("{}\n", request).as_bytes()).await?;
+        let mut reply = String::new();
+        BufReader::new(reader).read_line(&mut reply).await?;
+        Ok(reply.trim_end().to_string())
+    })
+}
@@ -0,0 +1,16 @@
+// --- Knihovna aplikace ---
+// Skenování senzorů, dekódování paketů, zpracování měření, ukládání a konfigurace nezávisle na okně,
+// aby je kromě GUI (main.rs) mohl použít i nástroj pro příkazovou řádku nebo služba na pozadí.
+pub mod ble;
+pub mod config;
+pub mod error;
+pub mod i18n;
+#[cfg(feature = "mock-ble")]
+pub mod mock_ble;
+pub mod parsing;
+pub mod power;
+pub mod processing;
+pub mod scanner;
+pub mod sinks;
+pub mod storage;
+pub mod units;
@@ -1,556 +1,1078 @@
 // Atribut pro skrytí konzolového okna ve finální verzi (v release buildu)
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+#[cfg(any(windows, target_os = "linux"))]
+mod autostart;
+#[cfg(windows)]
+mod badge;
+mod cli;
+#[cfg(windows)]
+mod clipboard;
+mod console;
+mod crash;
+mod headless;
+mod instance;
+mod report;
+mod stats;
+#[cfg(windows)]
+mod tray;
+mod views;
+
 // --- Importy ---
-use btleplug::api::{Central, CentralEvent, Manager as _, Peripheral, ScanFilter};
-use btleplug::platform::Manager;
-use chrono::{DateTime, Local, NaiveDateTime};
+use crate::stats::{check_anomalies, detect_anomalies, Anomaly, DataQuality, DaySummary};
+use crate::views::{humidity_to_color, value_to_color};
+use crate::views::calendar::draw_calendar_view;
+use crate::views::compare::{draw_baseline_view, draw_compare_view, draw_differential_view};
+use crate::views::devices::{draw_all_devices_view, draw_device_view};
+use crate::views::distribution::{draw_histogram_view, draw_profile_view, draw_scatter_view};
+use crate::views::plots::save_png;
+use crate::views::readout::{draw_data_details, draw_humidity_info, draw_scan_metadata, draw_temperature_info};
+use crate::views::table::draw_table_view;
+use chrono::{DateTime, Local, NaiveDate, Timelike};
 use eframe::egui;
 use egui_extras::{StripBuilder, Size};
 // OPRAVA: Odstraněn nepoužívaný PlotPoint
 use egui_plot::PlotMemory;
-use futures::stream::StreamExt;
 use serde::{Deserialize, Serialize};
-use std::cmp::Ordering;
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::fs;
-#[cfg(debug_assertions)]
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
 use log::{info, warn, error, debug};
+use temp_monitor::ble::DeviceInfo;
+use temp_monitor::config::{custom_config_path, import_settings, is_settings_bundle, load_config, save_config, set_custom_config_path, Config, ConfigWatcher, CONFIG_FILE, CONFIG_POLL_INTERVAL};
+use temp_monitor::error::Error;
+use temp_monitor::i18n::{set_language, tr, trf};
+use temp_monitor::power;
+use temp_monitor::processing::{background_data_processor, AppMessage, BleDataPoint, UiSender};
+use temp_monitor::scanner::supervised_scanner;
+use temp_monitor::sinks::OutputConfig;
+use temp_monitor::storage::{get_daily_log_filename, list_log_dates, load_annotations, load_history_from_csv, load_recent_points, log_filename_for_date, read_import_file, memory_window_start, save_history_snapshot, Annotation, HistoryPager, HistoryPoint, MAX_HISTORY_POINTS};
+use temp_monitor::units::{format_temp, set_temp_unit};
+#[cfg(windows)]
+use temp_monitor::units::TempUnit;
 
 // --- Konstanty a Konfigurace ---
-const MAX_HISTORY_POINTS: usize = 200;
-const CONFIG_FILE: &str = "config.json";
+// Po kolika snímcích od obnovení polohy se ověří, že okno skončilo na očekávaném monitoru
+const GEOMETRY_CHECK_FRAMES: u8 = 3;
+// Nejvýše tolik oznámení nad sebou; nejstarší při přetečení zmizí
+const MAX_TOASTS: usize = 5;
+// Od tohoto počtu senzorů je výchozí obrazovkou přehled s kartami
+const DASHBOARD_MIN_DEVICES: usize = 5;
+// Parametr pro spuštění rovnou v kioskovém režimu
+const KIOSK_ARG: &str = "--kiosk";
+// Konfigurace jinde než v pracovním adresáři, resp. přenosný režim se vším vedle programu
+const CONFIG_ARG: &str = "--config";
+const PORTABLE_ARG: &str = "--portable";
+// Stav okna a zobrazení při vlastním umístění konfigurace (jinak ho eframe ukládá do profilu uživatele, společný všem instancím)
+const UI_STATE_FILE: &str = "stav_aplikace.json";
+// Nejdéle tolik se při ukončení čeká na zastavení skeneru a na dopsání přijatých měření do CSV
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(3);
 
 // --- DATOVÉ STRUKTURY ---
 
-#[derive(Serialize, Deserialize, Clone, PartialEq)]
-struct Config {
-    target_mac: String,
-    scan_timeout_secs: u64,
-    scan_pause_secs: u64,
-    duplicate_threshold_secs: u64,
-    temp_warn_high: f32,
-    temp_warn_low: f32,
-    continuous_mode: bool,
-    load_all_history: bool,
+// Režim zobrazení grafů: surová data, nebo agregace do hodinových/denních intervalů (min–max + průměr)
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+enum AggregationMode { #[default] Raw, Hourly, Daily }
+
+impl AggregationMode {
+    const ALL: [AggregationMode; 3] = [AggregationMode::Raw, AggregationMode::Hourly, AggregationMode::Daily];
+    fn label(self) -> &'static str {
+        tr(match self { AggregationMode::Raw => "Surová data", AggregationMode::Hourly => "Hodinový přehled", AggregationMode::Daily => "Denní přehled" })
+    }
+    fn bucket_secs(self) -> f64 {
+        match self { AggregationMode::Raw => 0.0, AggregationMode::Hourly => 3600.0, AggregationMode::Daily => 86400.0 }
+    }
+    // Začátek intervalu (zarovnaný na celou hodinu / půlnoc v místním čase), do kterého bod patří
+    fn bucket_start(self, timestamp: DateTime<Local>) -> Option<DateTime<Local>> {
+        let naive = match self {
+            AggregationMode::Raw => return None,
+            AggregationMode::Hourly => timestamp.date_naive().and_hms_opt(timestamp.hour(), 0, 0)?,
+            AggregationMode::Daily => timestamp.date_naive().and_hms_opt(0, 0, 0)?,
+        };
+        naive.and_local_timezone(Local).earliest()
+    }
+}
+
+// Hlavní obsah okna pod záhlavím
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+enum View { #[default] Plots, Table, Histogram, Calendar, Compare, Baseline, Profile, Scatter, Differential }
+
+impl View {
+    const ALL: [View; 9] = [View::Plots, View::Table, View::Histogram, View::Calendar, View::Compare, View::Baseline, View::Profile, View::Scatter, View::Differential];
+    fn label(self) -> &'static str {
+        tr(match self { View::Plots => "📈 Grafy", View::Table => "☰ Tabulka", View::Histogram => "📊 Rozložení", View::Calendar => "📅 Kalendář", View::Compare => "⚖ Porovnání dnů", View::Baseline => "📏 Odchylka od reference", View::Profile => "🕑 Denní profil", View::Scatter => "⁘ Korelace", View::Differential => "Δ Rozdíl senzorů" })
+    }
 }
 
-impl Default for Config {
-    fn default() -> Self {
-        Self {
-            target_mac: "B8:59:CE:33:0F:93".to_string(),
-            scan_timeout_secs: 20,
-            scan_pause_secs: 20,
-            duplicate_threshold_secs: 30,
-            temp_warn_high: 30.0,
-            temp_warn_low: 10.0,
-            continuous_mode: true,
-            load_all_history: true,
+// Kategorie v okně nastavení
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+enum SettingsCategory { #[default] Device, Logging, Alerts, Display, Integrations }
+
+impl SettingsCategory {
+    const ALL: [SettingsCategory; 5] = [SettingsCategory::Device, SettingsCategory::Logging, SettingsCategory::Alerts, SettingsCategory::Display, SettingsCategory::Integrations];
+    fn label(self) -> &'static str {
+        tr(match self { SettingsCategory::Device => "📡 Zařízení", SettingsCategory::Logging => "💾 Záznam", SettingsCategory::Alerts => "🔔 Upozornění", SettingsCategory::Display => "🖥 Zobrazení", SettingsCategory::Integrations => "🔗 Integrace" })
+    }
+}
+
+// Záložka senzoru: hlavní senzor má všechny pohledy, další senzory vlastní hodnoty a grafy za 24 h
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+enum DeviceTab { #[default] Primary, All, Device(String) }
+
+// Sloupec, podle kterého se řadí tabulka měření
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+enum TableColumn { #[default] Time, Temp, Hum, Rssi }
+
+impl TableColumn {
+    const ALL: [TableColumn; 4] = [TableColumn::Time, TableColumn::Temp, TableColumn::Hum, TableColumn::Rssi];
+    fn label(self) -> &'static str {
+        tr(match self { TableColumn::Time => "Čas", TableColumn::Temp => "Teplota", TableColumn::Hum => "Vlhkost", TableColumn::Rssi => "RSSI (dBm)" })
+    }
+    fn compare(self, a: &HistoryPoint, b: &HistoryPoint) -> std::cmp::Ordering {
+        match self { TableColumn::Time => a.timestamp.cmp(&b.timestamp), TableColumn::Temp => a.temp.total_cmp(&b.temp), TableColumn::Hum => a.hum.cmp(&b.hum), TableColumn::Rssi => a.rssi.cmp(&b.rssi) }
+    }
+}
+
+// Hodnota, podle které se barví dny v kalendáři
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+enum CalendarMetric { #[default] AvgTemp, MaxTemp, AvgHum, MaxHum }
+
+impl CalendarMetric {
+    const ALL: [CalendarMetric; 4] = [CalendarMetric::AvgTemp, CalendarMetric::MaxTemp, CalendarMetric::AvgHum, CalendarMetric::MaxHum];
+    fn label(self) -> &'static str {
+        tr(match self { CalendarMetric::AvgTemp => "Průměrná teplota", CalendarMetric::MaxTemp => "Maximální teplota", CalendarMetric::AvgHum => "Průměrná vlhkost", CalendarMetric::MaxHum => "Maximální vlhkost" })
+    }
+    fn value(self, summary: &DaySummary) -> f64 {
+        match self { CalendarMetric::AvgTemp => summary.avg_temp, CalendarMetric::MaxTemp => summary.max_temp, CalendarMetric::AvgHum => summary.avg_hum, CalendarMetric::MaxHum => summary.max_hum }
+    }
+    fn format(self, value: f64) -> String {
+        match self { CalendarMetric::AvgTemp | CalendarMetric::MaxTemp => format_temp(value, 1), CalendarMetric::AvgHum | CalendarMetric::MaxHum => format!("{:.0}%", value) }
+    }
+    fn color(self, value: f64) -> egui::Color32 {
+        match self { CalendarMetric::AvgTemp | CalendarMetric::MaxTemp => value_to_color(value, 0.0, 40.0), CalendarMetric::AvgHum | CalendarMetric::MaxHum => humidity_to_color(value, 0.0, 100.0) }
+    }
+}
+
+// Období pro statistiky a odvozené pohledy (počítá se z historie v paměti)
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+enum Period { Last24h, Last7d, Last30d, #[default] All }
+
+impl Period {
+    const ALL: [Period; 4] = [Period::Last24h, Period::Last7d, Period::Last30d, Period::All];
+    fn label(self) -> &'static str {
+        tr(match self { Period::Last24h => "Posledních 24 h", Period::Last7d => "Posledních 7 dní", Period::Last30d => "Posledních 30 dní", Period::All => "Vše načtené" })
+    }
+    // Kalendářní dny, které období pokrývá (pro statistiky počítané z denních logů)
+    fn days(self) -> Vec<NaiveDate> {
+        let today = Local::now().date_naive();
+        let count = match self { Period::Last24h => 1, Period::Last7d => 7, Period::Last30d => 30, Period::All => return list_log_dates() };
+        (0..count).rev().map(|back| today - chrono::Duration::days(back)).collect()
+    }
+    fn start(self) -> Option<DateTime<Local>> {
+        let span = match self { Period::Last24h => chrono::Duration::hours(24), Period::Last7d => chrono::Duration::days(7), Period::Last30d => chrono::Duration::days(30), Period::All => return None };
+        Some(Local::now() - span)
+    }
+}
+
+// Předvolby rozsahu časové osy grafů (úsek končící posledním měřením)
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum TimeRange { Hour, SixHours, Day, Week, All, Today }
+
+impl TimeRange {
+    const ALL: [TimeRange; 5] = [TimeRange::Hour, TimeRange::SixHours, TimeRange::Day, TimeRange::Week, TimeRange::All];
+    fn label(self) -> &'static str {
+        tr(match self { TimeRange::Hour => "1 h", TimeRange::SixHours => "6 h", TimeRange::Day => "24 h", TimeRange::Week => "7 d", TimeRange::All => "Vše", TimeRange::Today => "Dnes" })
+    }
+    // Dnešek (jen z nabídky grafu) sahá od místní půlnoci
+    fn secs(self) -> Option<f64> {
+        match self {
+            TimeRange::Hour => Some(3600.0), TimeRange::SixHours => Some(6.0 * 3600.0), TimeRange::Day => Some(86400.0), TimeRange::Week => Some(7.0 * 86400.0), TimeRange::All => None,
+            TimeRange::Today => Some(Local::now().num_seconds_from_midnight() as f64),
         }
     }
 }
 
-#[derive(Clone, Debug)]
-struct HistoryPoint { timestamp: DateTime<Local>, temp: f32, hum: u8, }
-#[derive(Clone, Debug)]
-struct BleDataPoint { timestamp: DateTime<Local>, temp: f32, hum: u8, device_id: String, rssi: Option<i16>, raw_data: Vec<u8>, }
-enum AppMessage { NewData(BleDataPoint), StatusUpdate(String), CsvWriteStatus(bool), }
+// Filtr tabulky: období a podmínky na hodnoty ("hum > 70 a temp < 18")
+#[derive(Clone, PartialEq, Default)]
+struct TableFilter { period: Period, conditions: String }
+
+// Pořadí řádků tabulky (indexy do historie); přepočítá se při změně řazení, filtru nebo historie
+struct TableCache { key: (TableColumn, bool, usize, i64), filter: TableFilter, order: Vec<usize>, error: Option<String> }
+// Souhrn úseku vybraného tažením se Shiftem (hranice v sekundách časové osy)
+struct RangeStats { from: f64, to: f64, count: usize, temp: (f32, f32, f64), hum: (u8, u8, f64) }
+
+// Načtená data dvou porovnávaných dnů; klíč obsahuje i počet živých měření, pokud je jedním ze dnů dnešek
+struct CompareData { key: (NaiveDate, NaiveDate, usize), day_a: Vec<HistoryPoint>, day_b: Vec<HistoryPoint> }
+
+// Měření zvoleného období načtená z denních logů pro panel statistik; obnoví se se změnou období nebo novým měřením
+struct StatsCache { period: Period, last: Option<DateTime<Local>>, points: Vec<HistoryPoint> }
+
+// Export grafu do PNG: výřez ze snímku okna, pořízeného až ve snímku s časem exportu v nadpisu
+struct PlotExport { name: &'static str, taken_at: DateTime<Local>, rect: Option<egui::Rect>, requested: bool }
+// Závažnost oznámení určuje barvu, ikonu a dobu zobrazení
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Severity { Info, Success, Warning, Error }
+
+impl Severity {
+    fn icon(self) -> &'static str { match self { Severity::Info => "ℹ", Severity::Success => "✔", Severity::Warning => "⚠", Severity::Error => "✖" } }
+    fn color(self) -> egui::Color32 {
+        match self { Severity::Info => egui::Color32::LIGHT_BLUE, Severity::Success => egui::Color32::from_rgb(80, 200, 120), Severity::Warning => egui::Color32::GOLD, Severity::Error => egui::Color32::from_rgb(255, 90, 90) }
+    }
+}
+
+struct Toast { message: String, severity: Severity, created_at: Instant }
+// CSV přetažené do okna: grafy a pohledy ukazují jeho data, živá měření se mezitím ukládají stranou
+struct ImportedData { name: String, live_history: VecDeque<HistoryPoint> }
+
+// Poloha a velikost okna v normálním (nemaximalizovaném) stavu, v bodech; ukládá se pro každý monitor zvlášť
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+struct WindowGeometry { position: [f32; 2], size: [f32; 2], maximized: bool }
+
+impl WindowGeometry {
+    fn apply(&self, ctx: &egui::Context) {
+        ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(egui::pos2(self.position[0], self.position[1])));
+        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(egui::vec2(self.size[0], self.size[1])));
+        if self.maximized { ctx.send_viewport_cmd(egui::ViewportCommand::Maximized(true)); }
+    }
+}
 
 #[derive(Serialize, Deserialize)]
 #[serde(default)]
 struct TempMonitorApp {
-    config: Config,
+    // Konfigurace se vždy načítá z config.json (viz Default), do uloženého stavu okna nepatří
+    #[serde(skip)] config: Config,
     settings_open: bool,
-    #[serde(skip)] rx: mpsc::Receiver<AppMessage>,
-    #[serde(skip)] shared_config: Arc<Mutex<Config>>,
+    // Rozpracované nastavení; do `config` se dostane až tlačítkem OK nebo Použít
+    #[serde(skip)] settings_draft: Option<Config>,
+    settings_category: SettingsCategory,
+    #[serde(skip)] settings_search: String,
+    #[serde(skip)] imported: Option<ImportedData>,
+    aggregation: AggregationMode,
+    view: View,
+    histogram_period: Period,
+    histogram_temp_bin: f32,
+    histogram_hum_bin: u8,
+    calendar_metric: CalendarMetric,
+    stats_open: bool,
+    show_rate_plot: bool,
+    show_extremes: bool,
+    // Řazení tabulky měření (sloupec, sestupně)
+    table_sort: (TableColumn, bool),
+    // Teplota a vlhkost v jednom grafu s osou vlevo a vpravo (úspora místa na malých displejích)
+    combined_plot: bool,
+    // Řady skryté kliknutím v legendě, podle id grafu
+    hidden_series: HashMap<String, Vec<String>>,
+    stats_period: Period,
+    scatter_period: Period,
+    profile_period: Period,
+    // MAC adresy dvou senzorů, jejichž rozdíl se zobrazuje (A − B)
+    diff_devices: (String, String),
+    device_tab: DeviceTab,
+    // Přehled „Vše“ jako karty (None = automaticky podle počtu senzorů) a šířka karty
+    dashboard_cards: Option<bool>,
+    dashboard_card_width: f32,
+    // Referenční den, vůči kterému se počítá odchylka
+    baseline_day: Option<NaiveDate>,
+    // Geometrie okna podle rozlišení monitoru a monitor, na kterém bylo okno naposledy
+    window_geometry: HashMap<String, WindowGeometry>,
+    window_monitor: Option<String>,
+    // Monitor, na kterém má okno po obnovení skončit, a zbývající snímky do kontroly
+    #[serde(skip)] geometry_check: Option<(String, u8)>,
+    #[serde(skip)] geometry_restored: bool,
+    #[serde(skip)] calendar_month: Option<NaiveDate>,
+    #[serde(skip)] day_summaries: HashMap<NaiveDate, Option<DaySummary>>,
+    #[serde(skip)] day_quality: HashMap<NaiveDate, Option<DataQuality>>,
+    #[serde(skip)] detail_day: Option<(NaiveDate, Vec<HistoryPoint>)>,
+    #[serde(skip)] compare_days: (NaiveDate, NaiveDate),
+    #[serde(skip)] compare_cache: Option<CompareData>,
+    #[serde(skip)] table_cache: Option<TableCache>,
+    #[serde(skip)] table_filter: TableFilter,
+    // Vyfiltrovaná měření se zvýrazní i v grafech
+    #[serde(skip)] table_highlight: bool,
+    // Úsek časové osy právě vybíraný tažením se Shiftem (začátek, konec)
+    #[serde(skip)] range_selection: Option<(f64, f64)>,
+    #[serde(skip)] range_stats: Option<RangeStats>,
+    #[serde(skip)] baseline_target: NaiveDate,
+    #[serde(skip)] baseline_cache: Option<CompareData>,
+    #[serde(skip)] stats_cache: Option<StatsCache>,
+    #[serde(skip)] stats_loader: Option<(Period, mpsc::Receiver<Vec<HistoryPoint>>)>,
+    #[serde(skip)] report_open: bool,
+    #[serde(skip)] about_open: bool,
+    // Okno s protokolem aplikace: nejvyšší zobrazená úroveň a hledaný text
+    #[serde(skip)] log_window_open: bool,
+    #[serde(skip)] log_level: log::Level,
+    #[serde(skip)] log_search: String,
+    // Hlášení o pádu z minulého spuštění, které se nabídne k otevření
+    #[serde(skip)] crash_report: Option<PathBuf>,
+    // Panel varování a chyb; `console_seen` = počet záznamů, které už uživatel viděl
+    #[serde(skip)] console_open: bool,
+    #[serde(skip)] console_seen: usize,
+    #[serde(skip)] report_range: (NaiveDate, NaiveDate),
+    #[serde(skip)] rx: tokio::sync::mpsc::UnboundedReceiver<AppMessage>,
+    // Aktuální konfigurace pro skener, procesor a ikonu v oznamovací oblasti
+    #[serde(skip)] shared_config: tokio::sync::watch::Sender<Config>,
     #[serde(skip)] history: VecDeque<HistoryPoint>,
+    // Posledních 24 h nezávisle na limitu historie (pro klouzavé a dnešní min/max)
+    #[serde(skip)] recent_points: VecDeque<HistoryPoint>,
+    #[serde(skip)] anomalies: Vec<Anomaly>,
+    #[serde(skip)] annotations: Vec<Annotation>,
+    #[serde(skip)] annotation_draft: String,
     #[serde(skip)] last_data_point: Option<BleDataPoint>,
+    // Počet přijatých živých měření (značka pro obnovu pohledů obsahujících dnešek)
+    #[serde(skip)] live_readings: usize,
+    // Posledních 24 h a poslední měření dalších senzorů (klíč = MAC velkými písmeny)
+    #[serde(skip)] device_history: HashMap<String, VecDeque<HistoryPoint>>,
+    #[serde(skip)] device_last: HashMap<String, BleDataPoint>,
     #[serde(skip)] last_csv_write_ok: bool,
+    // Pro stavový řádek: začátek běhu a velikost dnešního logu (obnoví se po každém zápisu)
+    #[serde(skip)] started_at: Instant,
+    #[serde(skip)] log_file_size: Option<u64>,
     #[serde(skip)] scan_status: String,
+    // Skener naposledy narazil na chybějící oprávnění k Bluetooth (na macOS se nabídne otevření nastavení soukromí)
+    #[serde(skip)] bluetooth_permission_missing: bool,
     #[serde(skip)] zoom_factor: f32,
     #[serde(skip)] reset_plot: bool,
+    // Předvolba rozsahu zvolená v tomto snímku; grafy podle ní nastaví své osy
+    #[serde(skip)] pending_range: Option<TimeRange>,
+    // Rozsah z nabídky grafu se použije až v dalším snímku (grafy jsou v tomto už vykreslené)
+    #[serde(skip)] queued_range: Option<TimeRange>,
+    // Čas na ose x v místě, kde se otevřela nabídka grafu
+    #[serde(skip)] plot_menu_time: Option<f64>,
+    #[serde(skip)] plot_export: Option<PlotExport>,
+    #[serde(skip)] window_screenshot: bool,
     #[serde(skip)] background_processor: Option<thread::JoinHandle<()>>,
+    // Runtime skeneru; při ukončení se skener zastaví signálem, aby procesor dopsal frontu a skončil
+    #[serde(skip)] runtime: Option<tokio::runtime::Runtime>,
+    #[serde(skip)] scanner: Option<tokio::task::JoinHandle<()>>,
+    #[serde(skip)] scanner_shutdown: Option<tokio::sync::watch::Sender<bool>>,
+    // Změny config.json provedené mimo aplikaci a čas poslední kontroly
+    #[serde(skip)] config_watcher: ConfigWatcher,
+    #[serde(skip)] config_polled: Option<Instant>,
+    #[serde(skip)] shutdown_done: bool,
+    #[serde(skip)] exit_prompt: bool,
+    // Kompletní historie načítaná na pozadí po zapnutí `load_all_history`
+    #[serde(skip)] history_loader: Option<mpsc::Receiver<VecDeque<HistoryPoint>>>,
+    // Údaje ze služby Device Information podle adresy a právě probíhající čtení (adresa, výsledek)
+    #[serde(skip)] device_info: HashMap<String, DeviceInfo>,
+    #[serde(skip)] device_info_loader: Option<(String, mpsc::Receiver<Result<DeviceInfo, Error>>)>,
+    // Starší dny kompletní historie mimo paměťové okno a časový rozsah naposledy zobrazený v grafu
+    #[serde(skip)] history_pager: HistoryPager,
+    #[serde(skip)] plot_view: Option<(f64, f64)>,
     #[serde(skip)] config_changed: bool,
-    #[serde(skip)] toast_message: Option<(String, Instant)>,
+    #[serde(skip)] toasts: VecDeque<Toast>,
+    #[serde(skip)] rejected_count: usize,
+    #[serde(skip)] last_rejection: Option<String>,
+    #[serde(skip)] mini_mode: bool,
+    // Celá obrazovka jen s velkými hodnotami a grafem (F11 nebo --kiosk)
+    #[serde(skip)] kiosk: bool,
+    #[serde(skip)] full_size: Option<egui::Vec2>,
+    #[serde(skip)] tray_feed: Arc<Mutex<Option<mpsc::Sender<BleDataPoint>>>>,
+    #[cfg(windows)]
+    #[serde(skip)] tray: Option<tray::Tray>,
+    #[cfg(windows)]
+    #[serde(skip)] hwnd: Option<isize>,
+    #[cfg(windows)]
+    #[serde(skip)] quitting: bool,
+    // Text a barva naposledy vykreslené ikony na hlavním panelu (překresluje se jen při změně)
+    #[cfg(windows)]
+    #[serde(skip)] taskbar_badge: Option<(String, [u8; 3])>,
+    #[cfg(windows)]
+    #[serde(skip)] start_hidden: bool,
 }
 
 impl Default for TempMonitorApp {
     fn default() -> Self {
-        let (_tx, rx) = mpsc::channel();
+        let (_tx, rx) = tokio::sync::mpsc::unbounded_channel();
         Self {
-            config: load_config(), settings_open: false, rx, shared_config: Arc::new(Mutex::new(Config::default())),
-            history: VecDeque::new(), last_data_point: None, last_csv_write_ok: true, scan_status: "Inicializace...".to_string(),
-            zoom_factor: 1.0, reset_plot: false, background_processor: None, config_changed: false,
-            toast_message: None,
+            config: load_config(), settings_open: false, settings_draft: None, settings_category: SettingsCategory::Device, settings_search: String::new(),
+            imported: None, aggregation: AggregationMode::Raw, view: View::Plots,
+            histogram_period: Period::All, histogram_temp_bin: 0.5, histogram_hum_bin: 2,
+            calendar_metric: CalendarMetric::AvgTemp, stats_open: false, show_rate_plot: false, show_extremes: true, table_sort: (TableColumn::Time, true),
+            combined_plot: false, hidden_series: HashMap::new(), stats_period: Period::Last7d, scatter_period: Period::Last7d, profile_period: Period::Last7d,
+            diff_devices: (String::new(), String::new()), device_tab: DeviceTab::Primary, dashboard_cards: None, dashboard_card_width: 220.0,
+            calendar_month: None, day_summaries: HashMap::new(), day_quality: HashMap::new(), detail_day: None,
+            compare_days: (Local::now().date_naive(), Local::now().date_naive().pred_opt().unwrap_or_default()), compare_cache: None, table_cache: None,
+            table_filter: TableFilter::default(), table_highlight: false, range_selection: None, range_stats: None,
+            baseline_day: None, baseline_target: Local::now().date_naive(), baseline_cache: None,
+            window_geometry: HashMap::new(), window_monitor: None, geometry_check: None, geometry_restored: false,
+            stats_cache: None, stats_loader: None, report_open: false, about_open: false, log_window_open: false, log_level: log::Level::Info,
+            log_search: String::new(), crash_report: None, console_open: false, console_seen: 0,
+            report_range: (Local::now().date_naive() - chrono::Duration::days(6), Local::now().date_naive()), rx,
+            shared_config: tokio::sync::watch::channel(Config::default()).0,
+            history: VecDeque::new(), recent_points: VecDeque::new(), anomalies: Vec::new(), annotations: Vec::new(), annotation_draft: String::new(),
+            last_data_point: None, live_readings: 0, device_history: HashMap::new(), device_last: HashMap::new(), last_csv_write_ok: true,
+            started_at: Instant::now(), log_file_size: None, scan_status: tr("Inicializace...").to_string(), bluetooth_permission_missing: false,
+            zoom_factor: 1.0, reset_plot: false, pending_range: None, queued_range: None, plot_menu_time: None, plot_export: None, window_screenshot: false,
+            background_processor: None, runtime: None, scanner: None, scanner_shutdown: None, config_watcher: ConfigWatcher::new(), config_polled: None,
+            shutdown_done: false, exit_prompt: false, history_loader: None, device_info: HashMap::new(), device_info_loader: None,
+            history_pager: HistoryPager::default(), plot_view: None, config_changed: false,
+            toasts: VecDeque::new(), rejected_count: 0, last_rejection: None, mini_mode: false, kiosk: false, full_size: None,
+            tray_feed: Arc::new(Mutex::new(None)),
+            #[cfg(windows)]
+            tray: None,
+            #[cfg(windows)]
+            hwnd: None,
+            #[cfg(windows)]
+            quitting: false,
+            #[cfg(windows)]
+            taskbar_badge: None,
+            #[cfg(windows)]
+            start_hidden: false,
         }
     }
 }
 
 impl TempMonitorApp {
-    fn new(cc: &eframe::CreationContext<'_>) -> Self {
+    fn new(cc: &eframe::CreationContext<'_>, instance: Option<instance::Listener>) -> Self {
         info!("Vytváření nové instance aplikace TempMonitorApp.");
-        let mut app: Self = if let Some(storage) = cc.storage { eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default() } else { Default::default() };
-        let (gui_tx, gui_rx) = mpsc::channel(); let (scanner_tx, processor_rx) = mpsc::channel();
+        let mut app: Self = if custom_config_path().is_some() {
+            fs::read_to_string(UI_STATE_FILE).ok().and_then(|content| serde_json::from_str(&content).ok()).unwrap_or_default()
+        } else if let Some(storage) = cc.storage { eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default() } else { Default::default() };
+        set_language(app.config.language);
+        set_temp_unit(app.config.temp_unit);
+        #[cfg(windows)]
+        {
+            use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+            app.hwnd = match cc.window_handle().map(|h| h.as_raw()) { Ok(RawWindowHandle::Win32(handle)) => Some(handle.hwnd.get()), _ => None };
+            app.start_hidden = autostart::launched_by_autostart() && app.config.start_minimized && app.config.tray_icon;
+        }
+        #[cfg(any(windows, target_os = "linux"))]
+        if app.config.autostart {
+            if let Err(e) = autostart::set_enabled(true) { error!("Nelze obnovit automatické spuštění: {}", e); }
+        }
+        let (gui_tx, gui_rx) = tokio::sync::mpsc::unbounded_channel(); let (scanner_tx, processor_rx) = tokio::sync::mpsc::unbounded_channel();
         app.rx = gui_rx;
-        let shared_config = Arc::new(Mutex::new(app.config.clone()));
-        app.shared_config = shared_config.clone();
-        let processor_shared_config = shared_config.clone();
-        let processor = thread::spawn(move || { background_data_processor(processor_rx, gui_tx, processor_shared_config); });
+        app.shared_config = tokio::sync::watch::channel(app.config.clone()).0;
+        let processor_shared_config = app.shared_config.subscribe();
+        let tray_feed = app.tray_feed.clone();
+        let gui_tx = UiSender::with_repaint(gui_tx, cc.egui_ctx.clone());
+        let processor = thread::spawn(move || { background_data_processor(processor_rx, gui_tx, processor_shared_config, tray_feed); });
         app.background_processor = Some(processor);
         info!("Spouštím Bluetooth scanner v asynchronním vlákně.");
         let rt = tokio::runtime::Runtime::new().expect("Nelze vytvořit Tokio runtime");
-        rt.spawn(bluetooth_scanner(scanner_tx, shared_config));
-        std::mem::forget(rt);
-        app.history = load_history_from_csv();
+        let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+        let (paused_tx, paused_rx) = tokio::sync::watch::channel(false);
+        // Další spuštění aplikace jen zobrazí toto okno (i schované do oznamovací oblasti), skripty ji řídí přes `temp_monitor ctl`
+        if let Some(listener) = instance {
+            let ctx = cc.egui_ctx.clone();
+            #[cfg(windows)]
+            let hwnd = app.hwnd;
+            let on_show = Box::new(move || {
+                #[cfg(windows)]
+                tray::show_window(hwnd);
+                ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+                ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+                ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+                ctx.request_repaint();
+            });
+            listener.serve(instance::Control { on_show, config: app.shared_config.subscribe(), processor: scanner_tx.downgrade(), paused: paused_tx });
+        }
+        app.scanner = Some(rt.spawn(supervised_scanner(scanner_tx, app.shared_config.subscribe(), shutdown_rx, paused_rx)));
+        rt.spawn(power::monitor(app.shared_config.clone()));
+        app.scanner_shutdown = Some(shutdown_tx);
+        app.runtime = Some(rt);
+        app.kiosk = std::env::args().any(|arg| arg == KIOSK_ARG);
+        if app.device_tab == DeviceTab::Primary && app.config.all_devices().len() >= DASHBOARD_MIN_DEVICES { app.device_tab = DeviceTab::All; }
+        app.history = load_history_from_csv(app.config.load_all_history, app.config.load_older_logs, app.config.history_memory_days);
+        app.annotations = load_annotations();
+        app.crash_report = crash::take_pending_report();
+        app.log_file_size = fs::metadata(get_daily_log_filename()).ok().map(|m| m.len());
+        app.recent_points = load_recent_points(log_filename_for_date);
+        for (_, mac) in app.config.all_devices().into_iter().skip(1) {
+            let points = load_recent_points(|date| app.config.log_filename(&mac, date));
+            app.device_history.insert(mac, points);
+        }
+        if app.config.anomaly_detection { app.anomalies = detect_anomalies(&app.history, &app.config); }
         app
     }
 
     fn add_data_point(&mut self, data: BleDataPoint) {
         debug!("Aktualizuji UI s novým datovým bodem: {:?}", data);
+        if !self.config.is_primary(&data.mac) {
+            let points = self.device_history.entry(data.mac.to_uppercase()).or_default();
+            points.push_back(HistoryPoint { timestamp: data.timestamp, temp: data.temp, hum: data.hum, rssi: data.rssi });
+            let cutoff = Local::now() - chrono::Duration::hours(24);
+            while points.front().is_some_and(|p| p.timestamp < cutoff) { points.pop_front(); }
+            self.device_last.insert(data.mac.to_uppercase(), data);
+            return;
+        }
         let limit = if self.config.load_all_history { usize::MAX } else { MAX_HISTORY_POINTS };
-        while self.history.len() >= limit { self.history.pop_front(); }
-        let history_point = HistoryPoint { timestamp: data.timestamp, temp: data.temp, hum: data.hum };
-        self.history.push_back(history_point);
+        let history_point = HistoryPoint { timestamp: data.timestamp, temp: data.temp, hum: data.hum, rssi: data.rssi };
+        // Při prohlížení importovaného souboru se živá měření ukládají stranou a anomálie se přepočítají až po návratu
+        let window_start = memory_window_start(self.config.history_memory_days).filter(|_| self.config.loads_older_logs());
+        let live_history = self.live_history_mut();
+        while live_history.len() >= limit { live_history.pop_front(); }
+        // Dny mimo paměťové okno zůstávají jen na disku (graf si je případně načte přes history_pager)
+        if let Some(first) = window_start { while live_history.front().is_some_and(|p| p.timestamp.date_naive() < first) { live_history.pop_front(); } }
+        if self.config.anomaly_detection && self.imported.is_none() {
+            let window = self.history.len().saturating_sub(self.config.anomaly_window);
+            let found = check_anomalies(self.history.range(window..), &history_point, &self.config);
+            for anomaly in &found {
+                warn!("Detekována anomálie: {} {:.1} (z = {:.1})", if anomaly.is_temp { "teplota" } else { "vlhkost" }, anomaly.value, anomaly.z_score);
+                if self.config.anomaly_alert {
+                    let text = if anomaly.is_temp { format_temp(anomaly.value, 1) } else { format!("{:.0}%", anomaly.value) };
+                    self.toast(Severity::Warning, trf("Neobvyklé měření: {} (z = {})", &[&text, &format!("{:.1}", anomaly.z_score)]));
+                }
+            }
+            self.anomalies.extend(found);
+            if let Some(oldest) = self.history.front().map(|p| p.timestamp) { self.anomalies.retain(|a| a.timestamp >= oldest); }
+        }
+        // Souhrn dnešního dne v kalendáři je po novém měření zastaralý
+        self.day_summaries.remove(&data.timestamp.date_naive());
+        self.day_quality.remove(&data.timestamp.date_naive());
+        self.live_history_mut().push_back(history_point.clone());
+        self.recent_points.push_back(history_point);
+        let cutoff = Local::now() - chrono::Duration::hours(24);
+        while self.recent_points.front().is_some_and(|p| p.timestamp < cutoff) { self.recent_points.pop_front(); }
         self.last_data_point = Some(data);
+        self.live_readings += 1;
+    }
+
+    // Historie živých měření (při zobrazení importovaného souboru odložená stranou)
+    fn live_history_mut(&mut self) -> &mut VecDeque<HistoryPoint> {
+        match &mut self.imported { Some(imported) => &mut imported.live_history, None => &mut self.history }
+    }
+
+    // Přetažený CSV soubor se zobrazí místo živé historie; další soubor nahradí předchozí import
+    fn import_file(&mut self, path: &Path) {
+        let name = path.file_name().map_or_else(|| path.display().to_string(), |name| name.to_string_lossy().into_owned());
+        match read_import_file(path) {
+            Ok((points, skipped)) => {
+                info!("Importováno {} měření ze souboru '{}' ({} nečitelných řádků).", points.len(), path.display(), skipped);
+                let live_history = std::mem::replace(&mut self.history, points.into());
+                match &mut self.imported {
+                    Some(imported) => imported.name = name,
+                    None => self.imported = Some(ImportedData { name, live_history }),
+                }
+                self.range_selection = None;
+                self.range_stats = None;
+                self.history_changed();
+                if skipped > 0 { self.toast(Severity::Warning, trf("Importováno {} měření, {} řádků nešlo přečíst.", &[&self.history.len(), &skipped])); }
+                else { self.toast(Severity::Success, trf("Importováno {} měření.", &[&self.history.len()])); }
+            }
+            Err(e) => {
+                warn!("Soubor '{}' nelze importovat: {}", path.display(), e);
+                self.toast(Severity::Error, trf("Soubor {} nelze importovat: {}", &[&name, &e]));
+            }
+        }
+    }
+
+    // Nastavení z exportu jiného počítače; místní cesty a automatické spuštění zůstávají
+    fn import_settings_file(&mut self, path: &Path) {
+        let imported = fs::read_to_string(path).map_err(|e| Error::io(path, e)).and_then(|content| import_settings(&content, &self.config));
+        match imported {
+            Ok((config, changed)) => {
+                info!("Nastavení importováno ze souboru '{}'.", path.display());
+                self.settings_draft = None;
+                self.apply_settings(config);
+                match changed {
+                    Some(changed) => self.toast(Severity::Warning, trf("Nastavení importováno ({}).", &[&changed])),
+                    None => self.toast(Severity::Success, tr("Nastavení importováno.")),
+                }
+            },
+            Err(e) => {
+                warn!("Nastavení ze souboru '{}' nelze importovat: {}", path.display(), e);
+                self.toast(Severity::Error, trf("Nastavení nelze importovat: {}", &[&e]));
+            }
+        }
+    }
+
+    fn close_import(&mut self) {
+        let Some(imported) = self.imported.take() else { return; };
+        info!("Konec zobrazení importovaných dat '{}'.", imported.name);
+        self.history = imported.live_history;
+        self.range_selection = None;
+        self.range_stats = None;
+        self.history_changed();
+    }
+
+    // Soubory přetažené do okna; při přetahování nad oknem se zobrazí výzva k puštění
+    fn handle_dropped_files(&mut self, ctx: &egui::Context) {
+        let (hovering, dropped) = ctx.input(|i| (!i.raw.hovered_files.is_empty(), i.raw.dropped_files.iter().filter_map(|f| f.path.clone()).collect::<Vec<_>>()));
+        if hovering {
+            let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("drop_overlay")));
+            let rect = ctx.screen_rect();
+            painter.rect_filled(rect, 0.0, egui::Color32::from_black_alpha(180));
+            painter.text(rect.center(), egui::Align2::CENTER_CENTER, tr("Pusťte CSV soubor pro zobrazení jeho dat nebo export nastavení"), egui::FontId::proportional(24.0), egui::Color32::WHITE);
+        }
+        // Z více souborů najednou se zobrazí jen poslední; export nastavení se místo zobrazení použije
+        if let Some(path) = dropped.last() {
+            let is_settings = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")) && fs::read_to_string(path).is_ok_and(|content| is_settings_bundle(&content));
+            if is_settings { self.import_settings_file(path); } else { self.import_file(path); }
+        }
+    }
+
+    // Zavření okna jen schová aplikaci do oznamovací oblasti, měření běží dál
+    fn hides_on_close(&self) -> bool {
+        #[cfg(windows)]
+        { self.config.tray_icon && self.config.close_to_tray && !self.quitting }
+        #[cfg(not(windows))]
+        { false }
+    }
+
+    // Zastaví skener a počká, až procesor dopíše přijatá měření; `false`, když to nestihl
+    fn finish_background_work(&mut self) -> bool {
+        if let Some(runtime) = self.runtime.take() {
+            info!("Ukončuji Bluetooth scanner.");
+            if let Some(shutdown) = self.scanner_shutdown.take() { let _ = shutdown.send(true); }
+            if let Some(scanner) = self.scanner.take() {
+                if runtime.block_on(tokio::time::timeout(SHUTDOWN_TIMEOUT, scanner)).is_err() { warn!("Bluetooth scanner se nezastavil včas, ukončuji ho násilně."); }
+            }
+            runtime.shutdown_timeout(SHUTDOWN_TIMEOUT);
+        }
+        let deadline = Instant::now() + SHUTDOWN_TIMEOUT;
+        while self.background_processor.as_ref().is_some_and(|p| !p.is_finished()) && Instant::now() < deadline { thread::sleep(Duration::from_millis(20)); }
+        let finished = match self.background_processor.take() {
+            Some(processor) if !processor.is_finished() => { warn!("Background procesor stále zapisuje data."); self.background_processor = Some(processor); false }
+            Some(processor) => { let _ = processor.join(); info!("Rozpracovaná data zapsána, aplikaci lze ukončit."); true }
+            None => true,
+        };
+        if finished { self.save_history_snapshot(); }
+        finished
+    }
+
+    // Kompletní historii příští spuštění převezme ze snímku místo čtení všech logů. Jen pokud je v paměti celá
+    // (nenačítá se) a měření se zapisují do denních logů, podle kterých se snímek při načtení ověřuje.
+    fn save_history_snapshot(&self) {
+        if !self.config.loads_older_logs() || self.history_loader.is_some() || !self.config.outputs.contains(&OutputConfig::Csv) { return; }
+        let history = self.imported.as_ref().map_or(&self.history, |imported| &imported.live_history);
+        if let Err(e) = save_history_snapshot(history) { warn!("Snímek historie nelze uložit: {}", e); }
+    }
+
+    // Ikona okna s teplotou hlavního senzoru v barvě stavu (jako ikona v oznamovací oblasti); po vypnutí se vrátí ikona aplikace
+    #[cfg(windows)]
+    fn update_taskbar_badge(&mut self, ctx: &egui::Context) {
+        // Na baterii zůstane naposledy vykreslená ikona (vypnutí ikony s teplotou se ale projeví hned)
+        if self.config.taskbar_badge && self.config.battery_saving() { return; }
+        let wanted = self.last_data_point.as_ref().filter(|_| self.config.taskbar_badge).map(|data| {
+            let unit = temp_unit();
+            let text = format!("{:.0}{}", unit.convert(data.temp as f64), if unit == TempUnit::Kelvin { "" } else { "°" });
+            let color = if data.temp > self.config.temp_warn_high { [255, 215, 0] } else if data.temp < self.config.temp_warn_low { [120, 180, 255] } else { [255, 100, 100] };
+            (text, color)
+        });
+        if wanted == self.taskbar_badge { return; }
+        let icon = wanted.as_ref().map(|(text, color)| Arc::new(badge::temperature_icon(text, *color)));
+        ctx.send_viewport_cmd(egui::ViewportCommand::Icon(icon));
+        self.taskbar_badge = wanted;
+    }
+
+    // Vytvoří nebo zruší ikonu v oznamovací oblasti podle nastavení, vyřídí akce z jejího menu a případně schová okno místo zavření
+    #[cfg(windows)]
+    fn update_tray(&mut self, ctx: &egui::Context) {
+        if !self.config.tray_icon {
+            if self.tray.take().is_some() { *self.tray_feed.lock().unwrap() = None; }
+            return;
+        }
+        if self.tray.is_none() {
+            let tray = tray::Tray::spawn(self.hwnd, ctx.clone(), self.shared_config.subscribe());
+            if let Some(data) = &self.last_data_point { let _ = tray.readings.send(data.clone()); }
+            *self.tray_feed.lock().unwrap() = Some(tray.readings.clone());
+            self.tray = Some(tray);
+        }
+        let Some(tray) = &self.tray else { return; };
+        if self.start_hidden { tray.hide_window(); self.start_hidden = false; }
+        match tray.poll() {
+            Some(tray::TrayAction::Open) => ctx.send_viewport_cmd(egui::ViewportCommand::Focus),
+            Some(tray::TrayAction::Settings) => self.settings_open = true,
+            Some(tray::TrayAction::Profile(name)) => self.switch_profile(&name),
+            Some(tray::TrayAction::Quit) => { self.quitting = true; ctx.send_viewport_cmd(egui::ViewportCommand::Close); }
+            None => {}
+        }
+        if self.config.close_to_tray && !self.quitting && ctx.input(|i| i.viewport().close_requested()) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            tray.hide_window();
+            info!("Okno schováno do oznamovací oblasti, měření běží dál.");
+        }
     }
 }
 
 // --- Logika GUI ---
 impl eframe::App for TempMonitorApp {
+    // Při vlastním umístění konfigurace se nic neukládá do profilu uživatele, ani paměť egui
+    fn persist_egui_memory(&self) -> bool { custom_config_path().is_none() }
+
+    // Záloha pro ukončení jinou cestou než přes zavření okna v update(): i tak se skener zastaví a procesor dopíše data
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        if !self.shutdown_done { self.finish_background_work(); }
+    }
+
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
-        eframe::set_value(storage, eframe::APP_KEY, self);
+        if custom_config_path().is_some() {
+            if let Err(e) = serde_json::to_string_pretty(self).map_err(std::io::Error::from).and_then(|content| fs::write(UI_STATE_FILE, content)) { warn!("Nelze uložit stav aplikace do '{}': {}", UI_STATE_FILE, e); }
+        } else {
+            eframe::set_value(storage, eframe::APP_KEY, self);
+        }
         if self.config_changed {
             info!("Změna v konfiguraci detekována, ukládám do souboru.");
-            save_config(&self.config);
+            if let Err(e) = save_config(&self.config) {
+                error!("Nelze uložit konfiguraci: {}", e);
+                self.toast(Severity::Error, trf("Nastavení nelze uložit: {}", &[&e]));
+            }
+            self.config_watcher.mark_saved();
             self.config_changed = false;
         }
     }
 
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        ctx.request_repaint_after(Duration::from_secs(1));
+        // Nová měření a stav skeneru okno probudí sama (UiSender); bez nich stačí občas posunout údaje závislé na čase
+        // (stáří měření, doba běhu) a zkontrolovat config.json. Načítání historie na pozadí se hlídá častěji.
+        ctx.request_repaint_after(if self.history_loader.is_some() || self.device_info_loader.is_some() || self.stats_loader.is_some() { Duration::from_millis(200) } else { self.config.refresh_interval() });
         while let Ok(message) = self.rx.try_recv() {
             match message {
                 AppMessage::NewData(data_point) => self.add_data_point(data_point),
-                AppMessage::StatusUpdate(status) => { debug!("Aktualizace stavu skeneru: {}", status); self.scan_status = status; },
-                AppMessage::CsvWriteStatus(ok) => self.last_csv_write_ok = ok,
+                AppMessage::StatusUpdate(status) => { debug!("Aktualizace stavu skeneru: {}", status); self.scan_status = status; self.bluetooth_permission_missing = false; },
+                AppMessage::CsvWriteStatus(ok) => { self.last_csv_write_ok = ok; self.log_file_size = fs::metadata(get_daily_log_filename()).ok().map(|m| m.len()); },
+                AppMessage::ReadingRejected(reason) => { self.rejected_count += 1; self.last_rejection = Some(reason); },
+                // Podrobnosti už zapsal do konzole varování a chyb ten, kdo chybu poslal
+                AppMessage::Error(e) => { self.bluetooth_permission_missing = matches!(e, Error::PermissionDenied); self.scan_status = e.to_string(); },
+                AppMessage::TestAlert => self.toast(Severity::Warning, tr("Zkušební upozornění")),
+                // Dotaz místního řízení vyřizuje procesor, do okna nedorazí
+                AppMessage::CurrentReadings(_) => {}
             }
         }
+        self.config.on_battery = self.shared_config.borrow().on_battery;
+        self.poll_history_loader();
+        self.poll_device_info();
+        self.reload_changed_config();
+        #[cfg(windows)]
+        self.update_tray(ctx);
+        #[cfg(windows)]
+        self.update_taskbar_badge(ctx);
+        if ctx.input(|i| i.viewport().close_requested()) && !self.hides_on_close() && !self.shutdown_done {
+            if self.finish_background_work() { self.shutdown_done = true; } else { ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose); self.exit_prompt = true; }
+        }
+        self.draw_exit_prompt(ctx);
+        self.handle_dropped_files(ctx);
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::F12)) { self.request_window_screenshot(ctx); }
+        if let Some(screenshot) = ctx.input(|i| i.events.iter().find_map(|e| match e { egui::Event::Screenshot { image, .. } => Some(image.clone()), _ => None })) {
+            if std::mem::take(&mut self.window_screenshot) { self.save_window_screenshot(&screenshot); }
+            self.save_plot_export(&screenshot, ctx.pixels_per_point());
+        }
         let mut visual = egui::Visuals::dark();
-        visual.window_fill = egui::Color32::from_rgba_unmultiplied(20, 20, 20, 240);
+        if self.config.high_contrast {
+            visual.override_text_color = Some(egui::Color32::WHITE);
+            (visual.panel_fill, visual.window_fill, visual.extreme_bg_color) = (egui::Color32::BLACK, egui::Color32::BLACK, egui::Color32::BLACK);
+            visual.window_stroke = egui::Stroke::new(2.0, egui::Color32::WHITE);
+            (visual.window_shadow, visual.popup_shadow) = (egui::epaint::Shadow::NONE, egui::epaint::Shadow::NONE);
+            // Okraje ovládacích prvků, oddělovače i mřížka grafů jsou výraznější
+            visual.widgets.noninteractive.bg_stroke = egui::Stroke::new(1.5, egui::Color32::from_gray(200));
+            for widget in [&mut visual.widgets.inactive, &mut visual.widgets.hovered, &mut visual.widgets.active, &mut visual.widgets.open] {
+                widget.bg_stroke = egui::Stroke::new(widget.bg_stroke.width.max(1.5), egui::Color32::WHITE);
+                widget.fg_stroke.width = widget.fg_stroke.width.max(2.0);
+            }
+        } else {
+            visual.window_fill = egui::Color32::from_rgba_unmultiplied(20, 20, 20, 240);
+        }
         ctx.set_visuals(visual);
-        egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| {
-            egui::menu::bar(ui, |ui| {
-                ui.menu_button("Soubor", |ui| {
-                    if ui.button("Nastavení").clicked() { self.settings_open = true; ui.close_menu(); }
-                    if ui.button("Ukončit").clicked() { ctx.send_viewport_cmd(egui::ViewportCommand::Close); }
-                });
-                ui.separator();
-                if ui.button("➖").on_hover_text("Oddálit").clicked() { self.zoom_factor = 0.7; }
-                if ui.button("➕").on_hover_text("Přiblížit").clicked() { self.zoom_factor = 1.25; }
-                if ui.button("⛶").on_hover_text("Vycentrovat graf").clicked() { self.reset_plot = true; }
-            });
-        });
+        self.track_window_geometry(ctx);
+        // Nastavení otevřené z menu ikony potřebuje plné okno
+        if self.mini_mode && self.settings_open { self.set_mini_mode(ctx, false); }
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::F11)) || (self.kiosk && ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Escape))) {
+            self.set_kiosk(ctx, !self.kiosk);
+        }
+        if self.kiosk { self.draw_kiosk(ctx); self.draw_toasts(ctx); return; }
+        if self.mini_mode { self.draw_mini_widget(ctx); return; }
+        egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| egui::menu::bar(ui, |ui| self.draw_menu_bar(ui, ctx)));
+        self.draw_import_banner(ctx);
         if self.reset_plot { info!("Resetuji pohled grafů."); ctx.memory_mut(|memory| { memory.data.remove::<PlotMemory>(egui::Id::new("linked_plots")); }); }
         
+        if self.stats_open {
+            egui::SidePanel::right("stats_panel").resizable(true).default_width(260.0).show(ctx, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| self.draw_stats_panel(ui));
+            });
+        }
+
+        egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| self.draw_status_bar(ui));
+        if self.console_open {
+            egui::TopBottomPanel::bottom("console_panel").resizable(true).default_height(140.0).show(ctx, |ui| self.draw_console(ui));
+        }
         egui::CentralPanel::default().show(ctx, |ui| {
+            self.draw_device_tabs(ui);
+            match self.device_tab.clone() {
+                DeviceTab::All => return draw_all_devices_view(self, ui),
+                DeviceTab::Device(mac) => return draw_device_view(self, ui, &mac),
+                DeviceTab::Primary => {}
+            }
             StripBuilder::new(ui)
-                .size(Size::relative(0.10)).size(Size::relative(0.425)).size(Size::relative(0.425)).size(Size::relative(0.05))
+                .size(Size::relative(0.10)).size(Size::remainder())
                 .vertical(|mut strip| {
                     strip.cell(|ui| { ui.columns(4, |columns| {
-                        columns[0].vertical_centered(|ui| draw_temperature_info(ui, &self.history, &self.config));
-                        columns[1].vertical_centered(|ui| draw_humidity_info(ui, &self.history));
-                        columns[2].vertical(|ui| draw_scan_metadata(ui, &self.last_data_point, &self.scan_status));
-                        columns[3].vertical(|ui| draw_data_details(ui, &self.last_data_point, self.last_csv_write_ok));
+                        columns[0].vertical_centered(|ui| draw_temperature_info(ui, &self.history, &self.recent_points, &self.config));
+                        columns[1].vertical_centered(|ui| draw_humidity_info(ui, &self.history, &self.recent_points, &self.config));
+                        columns[2].vertical(|ui| draw_scan_metadata(ui, &self.last_data_point, &self.scan_status, &self.config));
+                        columns[3].vertical(|ui| {
+                            draw_data_details(ui, &self.last_data_point, self.last_csv_write_ok, self.rejected_count, &self.last_rejection);
+                            self.draw_device_info(ui);
+                        });
                     });});
-                    strip.cell(|ui| { ui.label(egui::RichText::new("Teplota").size(14.0).strong()); draw_temperature_graph(self, ui, ctx); });
-                    strip.cell(|ui| { ui.label(egui::RichText::new("Vlhkost").size(14.0).strong()); draw_humidity_graph(self, ui, ctx); });
-                    strip.cell(|ui| { ui.separator(); ui.vertical_centered(|ui| { ui.horizontal_centered(|ui| { ui.label(egui::RichText::new(format!("Autorem aplikace je Soběslav Holec")).size(20.0).color(egui::Color32::WHITE)); });});});
+                    strip.cell(|ui| match self.view {
+                        View::Plots => self.draw_plots_view(ui, ctx),
+                        View::Table => draw_table_view(self, ui),
+                        View::Histogram => draw_histogram_view(self, ui),
+                        View::Calendar => draw_calendar_view(self, ui),
+                        View::Compare => draw_compare_view(self, ui),
+                        View::Baseline => draw_baseline_view(self, ui),
+                        View::Profile => draw_profile_view(self, ui),
+                        View::Differential => draw_differential_view(self, ui),
+                        View::Scatter => draw_scatter_view(self, ui),
+                    });
                 });
         });
 
-        if let Some((message, created_at)) = &self.toast_message {
-            egui::Area::new("toast_area".into())
-                .anchor(egui::Align2::CENTER_BOTTOM, egui::vec2(0.0, -20.0))
-                .show(ctx, |ui| {
-                    let frame = egui::Frame::popup(ui.style());
-                    frame.show(ui, |ui| { ui.label(message); });
-                });
-            if created_at.elapsed() > Duration::from_secs(3) {
-                self.toast_message = None;
-            }
-        }
+        self.draw_toasts(ctx);
 
         if self.zoom_factor != 1.0 { self.zoom_factor = 1.0; }
         if self.reset_plot { self.reset_plot = false; }
+        self.pending_range = self.queued_range.take();
         self.draw_settings_window(ctx);
+        self.draw_day_detail_window(ctx);
+        self.draw_report_window(ctx);
+        self.draw_about_window(ctx);
+        self.draw_log_window(ctx);
+        self.draw_crash_report_window(ctx);
+        self.draw_range_stats_window(ctx);
     }
 }
 
 impl TempMonitorApp {
-    fn draw_settings_window(&mut self, ctx: &egui::Context) {
-        if self.settings_open {
-            let mut is_open = self.settings_open;
-            let old_config = self.config.clone();
-            egui::Window::new("Nastavení").open(&mut is_open).show(ctx, |ui| {
-                ui.label("Cílová MAC adresa:"); ui.text_edit_singleline(&mut self.config.target_mac);
-                ui.separator();
-                ui.add(egui::DragValue::new(&mut self.config.scan_timeout_secs).prefix("Timeout skenování (s): "));
-                ui.add(egui::DragValue::new(&mut self.config.scan_pause_secs).prefix("Pauza mezi skeny (s): "));
-                ui.separator();
-                ui.add(egui::DragValue::new(&mut self.config.duplicate_threshold_secs).prefix("Interval pro duplikáty (s): "));
-                ui.label("Záznamy ze stejného zařízení budou ignorovány po tuto dobu.");
-                ui.separator();
-                ui.checkbox(&mut self.config.continuous_mode, "Kontinuální režim");
-                ui.label("⚠️ Kontinuální režim pouze zrychluje skenování, stále platí interval pro duplikáty.");
-                ui.separator();
-                ui.checkbox(&mut self.config.load_all_history, "Načíst kompletní historii z CSV při startu");
-                ui.label("⚠️ Restartujte aplikaci, aby se změna projevila.");
-                if self.config.load_all_history { ui.label(egui::RichText::new("POZOR: Může zpomalit start.").color(egui::Color32::YELLOW)); }
-                ui.separator();
-                ui.add(egui::DragValue::new(&mut self.config.temp_warn_high).prefix("Mez pro varování (°C): ").speed(0.1));
-                ui.add(egui::DragValue::new(&mut self.config.temp_warn_low).prefix("Spodní mez (°C): ").speed(0.1));
-            });
-            if !is_open || self.config != old_config {
-                if self.config != old_config { info!("Detekována změna v nastavení."); self.config_changed = true; }
-                if let Ok(mut shared) = self.shared_config.lock() { *shared = self.config.clone(); debug!("Sdílená konfigurace byla aktualizována."); }
-            }
-            self.settings_open = is_open;
+    // Přepnutí velikosti historie bez restartu: zkrácení hned, kompletní historie se načte na pozadí
+    fn apply_history_size(&mut self) {
+        self.history_pager.clear();
+        if self.config.load_all_history {
+            let (tx, rx) = mpsc::channel();
+            let (older_logs, memory_days) = (self.config.load_older_logs, self.config.history_memory_days);
+            thread::spawn(move || { let _ = tx.send(load_history_from_csv(true, older_logs, memory_days)); });
+            self.history_loader = Some(rx);
+        } else {
+            self.history_loader = None;
+            let live_history = self.live_history_mut();
+            let excess = live_history.len().saturating_sub(MAX_HISTORY_POINTS);
+            live_history.drain(..excess);
+            info!("Historie v paměti zkrácena na {} bodů.", live_history.len());
+            if self.imported.is_none() { self.history_changed(); }
         }
     }
-}
 
-// --- Pomocná funkce: mapování hodnot na barvu ---
-// 0.0 → modrá, ~0.33 → zelená, ~0.66 → oranžová, 1.0 → červená
-fn value_to_color(value: f64, min: f64, max: f64) -> egui::Color32 {
-    let t = ((value - min) / (max - min)).clamp(0.0, 1.0);
-
-    if t < 0.33 {
-        // modrá → zelená
-        egui::Color32::from_rgb(0, (t * 3.0 * 255.0) as u8, 255)
-    } else if t < 0.66 {
-        // zelená → oranžová
-        egui::Color32::from_rgb(0, 255, 255 - ((t - 0.33) * 3.0 * 255.0) as u8)
-    } else {
-        // oranžová → červená
-        egui::Color32::from_rgb(255, 255 - ((t - 0.66) * 3.0 * 255.0) as u8, 0)
+    // Načtená historie nahradí tu v paměti; měření přijatá během načítání se připojí na konec
+    fn poll_history_loader(&mut self) {
+        let Some(rx) = &self.history_loader else { return; };
+        let mut loaded = match rx.try_recv() {
+            Ok(loaded) => loaded,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => { error!("Načítání historie na pozadí selhalo."); self.history_loader = None; return; }
+        };
+        self.history_loader = None;
+        let last = loaded.back().map(|p| p.timestamp);
+        let live_history = self.live_history_mut();
+        loaded.extend(live_history.drain(..).filter(|p| last.is_none_or(|last| p.timestamp > last)));
+        *live_history = loaded;
+        let count = live_history.len();
+        if self.imported.is_none() { self.history_changed(); }
+        self.toast(Severity::Info, trf("Načtena kompletní historie ({} měření).", &[&count]));
     }
-}
 
-fn humidity_to_color(value: f64, min: f64, max: f64) -> egui::Color32 {
-    let t = ((value - min) / (max - min)).clamp(0.0, 1.0);
+    fn history_changed(&mut self) {
+        self.anomalies = if self.config.anomaly_detection { detect_anomalies(&self.history, &self.config) } else { Vec::new() };
+        self.reset_plot = true;
+    }
 
-    if t < 0.33 {
-        // červená → oranžová
-        egui::Color32::from_rgb(255, (t * 3.0 * 255.0) as u8, 0)
-    } else if t < 0.66 {
-        // oranžová → zelená
-        egui::Color32::from_rgb(255 - ((t - 0.33) * 3.0 * 255.0) as u8, 255, 0)
-    } else {
-        // zelená → modrá
-        egui::Color32::from_rgb(0, 255 - ((t - 0.66) * 3.0 * 255.0) as u8, 255)
+    fn toast(&mut self, severity: Severity, message: impl Into<String>) {
+        if self.toasts.len() == MAX_TOASTS { self.toasts.pop_front(); }
+        self.toasts.push_back(Toast { message: message.into(), severity, created_at: Instant::now() });
     }
-}
 
-// --- Vykreslovací funkce ---
-
-fn draw_temperature_graph(app: &mut TempMonitorApp, ui: &mut egui::Ui, ctx: &egui::Context) {
-    use egui_plot::{GridMark, Line, Plot, Points, PlotPoints};
-    let temp_data_points: Vec<[f64; 2]> = app.history.iter().map(|p| [p.timestamp.timestamp() as f64, p.temp as f64]).collect();
-    let temp_line = Line::new(PlotPoints::new(temp_data_points.clone())).color(egui::Color32::from_rgb(255, 100, 100)).width(2.0);
-
-    let mut plot = Plot::new("temperature_plot").height(ui.available_height()).width(ui.available_width())
-        .link_axis(egui::Id::new("linked_plots"), true, false).show_background(false).allow_drag(true).allow_zoom(true)
-        .auto_bounds(egui::Vec2b::new(true, true)).show_x(false)
-        .label_formatter(|_name, value| { let time = DateTime::from_timestamp(value.x as i64, 0).unwrap_or_default().with_timezone(&Local); format!("Čas: {}\nTeplota: {:.1}°C", time.format("%H:%M:%S"), value.y) })
-        .x_axis_formatter(|mark: GridMark, _, _| { let time = DateTime::from_timestamp(mark.value as i64, 0).unwrap_or_default().with_timezone(&Local); time.format("%H:%M").to_string() })
-        .y_axis_formatter(|mark: GridMark, _, _| format!("{:.1}°C", mark.value));
-    if app.reset_plot { plot = plot.reset(); }
-    if let (Some(min), Some(max)) = (app.history.iter().map(|p| p.temp).min_by(|a, b| a.partial_cmp(b).unwrap()), app.history.iter().map(|p| p.temp).max_by(|a, b| a.partial_cmp(b).unwrap())) {
-        if (max - min).abs() < f32::EPSILON { plot = plot.include_y(min - 0.5).include_y(max + 0.5); }
-    }
-
-    // OPRAVA: Výsledek se už neukládá do proměnné
-    plot.show(ui, |plot_ui| {
-        // křivka
-        plot_ui.line(temp_line);
-
-        // barevné body podle hodnoty (-10 až 50 °C)
-        for p in app.history.iter() {
-            let x = p.timestamp.timestamp() as f64;
-            let y = p.temp as f64;
-            let color = value_to_color(y, 0.0, 40.0);
-            let pp = PlotPoints::new(vec![[x, y]]);
-            plot_ui.points(
-                Points::new(pp)
-                    .radius(3.0)
-                    .color(color)
-                    .highlight(true)
-            );
-        }
-
-        if app.zoom_factor != 1.0 { plot_ui.zoom_bounds(egui::vec2(app.zoom_factor, app.zoom_factor), plot_ui.plot_bounds().center()); }
-        
-        if plot_ui.response().clicked() {
-            if let Some(pos) = plot_ui.pointer_coordinate() {
-                let closest_point = app.history.iter().min_by_key(|p| (p.timestamp.timestamp() as f64 - pos.x).abs() as u64);
-                if let Some(point) = closest_point {
-                    if (point.temp as f64 - pos.y).abs() < 1.0 {
-                        let text_to_copy = format!("Čas: {}, Teplota: {:.1}°C", point.timestamp.format("%H:%M:%S"), point.temp);
-                        ctx.output_mut(|o| o.copied_text = text_to_copy.clone());
-                        app.toast_message = Some(("Zkopírováno do schránky!".to_owned(), Instant::now()));
-                        info!("Zkopírováno do schránky: {}", text_to_copy);
-                    }
-                }
-            }
+    fn request_window_screenshot(&mut self, ctx: &egui::Context) {
+        self.window_screenshot = true;
+        ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot);
+    }
+
+    // Celé okno do PNG vedle logů a (na Windows) zároveň do schránky
+    fn save_window_screenshot(&mut self, screenshot: &egui::ColorImage) {
+        let filename = format!("snimek_{}.png", Local::now().format("%Y-%m-%d_%H%M%S"));
+        if let Err(e) = save_png(&filename, screenshot) {
+            error!("Uložení snímku okna selhalo: {}", e);
+            self.toast(Severity::Error, trf("Snímek okna se nepodařilo uložit: {}", &[&e]));
+            return;
         }
-    });
-}
+        info!("Snímek okna uložen do '{}'.", filename);
+        #[cfg(windows)]
+        let (severity, message) = match clipboard::copy_image(screenshot) {
+            Ok(()) => (Severity::Success, trf("Snímek okna zkopírován do schránky a uložen do '{}'.", &[&filename])),
+            Err(e) => { error!("Snímek okna nelze zkopírovat do schránky: {}", e); (Severity::Warning, trf("Snímek okna uložen do '{}'.", &[&filename])) }
+        };
+        #[cfg(not(windows))]
+        let (severity, message) = (Severity::Success, trf("Snímek okna uložen do '{}'.", &[&filename]));
+        self.toast(severity, message);
+    }
 
-fn draw_humidity_graph(app: &mut TempMonitorApp, ui: &mut egui::Ui, ctx: &egui::Context) {
-    use egui_plot::{GridMark, Line, Plot, Points, PlotPoints};
-    let hum_data_points: Vec<_> = app.history.iter().map(|p| [p.timestamp.timestamp() as f64, p.hum as f64]).collect();
-    let hum_line = Line::new(PlotPoints::new(hum_data_points.clone())).color(egui::Color32::from_rgb(100, 100, 255)).width(2.0);
-
-    let mut plot = Plot::new("humidity_plot").height(ui.available_height()).width(ui.available_width())
-        .link_axis(egui::Id::new("linked_plots"), true, false).show_background(false).allow_drag(true).allow_zoom(true)
-        .auto_bounds(egui::Vec2b::new(true, true)).show_axes([true, true])
-        .label_formatter(|_name, value| { let time = DateTime::from_timestamp(value.x as i64, 0).unwrap_or_default().with_timezone(&Local); format!("Čas: {}\nVlhkost: {:.0}%", time.format("%H:%M:%S"), value.y) })
-        .x_axis_formatter(|mark: GridMark, _, _| { let time = DateTime::from_timestamp(mark.value as i64, 0).unwrap_or_default().with_timezone(&Local); time.format("%H:%M").to_string() })
-        .y_axis_formatter(|mark: GridMark, _, _| format!("{:.0}%", mark.value));
-    if app.reset_plot { plot = plot.reset(); }
-    if let (Some(min), Some(max)) = (app.history.iter().map(|p| p.hum).min(), app.history.iter().map(|p| p.hum).max()) {
-        if min == max { plot = plot.include_y(min as f64 - 1.0).include_y(max as f64 + 1.0); }
-    }
-    
-    plot.show(ui, |plot_ui| {
-        // křivka
-        plot_ui.line(hum_line);
-
-        // barevné body podle hodnoty (0 až 100 %)
-        for p in app.history.iter() {
-            let x = p.timestamp.timestamp() as f64;
-            let y = p.hum as f64;
-            let color = humidity_to_color(y, 0.0, 100.0);
-            let pp = PlotPoints::new(vec![[x, y]]);
-            plot_ui.points(
-                Points::new(pp)
-                    .radius(3.0)
-                    .color(color)
-                    .highlight(true)
-            );
-        }
-
-        if app.zoom_factor != 1.0 { plot_ui.zoom_bounds(egui::vec2(app.zoom_factor, app.zoom_factor), plot_ui.plot_bounds().center()); }
-        
-        if plot_ui.response().clicked() {
-            if let Some(pos) = plot_ui.pointer_coordinate() {
-                let closest_point = app.history.iter().min_by_key(|p| (p.timestamp.timestamp() as f64 - pos.x).abs() as u64);
-                if let Some(point) = closest_point {
-                    if (point.hum as f64 - pos.y).abs() < 2.0 {
-                        let text_to_copy = format!("Čas: {}, Vlhkost: {}%", point.timestamp.format("%H:%M:%S"), point.hum);
-                        ctx.output_mut(|o| o.copied_text = text_to_copy.clone());
-                        app.toast_message = Some(("Zkopírováno do schránky!".to_owned(), Instant::now()));
-                        info!("Zkopírováno do schránky: {}", text_to_copy);
-                    }
-                }
+    // Při startu obnoví polohu okna z minulého spuštění, pak ji průběžně zaznamenává pro aktuální monitor
+    fn track_window_geometry(&mut self, ctx: &egui::Context) {
+        let info = ctx.input(|i| i.viewport().clone());
+        let Some(monitor) = info.monitor_size.map(|size| format!("{:.0}x{:.0}", size.x, size.y)) else { return; };
+        if !self.geometry_restored {
+            self.geometry_restored = true;
+            if let Some((key, geometry)) = self.window_monitor.clone().and_then(|key| self.window_geometry.get(&key).map(|g| (key, *g))) {
+                info!("Obnovuji polohu okna pro monitor {}.", key);
+                geometry.apply(ctx);
+                self.geometry_check = Some((key, GEOMETRY_CHECK_FRAMES));
             }
+            return;
         }
-    });
-}
-
-
-// --- I/O, logovací a background funkce ---
-// (zde je zbytek kódu, který se nemění)
-// ...
-fn get_daily_log_filename() -> String { Local::now().format("log_%Y-%m-%d.csv").to_string() }
-fn draw_temperature_info(ui: &mut egui::Ui, history: &VecDeque<HistoryPoint>, config: &Config) {
-    let temp_min = history.iter().map(|p| p.temp).min_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal)).unwrap_or(0.0);
-    let temp_max = history.iter().map(|p| p.temp).max_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal)).unwrap_or(0.0);
-    ui.label(egui::RichText::new("Teplota").size(22.0).color(egui::Color32::GRAY));
-    if let Some(point) = history.back() {
-        let current_temp = point.temp;
-        let mut color = egui::Color32::from_rgb(255, 100, 100);
-        if current_temp > config.temp_warn_high { color = egui::Color32::GOLD; } else if current_temp < config.temp_warn_low { color = egui::Color32::from_rgb(120, 180, 255); }
-        ui.label(egui::RichText::new(format!("{:.1}°C", current_temp)).size(42.0).color(color));
-    } else { ui.label(egui::RichText::new("N/A").size(32.0)); }
-    ui.label(egui::RichText::new(format!("Min: {:.1}° / Max: {:.1}°", temp_min, temp_max)).size(20.0).color(egui::Color32::WHITE));
-}
-
-fn draw_humidity_info(ui: &mut egui::Ui, history: &VecDeque<HistoryPoint>) {
-    let hum_min = history.iter().map(|p| p.hum).min().unwrap_or(0);
-    let hum_max = history.iter().map(|p| p.hum).max().unwrap_or(0);
-    ui.label(egui::RichText::new("Vlhkost").size(22.0).color(egui::Color32::GRAY));
-    if let Some(point) = history.back() {
-        ui.label(egui::RichText::new(format!("{}%", point.hum)).size(42.0).color(egui::Color32::from_rgb(100, 100, 255)));
-    } else { ui.label(egui::RichText::new("N/A").size(32.0)); }
-    ui.label(egui::RichText::new(format!("Min: {}% / Max: {}%", hum_min, hum_max)).size(20.0).color(egui::Color32::WHITE));
-}
-
-fn draw_scan_metadata(ui: &mut egui::Ui, last_data: &Option<BleDataPoint>, status: &str) {
-    ui.horizontal(|ui| { ui.label(egui::RichText::new("Stav:").color(egui::Color32::GRAY)); ui.label(status); });
-    if let Some(data) = last_data {
-        ui.horizontal(|ui| { ui.label(egui::RichText::new("Aktualizace:").size(17.0).color(egui::Color32::GRAY)); ui.label(data.timestamp.format("%H:%M:%S").to_string()); });
-        ui.horizontal(|ui| { ui.label(egui::RichText::new("RSSI:").size(17.0).color(egui::Color32::GRAY)); if let Some(rssi) = data.rssi { ui.label(format!("{} dBm", rssi)); } else { ui.label("N/A"); }});
+        if let Some((expected, frames)) = self.geometry_check.take() {
+            if monitor == expected { return; }
+            if frames > 0 { self.geometry_check = Some((expected, frames - 1)); return; }
+            // Monitor z minula už není připojený – použije se poloha uložená pro ten současný
+            warn!("Monitor {} nenalezen, okno je na monitoru {}.", expected, monitor);
+            match self.window_geometry.get(&monitor) {
+                Some(geometry) => geometry.apply(ctx),
+                None => ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(egui::pos2(40.0, 40.0))),
+            }
+            return;
+        }
+        if self.mini_mode || info.minimized == Some(true) || info.fullscreen == Some(true) { return; }
+        let (Some(outer), Some(inner)) = (info.outer_rect, info.inner_rect) else { return; };
+        let maximized = info.maximized == Some(true);
+        let entry = self.window_geometry.entry(monitor.clone()).or_insert(WindowGeometry { position: [outer.min.x, outer.min.y], size: [inner.width(), inner.height()], maximized });
+        // U maximalizovaného okna zůstává uložená normální velikost, aby se po zrušení maximalizace vrátila
+        if !maximized { entry.position = [outer.min.x, outer.min.y]; entry.size = [inner.width(), inner.height()]; }
+        entry.maximized = maximized;
+        self.window_monitor = Some(monitor);
     }
-}
 
-fn draw_data_details(ui: &mut egui::Ui, last_data: &Option<BleDataPoint>, csv_ok: bool) {
-    if let Some(data) = last_data {
-        ui.horizontal(|ui| { ui.label(egui::RichText::new("ID Zařízení:").size(17.0).color(egui::Color32::GRAY)); ui.label(data.device_id.to_string()); });
-        ui.horizontal(|ui| { ui.label(egui::RichText::new("Raw data:").size(17.0).color(egui::Color32::GRAY)); ui.label(data.raw_data.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" ")); });
-        ui.horizontal(|ui| { ui.label(egui::RichText::new("Zápis CSV:").size(17.0).color(egui::Color32::GRAY)); if csv_ok { ui.label(egui::RichText::new("OK").color(egui::Color32::GREEN)); } else { ui.label(egui::RichText::new("Chyba").color(egui::Color32::RED)); } });
+    // Úpravy config.json zvenku (ručně nebo nástrojem pro správu instalací) se projeví bez restartu
+    fn reload_changed_config(&mut self) {
+        if self.config_polled.is_some_and(|at| at.elapsed() < CONFIG_POLL_INTERVAL) { return; }
+        self.config_polled = Some(Instant::now());
+        match self.config_watcher.poll() {
+            // Stav napájení soubor neobsahuje, se souborem se porovnává jen zbytek
+            Some(Ok(config)) if Config { on_battery: self.config.on_battery, ..config.clone() } != self.config => {
+                info!("Konfigurace se změnila mimo aplikaci, načítám ji znovu.");
+                self.apply_settings(config);
+                // Soubor už změnu obsahuje; zpětný zápis by jen přeformátoval cizí úpravy
+                self.config_changed = false;
+                self.toast(Severity::Info, tr("Konfigurace byla změněna v souboru a znovu načtena."));
+            },
+            Some(Err(e)) => {
+                warn!("Změněnou konfiguraci nelze načíst: {}", e);
+                self.toast(Severity::Warning, trf("Změněný config.json nelze načíst, platí dosavadní nastavení: {}", &[&e]));
+            },
+            _ => {}
+        }
     }
-}
 
-fn log_to_csv(temp: f32, hum: u8) -> Result<(), csv::Error> {
-    let filename = get_daily_log_filename(); let file_exists = Path::new(&filename).exists();
-    let file = fs::OpenOptions::new().append(true).create(true).open(filename)?;
-    let mut wtr = csv::WriterBuilder::new().delimiter(b';').from_writer(file);
-    if !file_exists { wtr.write_record(&["Datum", "Cas", "Teplota", "Vlhkost"])?; }
-    let now = Local::now(); let temp_str = format!("{:.1}", temp).replace('.', ",");
-    wtr.write_record(&[ now.format("%Y.%m.%d").to_string(), now.format("%H:%M:%S").to_string(), temp_str, hum.to_string() ])?;
-    wtr.flush()?; Ok(())
-}
-
-fn load_history_from_csv() -> VecDeque<HistoryPoint> {
-    let config = load_config();
-    info!("Načítám historii z CSV. Načíst vše: {}", config.load_all_history);
-    let capacity = if config.load_all_history { 0 } else { MAX_HISTORY_POINTS };
-    let mut history = VecDeque::with_capacity(capacity);
-    let filename = get_daily_log_filename();
-    if let Ok(file) = fs::File::open(&filename) {
-        let mut rdr = csv::ReaderBuilder::new().delimiter(b';').from_reader(file);
-        let all_records: Vec<_> = rdr.records().filter_map(Result::ok).collect();
-        info!("Nalezeno {} záznamů v souboru '{}'.", all_records.len(), filename);
-        let records_to_load: Box<dyn Iterator<Item = &csv::StringRecord>> = if config.load_all_history {
-            Box::new(all_records.iter())
-        } else {
-            let start_index = all_records.len().saturating_sub(MAX_HISTORY_POINTS);
-            Box::new(all_records.iter().skip(start_index))
-        };
-        for result in records_to_load {
-            if let (Some(date_str), Some(time_str), Some(temp_str), Some(hum_str)) = (result.get(0), result.get(1), result.get(2), result.get(3)) {
-                let datetime_str = format!("{} {}", date_str, time_str);
-                if let Ok(naive_dt) = NaiveDateTime::parse_from_str(&datetime_str, "%Y.%m.%d %H:%M:%S") {
-                    if let (Ok(temp), Ok(hum)) = (temp_str.replace(',', ".").parse(), hum_str.parse()) {
-                        history.push_back(HistoryPoint { timestamp: naive_dt.and_local_timezone(Local).unwrap(), temp, hum });
-                    }
-                }
+    fn apply_settings(&mut self, mut config: Config) {
+        // Stav napájení patří monitoru, nová konfigurace (ze souboru, z profilu) ho nesmí přepsat
+        config.on_battery = self.shared_config.borrow().on_battery;
+        let old_config = std::mem::replace(&mut self.config, config);
+        if self.config == old_config { return; }
+        info!("Detekována změna v nastavení.");
+        self.config_changed = true;
+        if self.config.language != old_config.language {
+            set_language(self.config.language);
+            // Menu ikony se sestaví znovu v novém jazyce
+            #[cfg(windows)]
+            { self.tray = None; }
+        }
+        if self.config.temp_unit != old_config.temp_unit { set_temp_unit(self.config.temp_unit); }
+        // Menu ikony nabízí profily podle konfigurace při sestavení, po změně seznamu nebo zapnutého profilu se sestaví znovu
+        #[cfg(windows)]
+        if self.config.active_profile != old_config.active_profile || self.config.profiles.iter().map(|p| &p.name).ne(old_config.profiles.iter().map(|p| &p.name)) { self.tray = None; }
+        if self.config.json_log_file != old_config.json_log_file {
+            if let Err(e) = console::set_json_log(&self.config.json_log_file) {
+                error!("Soubor protokolu JSON '{}' nelze otevřít: {}", self.config.json_log_file, e);
+                self.toast(Severity::Error, trf("Protokol JSON nelze zapisovat: {}", &[&e]));
+            }
+        }
+        if self.config.load_all_history != old_config.load_all_history || (self.config.load_all_history && (self.config.load_older_logs != old_config.load_older_logs || self.config.history_memory_days != old_config.history_memory_days)) { self.apply_history_size(); }
+        #[cfg(any(windows, target_os = "linux"))]
+        if self.config.autostart != old_config.autostart {
+            if let Err(e) = autostart::set_enabled(self.config.autostart) {
+                error!("Nelze změnit automatické spuštění: {}", e);
+                self.toast(Severity::Error, trf("Automatické spuštění se nepodařilo nastavit: {}", &[&e]));
+                self.config.autostart = old_config.autostart;
             }
         }
-        info!("Načteno {} bodů do historie grafu.", history.len());
-    } else { warn!("Soubor s historií '{}' nenalezen.", filename); }
-    history
+        self.shared_config.send_replace(self.config.clone());
+        debug!("Sdílená konfigurace byla aktualizována.");
+    }
 }
 
-fn load_config() -> Config {
-    info!("Načítám konfiguraci z '{}'.", CONFIG_FILE);
-    fs::read_to_string(CONFIG_FILE).ok().and_then(|c| serde_json::from_str::<Config>(&c).ok()).unwrap_or_default()
-}
-fn save_config(config: &Config) {
-    if let Ok(content) = serde_json::to_string_pretty(config) { let _ = fs::write(CONFIG_FILE, content); }
+// --- Pomocné funkce ---
+
+// Otevře složku ve správci souborů systému
+fn open_folder(dir: &Path) -> std::io::Result<()> {
+    let program = if cfg!(windows) { "explorer" } else if cfg!(target_os = "macos") { "open" } else { "xdg-open" };
+    std::process::Command::new(program).arg(dir).spawn().map(|_| ())
 }
 
-fn background_data_processor(rx: mpsc::Receiver<AppMessage>, tx: mpsc::Sender<AppMessage>, shared_config: Arc<Mutex<Config>>) {
-    info!("Spouštím background procesor pro data.");
-    let mut last_save_time: Option<Instant> = None;
-    for received in rx {
-        match received {
-            AppMessage::NewData(data_point) => {
-                let config = shared_config.lock().unwrap().clone();
-                let now = Instant::now();
-                let should_save = last_save_time.map_or(true, |last| {
-                    now.duration_since(last).as_secs() >= config.duplicate_threshold_secs
-                });
-                if should_save {
-                    info!("Zapisuji data do CSV: teplota={}, vlhkost={}", data_point.temp, data_point.hum);
-                    let write_ok = log_to_csv(data_point.temp, data_point.hum).is_ok();
-                    if !write_ok { error!("Nepodařilo se zapsat do CSV souboru!"); }
-                    let _ = tx.send(AppMessage::CsvWriteStatus(write_ok));
-                    last_save_time = Some(now);
-                    if tx.send(AppMessage::NewData(data_point)).is_err() { error!("GUI kanál je uzavřen, ukončuji background procesor."); break; }
-                } else {
-                    debug!("Přeskakuji zápis i zobrazení v grafu (duplikát).");
-                }
-            },
-            AppMessage::StatusUpdate(status) => {
-                if tx.send(AppMessage::StatusUpdate(status)).is_err() { error!("GUI kanál je uzavřen, ukončuji background procesor."); break; }
-            },
-            _ => {}
-        }
+// Vyjme z parametrů --config <soubor> a --portable; vrací absolutní cestu ke konfiguraci, pokud je jiná než výchozí
+fn take_config_location(args: &mut Vec<String>) -> Result<Option<PathBuf>, String> {
+    let mut location = None;
+    if let Some(i) = args.iter().position(|arg| arg == PORTABLE_ARG) {
+        args.remove(i);
+        let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+        location = exe.parent().map(|dir| dir.join(CONFIG_FILE));
+    }
+    if let Some(i) = args.iter().position(|arg| arg == CONFIG_ARG) {
+        if i + 1 >= args.len() { return Err(tr("za --config chybí cesta ke konfiguraci").to_string()); }
+        let path = args.remove(i + 1);
+        args.remove(i);
+        location = Some(std::path::absolute(path).map_err(|e| e.to_string())?);
     }
-    info!("Background procesor ukončen.");
+    Ok(location)
 }
 
-
 fn main() -> Result<(), eframe::Error> {
-    #[cfg(debug_assertions)]
-    env_logger::Builder::new()
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let location = take_config_location(&mut args);
+    let headless = args.iter().any(|arg| arg == headless::HEADLESS_ARG);
+    let command = cli::is_command(args.first());
+    // Bez okna chybí panel konzole, proto jdou varování a chyby na stderr i v release buildu
+    let stderr_logger = (cfg!(debug_assertions) || headless || command).then(|| env_logger::Builder::new()
         .format(|buf, record| { writeln!(buf, "[{}] [{}] - {}", chrono::Local::now().format("%Y-%m-%d %H:%M:%S"), record.level(), record.args()) })
-        .filter(None, log::LevelFilter::Info)
-        .init();
+        .filter(None, if cfg!(debug_assertions) { log::LevelFilter::Info } else { log::LevelFilter::Warn })
+        .build());
+    console::init(stderr_logger);
     info!("Logger inicializován, spouštím aplikaci...");
-    let viewport = egui::ViewportBuilder::default().with_inner_size([850.0, 450.0]).with_decorations(true).with_transparent(true).with_app_id("temp_monitor_sobes");
-    let options = eframe::NativeOptions { viewport, ..Default::default() };
-    eframe::run_native("Teploměr", options, Box::new(|cc| Box::new(TempMonitorApp::new(cc))))
-}
-
-async fn bluetooth_scanner(tx: mpsc::Sender<AppMessage>, shared_config: Arc<Mutex<Config>>) {
-    info!("Spouštím hlavní smyčku Bluetooth scanneru.");
-    loop {
-        let current_config = { if let Ok(config) = shared_config.lock() { config.clone() } else { Config::default() } };
-        debug!("Nová iterace scanneru, MAC: {}", current_config.target_mac);
-        let manager = match Manager::new().await {
-            Ok(m) => m,
-            Err(e) => {
-                error!("Chyba při inicializaci BT manažeru: {}", e);
-                let _ = tx.send(AppMessage::StatusUpdate("Chyba: BT adaptér nenalezen".into()));
-                thread::sleep(Duration::from_secs(if current_config.continuous_mode { 1 } else { current_config.scan_pause_secs }));
-                continue;
-            }
-        };
-        if let Some(central) = manager.adapters().await.unwrap_or_default().into_iter().next() {
-            let status_msg = if current_config.continuous_mode { "Skenuji (kontinuální režim)..." } else { "Skenuji..." };
-            info!("Zahajuji skenování na adaptéru...");
-            let _ = tx.send(AppMessage::StatusUpdate(status_msg.into()));
-            if central.start_scan(ScanFilter::default()).await.is_ok() {
-                let scan_duration = if current_config.continuous_mode { 60 } else { current_config.scan_timeout_secs };
-                let _ = tokio::time::timeout(Duration::from_secs(scan_duration), async {
-                    let mut events = central.events().await.unwrap();
-                    while let Some(event) = events.next().await {
-                        if let CentralEvent::DeviceDiscovered(id) | CentralEvent::DeviceUpdated(id) = event {
-                            if let Ok(p) = central.peripheral(&id).await {
-                                if let Ok(Some(props)) = p.properties().await {
-                                    if props.address.to_string().eq_ignore_ascii_case(&current_config.target_mac) {
-                                        info!("Cílové zařízení nalezeno: {}", props.address);
-                                        if let Some((company_id, data)) = props.manufacturer_data.iter().next() {
-                                            if data.len() >= 2 {
-                                                let temp = i16::from_le_bytes([(*company_id >> 8) as u8, data[0]]) as f32 / 10.0;
-                                                let hum = data[1];
-                                                let data_point = BleDataPoint { timestamp: Local::now(), temp, hum, device_id: id.to_string(), rssi: props.rssi, raw_data: data.clone() };
-                                                info!("Úspěšně parsována data, posílám do procesoru: T={:.1}C, H={}%", temp, hum);
-                                                if tx.send(AppMessage::NewData(data_point)).is_err() { break; }
-                                                if !current_config.continuous_mode { return; }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }).await;
-                info!("Skenování ukončeno (timeout).");
-                let _ = central.stop_scan().await;
-            }
+    let location = location.unwrap_or_else(|e| { eprintln!("{} {}", tr("Chyba:"), e); std::process::exit(2) });
+    #[cfg(any(windows, target_os = "linux"))]
+    if autostart::launched_by_autostart() && location.is_none() { autostart::enter_exe_dir(); }
+    if let Some(path) = location {
+        // Logy a poznámky mají relativní cesty, s vlastní konfigurací se tedy ukládají do její složky
+        if let Some(dir) = path.parent() {
+            if let Err(e) = std::env::set_current_dir(dir) { error!("Nelze přejít do složky s konfigurací {}: {}", dir.display(), e); }
         }
-        let _ = tx.send(AppMessage::StatusUpdate("Čekám...".into()));
-        let pause_duration = if current_config.continuous_mode { 1 } else { current_config.scan_pause_secs };
-        debug!("Pauza na {} sekund.", pause_duration);
-        thread::sleep(Duration::from_secs(pause_duration));
+        info!("Používám konfiguraci '{}'.", path.display());
+        set_custom_config_path(path);
+    }
+    crash::install_hook();
+    let config = load_config();
+    set_language(config.language);
+    set_temp_unit(config.temp_unit);
+    if command { std::process::exit(cli::run(&args, &config)); }
+    if let Err(e) = console::set_json_log(&config.json_log_file) { error!("Soubor protokolu JSON '{}' nelze otevřít: {}", config.json_log_file, e); }
+    // Příkazy jen čtou logy; okno i režim bez okna do nich zapisují, smí tedy běžet jen jednou nad stejnou složkou
+    let instance = match instance::claim() {
+        Ok(Some(listener)) => Some(listener),
+        Ok(None) => {
+            info!("Aplikace už běží, zobrazuji její okno a končím.");
+            if headless { eprintln!("{}", tr("Aplikace nad touto složkou s logy už běží.")); std::process::exit(1); }
+            return Ok(());
+        },
+        Err(e) => { warn!("Nelze ověřit, zda aplikace už neběží: {}", e); None },
+    };
+    if headless {
+        headless::run(config, instance);
+        return Ok(());
     }
+    let mut viewport = egui::ViewportBuilder::default().with_inner_size([850.0, 450.0]).with_decorations(true).with_transparent(true).with_app_id("temp_monitor_sobes");
+    if config.always_on_top { viewport = viewport.with_always_on_top(); }
+    if std::env::args().any(|arg| arg == KIOSK_ARG) { viewport = viewport.with_fullscreen(true); }
+    // Polohu a velikost okna (i maximalizaci) ukládá aplikace sama, viz track_window_geometry
+    let options = eframe::NativeOptions { viewport, persist_window: false, ..Default::default() };
+    eframe::run_native(tr("Teploměr"), options, Box::new(move |cc| Box::new(TempMonitorApp::new(cc, instance))))
 }
@@ -0,0 +1,67 @@
+// --- Skriptovaný Bluetooth adaptér (feature `mock-ble`) ---
+// Místo skutečného adaptéru přehraje pakety připravené přes set_script, takže parsování, filtr duplikátů
+// i upozornění jdou otestovat (tests/mock_ble.rs) na strojích bez Bluetooth. Skener ani procesor o záměně nevědí.
+use crate::ble::{Advertisement, BleCentral, DeviceInfo};
+use crate::error::{Error, Result};
+use futures::stream::{BoxStream, StreamExt};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+// Paket se odešle `delay` po předchozím
+pub struct ScriptedAdvertisement { pub delay: Duration, pub advertisement: Advertisement }
+
+static SCRIPT: Mutex<VecDeque<ScriptedAdvertisement>> = Mutex::new(VecDeque::new());
+static ADAPTER_AVAILABLE: AtomicBool = AtomicBool::new(true);
+static ADAPTER_HANGS: AtomicBool = AtomicBool::new(false);
+
+// Nahradí dosud nepřehrané pakety; každý paket se přehraje jen jednou, i když skener začne nový sken
+pub fn set_script(script: Vec<ScriptedAdvertisement>) { *SCRIPT.lock().unwrap() = script.into(); }
+
+pub fn remaining() -> usize { SCRIPT.lock().unwrap().len() }
+
+// Bez adaptéru selže otevření stejně jako na počítači bez Bluetooth
+pub fn set_adapter_available(available: bool) { ADAPTER_AVAILABLE.store(available, Ordering::SeqCst); }
+
+// Zaseknutý Bluetooth stack: otevření adaptéru se nikdy nedokončí (pro hlídač skeneru)
+pub fn set_adapter_hangs(hangs: bool) { ADAPTER_HANGS.store(hangs, Ordering::SeqCst); }
+
+// Paket TP357 s daným měřením (kódování viz parsing::decode_manufacturer_data); baterie 0 = vybitá, 1 = polovina, 2 = plná
+pub fn tp357_advertisement(address: &str, temp: f32, hum: u8, battery: u8) -> Advertisement {
+    let [low, high] = ((temp * 10.0).round() as i16).to_le_bytes();
+    let company_id = u16::from(low) << 8 | 0xC2;
+    let address = address.to_uppercase();
+    let local_name = format!("TP357 ({})", address.replace([':', '-'], "").get(..4).unwrap_or_default());
+    Advertisement { device_id: format!("mock/{}", address), address, local_name: Some(local_name), rssi: Some(-60), manufacturer_data: HashMap::from([(company_id, vec![high, hum, battery])]) }
+}
+
+pub struct MockCentral;
+
+impl BleCentral for MockCentral {
+    async fn open() -> Result<Self> {
+        if ADAPTER_HANGS.load(Ordering::SeqCst) { std::future::pending::<()>().await; }
+        if ADAPTER_AVAILABLE.load(Ordering::SeqCst) { Ok(MockCentral) } else { Err(Error::NoAdapter) }
+    }
+
+    async fn start_scan(&self) -> Result<()> { Ok(()) }
+
+    // Po vyčerpání skriptu už nic nepřijde, jako u adaptéru bez senzorů v dosahu. Pakety cizích zařízení se posílají
+    // všechny, takže testy ověřují i filtr ve skeneru.
+    async fn advertisements(&self, _watched: &[String]) -> Result<BoxStream<'static, Advertisement>> {
+        Ok(futures::stream::unfold((), |()| async {
+            let delay = SCRIPT.lock().unwrap().front().map(|s| s.delay);
+            let Some(delay) = delay else { return std::future::pending().await; };
+            tokio::time::sleep(delay).await;
+            let next = SCRIPT.lock().unwrap().pop_front();
+            next.map(|s| (s.advertisement, ()))
+        }).boxed())
+    }
+
+    async fn stop_scan(&self) {}
+
+    // Senzor ze skriptu se připojit nedá; údaje jako u TP357 s neznámou revizí firmwaru
+    async fn device_info(&self, address: &str) -> Result<DeviceInfo> {
+        Ok(DeviceInfo { manufacturer: Some("ThermoPro".into()), model: Some(format!("TP357 (mock {})", address)), ..DeviceInfo::default() })
+    }
+}
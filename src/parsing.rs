@@ -0,0 +1,96 @@
+// --- Dekódování paketů TP357 ---
+// Teploměr nepotřebuje připojení: měření vysílá v reklamních paketech jako "manufacturer data".
+// Revize firmwaru se liší rozložením dat v paketu; LayoutDetector pro každý senzor najde rozložení, které dává
+// věrohodné hodnoty, drží se ho a neznámá rozložení jen ohlásí do protokolu.
+use log::{info, warn};
+use std::collections::{HashMap, HashSet};
+
+// Jedno měření přečtené z paketu (teplota ve °C)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tp357Reading { pub temp: f32, pub hum: u8, pub battery: Option<u8> }
+
+// Spodní bajt ID výrobce, kterým se hlásí TP357 s původním rozložením
+const SPLIT_TEMP_MARKER: u8 = 0xC2;
+// Rozsah čidla se širokou rezervou; hodnoty mimo něj znamenají špatně zvolené rozložení.
+// Meze uživatele (temp_min/temp_max) kontroluje až procesor, který odmítnutí i ohlásí.
+const PLAUSIBLE_TEMP: std::ops::RangeInclusive<f32> = -60.0..=125.0;
+// Po tolika nevěrohodných paketech za sebou se rozložení senzoru hledá znovu
+const REDETECT_AFTER: u32 = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PayloadLayout {
+    // Spodní bajt teploty (desetiny °C se znaménkem) je horní bajt ID výrobce, horní bajt je data[0];
+    // data[1] je vlhkost v %, data[2] baterie
+    SplitTemp,
+    // Celá teplota v datech (data[0..2], little-endian), data[2] vlhkost, data[3] baterie; ID výrobce je pevné
+    PayloadTemp,
+}
+
+impl PayloadLayout {
+    pub const ALL: [PayloadLayout; 2] = [PayloadLayout::SplitTemp, PayloadLayout::PayloadTemp];
+
+    pub fn decode(self, company_id: u16, data: &[u8]) -> Option<Tp357Reading> {
+        match self {
+            PayloadLayout::SplitTemp => {
+                if data.len() < 2 { return None; }
+                let temp = i16::from_le_bytes([(company_id >> 8) as u8, data[0]]) as f32 / 10.0;
+                Some(Tp357Reading { temp, hum: data[1], battery: battery_level(data.get(2)) })
+            },
+            PayloadLayout::PayloadTemp => {
+                if data.len() < 3 { return None; }
+                let temp = i16::from_le_bytes([data[0], data[1]]) as f32 / 10.0;
+                Some(Tp357Reading { temp, hum: data[2], battery: battery_level(data.get(3)) })
+            },
+        }
+    }
+
+    // Pořadí zkoušení: paket se značkou původního rozložení nejdřív jako původní
+    fn candidates(company_id: u16) -> [PayloadLayout; 2] {
+        if company_id as u8 == SPLIT_TEMP_MARKER { Self::ALL } else { [PayloadLayout::PayloadTemp, PayloadLayout::SplitTemp] }
+    }
+}
+
+// Původní pevné rozložení (TP357 s ID výrobce končícím 0xC2)
+pub fn decode_manufacturer_data(company_id: u16, data: &[u8]) -> Option<Tp357Reading> { PayloadLayout::SplitTemp.decode(company_id, data) }
+
+// TP357 hlásí stav baterie jen ve třech stupních (bajt za vlhkostí: 0 = vybitá, 1 = polovina, 2 = plná)
+fn battery_level(byte: Option<&u8>) -> Option<u8> {
+    match byte? { 0 => Some(1), 1 => Some(50), 2 => Some(100), _ => None }
+}
+
+fn is_plausible(reading: &Tp357Reading) -> bool { PLAUSIBLE_TEMP.contains(&reading.temp) && reading.hum <= 100 }
+
+// Rozložení zvolené pro senzor a počet nevěrohodných paketů za sebou
+struct DeviceLayout { layout: PayloadLayout, misses: u32 }
+
+// Rozložení dat podle senzoru (adresy). Zvolené rozložení platí, dokud dává věrohodné hodnoty; jednotlivý poškozený
+// paket se zahodí, až opakované selhání (např. po aktualizaci firmwaru) spustí nové hledání.
+#[derive(Default)]
+pub struct LayoutDetector { devices: HashMap<String, DeviceLayout>, reported: HashSet<(String, u16, usize)> }
+
+impl LayoutDetector {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn layout(&self, address: &str) -> Option<PayloadLayout> { self.devices.get(address).map(|d| d.layout) }
+
+    pub fn decode(&mut self, address: &str, company_id: u16, data: &[u8]) -> Option<Tp357Reading> {
+        if let Some(device) = self.devices.get_mut(address) {
+            if let Some(reading) = device.layout.decode(company_id, data).filter(is_plausible) { device.misses = 0; return Some(reading); }
+            device.misses += 1;
+            if device.misses < REDETECT_AFTER { return None; }
+            info!("Senzor {} posílá {} nevěrohodných paketů za sebou, hledám nové rozložení dat.", address, device.misses);
+            self.devices.remove(address);
+        }
+        for layout in PayloadLayout::candidates(company_id) {
+            let Some(reading) = layout.decode(company_id, data).filter(is_plausible) else { continue; };
+            info!("Senzor {} používá rozložení dat {:?}.", address, layout);
+            self.devices.insert(address.to_string(), DeviceLayout { layout, misses: 0 });
+            return Some(reading);
+        }
+        // Neznámý tvar se ohlásí jednou (pro hlášení chyby stačí jeden vzorek), další pakety stejného tvaru se jen zahodí
+        if self.reported.insert((address.to_string(), company_id, data.len())) {
+            warn!("Neznámé rozložení dat od {}: ID výrobce {:04X}, data {}. Paket se nepoužije.", address, company_id, data.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" "));
+        }
+        None
+    }
+}
@@ -0,0 +1,77 @@
+// --- Napájení ---
+// Zda počítač běží z baterie (notebook odpojený od sítě); podle toho aplikace zpomalí skenování a obnovu okna.
+// Zjištění stavu není úplně zadarmo (na macOS nový proces), proto ho zjišťuje jen monitor na pozadí a ostatním
+// (okno, skener, procesor) ho předává v poli Config::on_battery sdílené konfigurace.
+use crate::config::Config;
+use log::info;
+use std::time::Duration;
+use tokio::sync::watch;
+
+// Jak často monitor stav napájení zjišťuje
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+// Počítač bez baterie, nebo se stavem, který nejde zjistit, se bere jako napájený ze sítě
+pub fn on_battery() -> bool { platform::on_battery() }
+
+// Běží do ukončení runtime; změnu napájení zapíše do protokolu a rozešle příjemcům sdílené konfigurace
+pub async fn monitor(config: watch::Sender<Config>) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    loop {
+        interval.tick().await;
+        let Ok(on_battery) = tokio::task::spawn_blocking(on_battery).await else { continue; };
+        config.send_if_modified(|config| {
+            if config.on_battery == on_battery { return false; }
+            info!("{}", if on_battery { "Počítač běží z baterie." } else { "Počítač je napájen ze sítě." });
+            config.on_battery = on_battery;
+            true
+        });
+    }
+}
+
+// Zdroje napájení v /sys/class/power_supply: baterie se vybíjí a žádný jiný zdroj (síť, USB) není připojený.
+// Baterie bezdrátové myši, klávesnice či sluchátek (scope=Device) nenapájí počítač, proto se přeskočí.
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::fs;
+
+    pub fn on_battery() -> bool {
+        let Ok(entries) = fs::read_dir("/sys/class/power_supply") else { return false; };
+        let (mut discharging, mut external) = (false, false);
+        for entry in entries.flatten() {
+            let read = |name: &str| fs::read_to_string(entry.path().join(name)).map(|s| s.trim().to_string()).unwrap_or_default();
+            if read("scope") == "Device" { continue; }
+            if read("type") == "Battery" {
+                discharging |= read("status") == "Discharging";
+            } else {
+                external |= read("online") == "1";
+            }
+        }
+        discharging && !external
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use windows_sys::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+    // ACLineStatus: 0 = odpojeno od sítě, 1 = připojeno, 255 = neznámé
+    pub fn on_battery() -> bool {
+        let mut status: SYSTEM_POWER_STATUS = unsafe { std::mem::zeroed() };
+        unsafe { GetSystemPowerStatus(&mut status) != 0 && status.ACLineStatus == 0 }
+    }
+}
+
+// První řádek `pmset -g batt`: "Now drawing from 'Battery Power'", resp. 'AC Power'
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::process::Command;
+
+    pub fn on_battery() -> bool {
+        Command::new("pmset").args(["-g", "batt"]).output().is_ok_and(|output| String::from_utf8_lossy(&output.stdout).contains("'Battery Power'"))
+    }
+}
+
+#[cfg(not(any(target_os = "linux", windows, target_os = "macos")))]
+mod platform {
+    pub fn on_battery() -> bool { false }
+}
@@ -0,0 +1,170 @@
+// --- Zpracování měření ---
+// Vlákno mezi skenerem a GUI: filtruje nevěrohodné hodnoty a duplikáty a přijatá měření zapisuje do denních logů.
+use crate::config::Config;
+use crate::error::Error;
+use crate::i18n::{tr, trf};
+use crate::sinks::{Alert, SinkErrors, SinkRegistry};
+use crate::units::format_temp;
+use chrono::{DateTime, Local};
+use log::{debug, error, info, warn};
+use std::collections::HashMap;
+use std::sync::{mpsc, Arc, Mutex};
+use tokio::sync::{mpsc as async_mpsc, oneshot, watch};
+use std::time::Instant;
+
+// Po tolika odmítnutých skocích v řadě se nová hodnota přijme jako nová úroveň (např. senzor přenesen do jiné místnosti)
+pub const SPIKE_RESET_COUNT: u32 = 3;
+// Kontrola skoku se uplatní jen vůči měření mladšímu než tato doba
+pub const SPIKE_JUMP_WINDOW_SECS: u64 = 15 * 60;
+
+#[derive(Clone, Debug)]
+pub struct BleDataPoint { pub timestamp: DateTime<Local>, pub temp: f32, pub hum: u8, pub device_id: String, pub mac: String, pub rssi: Option<i16>, pub battery: Option<u8>, pub raw_data: Vec<u8>, }
+// Kanál k oknu (nebo k výpisu v režimu bez okna). Okno se po každé zprávě hned probudí k překreslení,
+// takže nemusí samo obnovovat každou sekundu, aby nová měření nezůstala ležet ve frontě.
+#[derive(Clone)]
+pub struct UiSender { tx: async_mpsc::UnboundedSender<AppMessage>, repaint: Option<eframe::egui::Context> }
+
+impl UiSender {
+    pub fn with_repaint(tx: async_mpsc::UnboundedSender<AppMessage>, ctx: eframe::egui::Context) -> Self { Self { tx, repaint: Some(ctx) } }
+
+    pub fn send(&self, message: AppMessage) -> Result<(), async_mpsc::error::SendError<AppMessage>> {
+        self.tx.send(message)?;
+        if let Some(ctx) = &self.repaint { ctx.request_repaint(); }
+        Ok(())
+    }
+}
+
+impl From<async_mpsc::UnboundedSender<AppMessage>> for UiSender {
+    fn from(tx: async_mpsc::UnboundedSender<AppMessage>) -> Self { Self { tx, repaint: None } }
+}
+
+// CurrentReadings a TestAlert posílá procesoru místní řízení (instance.rs); TestAlert procesor po zápisu do výstupů předá i oknu
+pub enum AppMessage { NewData(BleDataPoint), StatusUpdate(String), CsvWriteStatus(bool), ReadingRejected(String), Error(Error), CurrentReadings(oneshot::Sender<Vec<BleDataPoint>>), TestAlert, }
+
+// Rovnovážná vlhkost dřeva (%) podle Hailwood–Horrobinova modelu (Wood Handbook, metrické koeficienty)
+pub fn equilibrium_moisture_content(temp_c: f64, rh_percent: f64) -> f64 {
+    let t = temp_c;
+    let h = (rh_percent / 100.0).clamp(0.0, 0.99);
+    let w = 349.0 + 1.29 * t + 0.0135 * t * t;
+    let k = 0.805 + 0.000736 * t - 0.000_002_73 * t * t;
+    let k1 = 6.27 - 0.00938 * t - 0.000303 * t * t;
+    let k2 = 1.91 + 0.0407 * t - 0.000293 * t * t;
+    let kh = k * h;
+    1800.0 / w * (kh / (1.0 - kh) + (k1 * kh + 2.0 * k1 * k2 * kh * kh) / (1.0 + k1 * kh + k1 * k2 * kh * kh))
+}
+
+// Vrátí důvod, proč je měření nevěrohodné; `true` u skoku (relativní kontrola), `false` u hodnoty mimo platný rozsah
+pub fn implausibility_reason(data: &BleDataPoint, last_accepted: Option<(Instant, f32, u8)>, config: &Config) -> Option<(String, bool)> {
+    if !(config.temp_valid_min..=config.temp_valid_max).contains(&data.temp) {
+        return Some((trf("teplota {} mimo platný rozsah", &[&format_temp(data.temp as f64, 1)]), false));
+    }
+    if data.hum > 100 { return Some((trf("vlhkost {}% mimo platný rozsah", &[&data.hum]), false)); }
+    if let Some((at, temp, hum)) = last_accepted {
+        if at.elapsed().as_secs() <= SPIKE_JUMP_WINDOW_SECS {
+            if (data.temp - temp).abs() > config.max_temp_jump { return Some((trf("skok teploty {} → {}", &[&format_temp(temp as f64, 1), &format_temp(data.temp as f64, 1)]), true)); }
+            if data.hum.abs_diff(hum) > config.max_hum_jump { return Some((trf("skok vlhkosti {}% → {}%", &[&hum, &data.hum]), true)); }
+        }
+    }
+    None
+}
+
+// Stav filtru duplikátů a skoků pro jeden senzor
+#[derive(Default)]
+pub struct DeviceFilterState { pub last_save_time: Option<Instant>, pub last_accepted: Option<(Instant, f32, u8)>, pub consecutive_jumps: u32, pub alert: Option<&'static str>, pub last_reading: Option<BleDataPoint> }
+
+// Měření za mezemi upozornění; vrací druh (upozorní se jen na jeho změnu, ne na každé měření) a text pro výstupy
+pub fn threshold_alert(data: &BleDataPoint, config: &Config) -> Option<(&'static str, String)> {
+    let temp = || format!("{} {}", tr("Teplota"), format_temp(data.temp as f64, 1));
+    let hum = || format!("{} {} %", tr("Vlhkost"), data.hum);
+    let (kind, value, limit) = if data.temp > config.temp_warn_high { ("temp_high", temp(), tr("nad horní mezí")) }
+        else if data.temp < config.temp_warn_low { ("temp_low", temp(), tr("pod spodní mezí")) }
+        else if data.hum > config.hum_warn_high { ("hum_high", hum(), tr("nad horní mezí")) }
+        else if data.hum < config.hum_warn_low { ("hum_low", hum(), tr("pod spodní mezí")) }
+        else { return None; };
+    Some((kind, format!("{}: {} {}", config.device_name(&data.mac), value, limit)))
+}
+
+// Chyby výstupů zapíše do logu a pošle oknu; vrací, zda všechny výstupy zapsaly bez chyby
+fn report_sink_errors(tx: &UiSender, errors: SinkErrors) -> bool {
+    let ok = errors.is_empty();
+    for (sink, e) in errors {
+        error!("Výstup '{}' selhal: {}", sink, e);
+        let _ = tx.send(AppMessage::Error(e));
+    }
+    ok
+}
+
+// Běží ve vlastním vlákně (blokující příjem z kanálu Tokia); `tray_feed` dostává kopii měření hlavního senzoru
+// pro ikonu v oznamovací oblasti (i když je okno schované)
+pub fn background_data_processor(mut rx: async_mpsc::UnboundedReceiver<AppMessage>, tx: UiSender, shared_config: watch::Receiver<Config>, tray_feed: Arc<Mutex<Option<mpsc::Sender<BleDataPoint>>>>) {
+    info!("Spouštím background procesor pro data.");
+    let mut devices: HashMap<String, DeviceFilterState> = HashMap::new();
+    let mut sinks = SinkRegistry::new(&shared_config.borrow().outputs);
+    while let Some(received) = rx.blocking_recv() {
+        match received {
+            AppMessage::NewData(data_point) => {
+                let config = shared_config.borrow().clone();
+                report_sink_errors(&tx, sinks.update(&config));
+                let state = devices.entry(data_point.mac.to_uppercase()).or_default();
+                if config.spike_filter_enabled {
+                    if let Some((reason, is_jump)) = implausibility_reason(&data_point, state.last_accepted, &config) {
+                        if is_jump { state.consecutive_jumps += 1; }
+                        if !is_jump || state.consecutive_jumps < SPIKE_RESET_COUNT {
+                            warn!("Odmítnuto podezřelé měření ze senzoru {} ({}), raw data: {:02X?}", data_point.mac, reason, data_point.raw_data);
+                            let reason = if config.is_primary(&data_point.mac) { reason } else { format!("{}: {}", config.device_name(&data_point.mac), reason) };
+                            if tx.send(AppMessage::ReadingRejected(reason)).is_err() { error!("GUI kanál je uzavřen, ukončuji background procesor."); break; }
+                            continue;
+                        }
+                        info!("Hodnota se opakovaně liší od předchozí úrovně, přijímám ji jako novou úroveň.");
+                    }
+                }
+                state.consecutive_jumps = 0;
+                state.last_accepted = Some((Instant::now(), data_point.temp, data_point.hum));
+                state.last_reading = Some(data_point.clone());
+                let alert = threshold_alert(&data_point, &config);
+                if alert.as_ref().map(|(kind, _)| *kind) != state.alert {
+                    state.alert = alert.as_ref().map(|(kind, _)| *kind);
+                    if let Some((_, message)) = alert {
+                        info!("Upozornění: {}", message);
+                        report_sink_errors(&tx, sinks.on_alert(&Alert { timestamp: data_point.timestamp, mac: data_point.mac.clone(), message }, &config));
+                    }
+                }
+                let now = Instant::now();
+                let should_save = state.last_save_time.is_none_or(|last| {
+                    now.duration_since(last).as_secs() >= config.duplicate_threshold_secs
+                });
+                if should_save {
+                    info!("Zapisuji data do výstupů ({}): teplota={}, vlhkost={}", data_point.mac, data_point.temp, data_point.hum);
+                    let write_ok = report_sink_errors(&tx, sinks.on_reading(&data_point, &config));
+                    state.last_save_time = Some(now);
+                    // Stav zápisu a velikost logu v GUI patří jen k hlavnímu senzoru
+                    if config.is_primary(&data_point.mac) {
+                        let _ = tx.send(AppMessage::CsvWriteStatus(write_ok));
+                        if let Some(feed) = tray_feed.lock().unwrap().as_ref() { let _ = feed.send(data_point.clone()); }
+                    }
+                    if tx.send(AppMessage::NewData(data_point)).is_err() { error!("GUI kanál je uzavřen, ukončuji background procesor."); break; }
+                } else {
+                    debug!("Přeskakuji zápis i zobrazení v grafu (duplikát).");
+                }
+            },
+            // Zprávu nelze odeslat už ve stráži větve (přesun hodnoty), proto výsledek odeslání zvlášť
+            message @ (AppMessage::StatusUpdate(_) | AppMessage::Error(_)) => {
+                let sent = tx.send(message).is_ok();
+                if !sent { error!("GUI kanál je uzavřen, ukončuji background procesor."); break; }
+            },
+            AppMessage::CurrentReadings(reply) => { let _ = reply.send(devices.values().filter_map(|state| state.last_reading.clone()).collect()); },
+            AppMessage::TestAlert => {
+                let config = shared_config.borrow().clone();
+                report_sink_errors(&tx, sinks.update(&config));
+                info!("Zkušební upozornění.");
+                report_sink_errors(&tx, sinks.on_alert(&Alert { timestamp: Local::now(), mac: config.target_mac.to_uppercase(), message: tr("Zkušební upozornění").to_string() }, &config));
+                if tx.send(AppMessage::TestAlert).is_err() { error!("GUI kanál je uzavřen, ukončuji background procesor."); break; }
+            },
+            _ => {}
+        }
+        if rx.is_empty() { report_sink_errors(&tx, sinks.flush()); }
+    }
+    let config = shared_config.borrow().clone();
+    report_sink_errors(&tx, sinks.close(&config));
+    info!("Background procesor ukončen.");
+}
@@ -0,0 +1,126 @@
+// --- Report ---
+// Denní souhrn do CSV a HTML report s grafy jako SVG, který jde otevřít v prohlížeči nebo vytisknout.
+use crate::TempMonitorApp;
+use crate::stats::{degree_days, find_excursions, mean_kinetic_temperature, summarize_points};
+use crate::views::format_duration_short;
+use crate::views::plots::lttb_downsample;
+use chrono::{DateTime, Local, NaiveDate};
+use std::fs;
+use log::info;
+use temp_monitor::config::Config;
+use temp_monitor::i18n::{language, tr, trf};
+use temp_monitor::storage::{list_log_dates, log_filename_for_date, read_history_file, Annotation, HistoryPoint};
+use temp_monitor::units::{format_temp, temp_unit, TempUnit};
+
+// Soubor, do kterého se exportuje denní souhrn (Soubor → Exportovat denní souhrn)
+pub const DAILY_SUMMARY_FILE: &str = "denni_souhrn.csv";
+
+impl TempMonitorApp {
+    // Uloží souhrn všech dní s logem do CSV; vrací počet exportovaných dní
+    pub fn export_daily_summary(&mut self) -> Result<usize, csv::Error> {
+        let base = self.config.degree_day_base as f64;
+        let unit = temp_unit();
+        let mut wtr = csv::WriterBuilder::new().delimiter(b';').from_path(DAILY_SUMMARY_FILE)?;
+        // Ve °C zůstávají názvy sloupců beze změny kvůli existujícím tabulkám, jinak nesou jednotku
+        let suffix = if unit == TempUnit::Celsius { String::new() } else { format!(" [{}]", unit.symbol()) };
+        let mut header: Vec<String> = ["Datum", "Mereni", "TeplotaMin", "TeplotaMax", "TeplotaPrumer", "VlhkostMin", "VlhkostMax", "VlhkostPrumer", "DenostupneVytapeni", "DenostupneChlazeni", "Poznamky"].iter().map(|h| h.to_string()).collect();
+        for i in [2, 3, 4, 8, 9] { header[i] += &suffix; }
+        wtr.write_record(&header)?;
+        let decimal = |v: f64| format!("{:.1}", v).replace('.', ",");
+        let (mut exported, mut total_hdd, mut total_cdd) = (0, 0.0, 0.0);
+        for date in list_log_dates() {
+            let Some(s) = self.day_summary(date) else { continue; };
+            let (hdd, cdd) = degree_days(s.avg_temp, base);
+            total_hdd += hdd; total_cdd += cdd;
+            let notes: Vec<String> = self.annotations.iter().filter(|a| a.timestamp.date_naive() == date).map(|a| format!("{} {}", a.timestamp.format("%H:%M"), a.text)).collect();
+            wtr.write_record([date.format("%Y.%m.%d").to_string(), s.count.to_string(), decimal(unit.convert(s.min_temp)), decimal(unit.convert(s.max_temp)),
+                decimal(unit.convert(s.avg_temp)), decimal(s.min_hum), decimal(s.max_hum), decimal(s.avg_hum), decimal(unit.delta(hdd)), decimal(unit.delta(cdd)), notes.join(" | ")])?;
+            exported += 1;
+        }
+        wtr.write_record(["Celkem".to_string(), String::new(), String::new(), String::new(), String::new(), String::new(), String::new(), String::new(), decimal(unit.delta(total_hdd)), decimal(unit.delta(total_cdd)), String::new()])?;
+        wtr.flush()?;
+        info!("Denní souhrn exportován ({} dní).", exported);
+        Ok(exported)
+    }
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+// Jednoduchý SVG graf jedné veličiny s mřížkou a volitelnými vodorovnými mezemi
+fn svg_chart(points: &[HistoryPoint], value: impl Fn(&HistoryPoint) -> f64, unit: &str, color: &str, limits: &[f64]) -> String {
+    const W: f64 = 900.0; const H: f64 = 260.0; const LEFT: f64 = 55.0; const BOTTOM: f64 = 25.0; const TOP: f64 = 10.0;
+    let series: Vec<[f64; 2]> = points.iter().map(|p| [p.timestamp.timestamp() as f64, value(p)]).collect();
+    let series = lttb_downsample(&series, W as usize);
+    if series.len() < 2 { return format!("<p>{}</p>", tr("Nedostatek dat pro graf.")); }
+    let (x_min, x_max) = (series[0][0], series[series.len() - 1][0].max(series[0][0] + 1.0));
+    let (mut y_min, mut y_max) = series.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| (lo.min(p[1]), hi.max(p[1])));
+    for limit in limits { y_min = y_min.min(*limit); y_max = y_max.max(*limit); }
+    if y_max - y_min < 1.0 { y_min -= 0.5; y_max += 0.5; }
+    let sx = |x: f64| LEFT + (x - x_min) / (x_max - x_min) * (W - LEFT - 10.0);
+    let sy = |y: f64| TOP + (y_max - y) / (y_max - y_min) * (H - TOP - BOTTOM);
+    let mut svg = format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="{W}" height="{H}" style="background:#fff;font:11px sans-serif">"#);
+    for i in 0..=4 {
+        let y = y_min + (y_max - y_min) * i as f64 / 4.0;
+        svg += &format!(r##"<line x1="{LEFT}" x2="{}" y1="{y1:.1}" y2="{y1:.1}" stroke="#ddd"/><text x="{}" y="{:.1}" text-anchor="end">{:.1}{}</text>"##, W - 10.0, LEFT - 4.0, sy(y) + 4.0, y, html_escape(unit), y1 = sy(y));
+    }
+    for i in 0..=4 {
+        let x = x_min + (x_max - x_min) * i as f64 / 4.0;
+        let time = DateTime::from_timestamp(x as i64, 0).unwrap_or_default().with_timezone(&Local);
+        svg += &format!(r#"<text x="{:.1}" y="{}" text-anchor="middle">{}</text>"#, sx(x), H - 6.0, time.format("%d.%m. %H:%M"));
+    }
+    for limit in limits {
+        svg += &format!(r##"<line x1="{LEFT}" x2="{}" y1="{y:.1}" y2="{y:.1}" stroke="#c80" stroke-dasharray="6 4"/>"##, W - 10.0, y = sy(*limit));
+    }
+    let path: Vec<String> = series.iter().map(|p| format!("{:.1},{:.1}", sx(p[0]), sy(p[1]))).collect();
+    svg += &format!(r#"<polyline fill="none" stroke="{}" stroke-width="1.5" points="{}"/></svg>"#, color, path.join(" "));
+    svg
+}
+
+pub fn write_html_report(from: NaiveDate, to: NaiveDate, config: &Config, annotations: &[Annotation]) -> std::io::Result<String> {
+    let points: Vec<HistoryPoint> = from.iter_days().take_while(|d| *d <= to).filter_map(|d| read_history_file(&log_filename_for_date(d))).flatten().collect();
+    let filename = format!("report_{}_{}.html", from.format("%Y-%m-%d"), to.format("%Y-%m-%d"));
+    let title = tr("Report teploty a vlhkosti");
+    let mut html = format!(r#"<!DOCTYPE html><html lang="{}"><head><meta charset="utf-8"><title>{}</title>
+<style>body{{font-family:sans-serif;max-width:960px;margin:auto;color:#222}}table{{border-collapse:collapse;margin:8px 0}}td,th{{border:1px solid #bbb;padding:4px 10px;text-align:right}}th{{background:#eee}}h2{{margin-top:28px}}</style></head><body>"#, language().code(), title);
+    html += &format!("<h1>{}</h1><p>{}: <b>{}</b> – <b>{}</b><br>{}: {}<br>{}: {}</p>", title,
+        tr("Období"), from.format("%d.%m.%Y"), to.format("%d.%m.%Y"), tr("Senzor"), html_escape(&config.target_mac), tr("Vytvořeno"), Local::now().format("%d.%m.%Y %H:%M"));
+    match summarize_points(&points) {
+        Some(s) => {
+            html += &format!("<h2>{}</h2><table><tr><th></th><th>{}</th><th>{}</th><th>{}</th></tr>", tr("Statistiky"), tr("Minimum"), tr("Maximum"), tr("Průměr"));
+            html += &format!("<tr><th>{}</th><td>{}</td><td>{}</td><td>{}</td></tr>", tr("Teplota"), format_temp(s.min_temp, 1), format_temp(s.max_temp, 1), format_temp(s.avg_temp, 1));
+            html += &format!("<tr><th>{}</th><td>{:.0} %</td><td>{:.0} %</td><td>{:.0} %</td></tr></table><p>{}: {}</p>", tr("Vlhkost"), s.min_hum, s.max_hum, s.avg_hum, tr("Počet měření"), s.count);
+            if let Some(mkt) = mean_kinetic_temperature(&points, config.mkt_activation_energy as f64) {
+                html += &format!("<p>{}: <b>{}</b></p>", trf("Střední kinetická teplota (MKT, ΔH = {} kJ/mol)", &[&format!("{:.3}", config.mkt_activation_energy)]), format_temp(mkt, 2));
+            }
+        }
+        None => html += &format!("<p>{}</p>", tr("Pro zvolené období nejsou k dispozici žádná data.")),
+    }
+    html += &format!("<h2>{}</h2>", tr("Teplota"));
+    let unit = temp_unit();
+    html += &svg_chart(&points, |p| unit.convert(p.temp as f64), unit.symbol(), "#d33", &[unit.convert(config.temp_warn_low as f64), unit.convert(config.temp_warn_high as f64)]);
+    html += &format!("<h2>{}</h2>", tr("Vlhkost"));
+    html += &svg_chart(&points, |p| p.hum as f64, "%", "#33d", &[]);
+    let excursions = find_excursions(&points, config.temp_warn_low, config.temp_warn_high);
+    html += &format!("<h2>{}</h2>", trf("Překročení mezí ({} – {})", &[&format_temp(config.temp_warn_low as f64, 1), &format_temp(config.temp_warn_high as f64, 1)]));
+    if excursions.is_empty() {
+        html += &format!("<p>{}</p>", tr("Teplota se po celé období držela v mezích."));
+    } else {
+        html += &format!("<table><tr><th>{}</th><th>{}</th><th>{}</th><th>{}</th><th>{}</th></tr>", tr("Začátek"), tr("Konec"), tr("Trvání"), tr("Typ"), tr("Extrém"));
+        for e in &excursions {
+            html += &format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>", e.start.format("%d.%m.%Y %H:%M"), e.end.format("%d.%m.%Y %H:%M"),
+                format_duration_short((e.end - e.start).num_seconds() as f64), tr(if e.above { "nad horní mezí" } else { "pod spodní mezí" }), format_temp(e.extreme as f64, 1));
+        }
+        html += "</table>";
+    }
+    let notes: Vec<&Annotation> = annotations.iter().filter(|a| (from..=to).contains(&a.timestamp.date_naive())).collect();
+    if !notes.is_empty() {
+        html += &format!("<h2>{}</h2><table><tr><th>{}</th><th>{}</th></tr>", tr("Poznámky"), tr("Čas"), tr("Poznámka"));
+        for note in notes { html += &format!("<tr><td>{}</td><td style=\"text-align:left\">{}</td></tr>", note.timestamp.format("%d.%m.%Y %H:%M"), html_escape(&note.text)); }
+        html += "</table>";
+    }
+    html += "</body></html>";
+    fs::write(&filename, html)?;
+    Ok(filename)
+}
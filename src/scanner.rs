@@ -0,0 +1,221 @@
+// --- Bluetooth skener ---
+// Smyčka skenování reklamních paketů sledovaných senzorů; měření posílá procesoru kanálem AppMessage.
+use crate::ble::{BleCentral, DeviceInfo};
+use crate::config::{Config, ScanWindow};
+use crate::error::Error;
+use crate::i18n::{tr, trf};
+use crate::parsing::LayoutDetector;
+use crate::processing::{AppMessage, BleDataPoint};
+use chrono::Local;
+use futures::stream::StreamExt;
+use log::{debug, error, info, warn};
+use std::collections::HashSet;
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+
+// S feature `mock-ble` skener místo Bluetooth přehrává skriptované pakety
+#[cfg(not(feature = "mock-ble"))]
+type Adapter = crate::ble::BtleplugCentral;
+#[cfg(feature = "mock-ble")]
+type Adapter = crate::mock_ble::MockCentral;
+
+// Nejkratší doba bez jediné zprávy skeneru, po které ho hlídač považuje za zaseknutý
+const WATCHDOG_MIN_SECS: u64 = 300;
+// Odstup před restartem, aby skener padající hned po startu nezahltil protokol
+const RESTART_DELAY: Duration = Duration::from_secs(5);
+
+// Nastavení, jehož změna vyžaduje nové skenování: sledované MAC, režim, časy skenu, časová okna a úspora na baterii
+// (barvy, meze apod. skener nezajímají)
+type ScanSettings = (Vec<String>, bool, u64, u64, Vec<ScanWindow>, bool);
+
+fn scan_settings(config: &Config) -> ScanSettings {
+    (config.all_devices().into_iter().map(|(_, mac)| mac).collect(), config.continuous_mode, config.scan_timeout_secs, config.scan_pause_secs, config.scan_windows.clone(), config.battery_saving())
+}
+
+// Dokončí se na nejbližším začátku nebo konci časového okna skenování; bez oken čeká navždy
+async fn scan_window_change(config: &Config) {
+    let now = Local::now().naive_local();
+    match config.next_scan_window_change(now).and_then(|at| (at - now).to_std().ok()) {
+        Some(wait) => tokio::time::sleep(wait).await,
+        None => std::future::pending().await,
+    }
+}
+
+// Dokončí se, jakmile se změní nastavení skenování nebo napájení (obojí přichází sdílenou konfigurací);
+// po zrušení odesílatele konfigurace čeká navždy
+async fn scan_settings_changed(config: &mut watch::Receiver<Config>, current: &ScanSettings) {
+    while config.changed().await.is_ok() {
+        if scan_settings(&config.borrow_and_update()) != *current { return; }
+    }
+    std::future::pending::<()>().await
+}
+
+// Dokončí se, jakmile je skenování pozastavené (`true`), resp. obnovené (`false`); po zrušení odesílatele čeká navždy
+async fn paused_is(paused: &mut watch::Receiver<bool>, value: bool) {
+    loop {
+        if *paused.borrow_and_update() == value { return; }
+        if paused.changed().await.is_err() { std::future::pending::<()>().await }
+    }
+}
+
+// Počká zadanou dobu, nebo jen do změny nastavení skenování či pozastavení; `false`, pokud mezitím přišel požadavek na ukončení
+async fn pause(duration: Duration, shutdown: &mut watch::Receiver<bool>, config: &mut watch::Receiver<Config>, current: &ScanSettings, paused: &mut watch::Receiver<bool>) -> bool {
+    if *shutdown.borrow() { return false; }
+    tokio::select! {
+        _ = tokio::time::sleep(duration) => true,
+        _ = scan_settings_changed(config, current) => { info!("Nastavení skenování se změnilo, začínám nový sken."); true },
+        _ = paused_is(paused, true) => true,
+        _ = shutdown.changed() => false,
+    }
+}
+
+// Chybu zapíše do logu (konzole varování a chyb) a pošle ji k zobrazení ve stavovém řádku; stejná chyba
+// se při opakovaných pokusech (např. vypnutý adaptér) zapíše jen jednou, dokud skenování znovu neuspěje
+fn report(tx: &mpsc::UnboundedSender<AppMessage>, last_error: &mut Option<String>, context: &str, e: Error) {
+    let text = e.to_string();
+    if last_error.as_deref() != Some(text.as_str()) { error!("{}: {}", context, text); } else { debug!("{}: {}", context, text); }
+    *last_error = Some(text);
+    let _ = tx.send(AppMessage::Error(e));
+}
+
+// Teploměry ThermoPro se hlásí názvem „TP357 (XXXX)“ (podobně i příbuzné modely)
+fn is_thermopro(name: Option<&str>) -> bool { name.is_some_and(|name| name.starts_with("TP35")) }
+
+// Skener skončí po odeslání `true` do `shutdown` (nebo zrušení odesílatele); tím zahodí `tx`, procesor dopíše frontu a také skončí.
+// Po odeslání `true` do `paused` přeruší sken a čeká na `false` (pozastavení z místního řízení).
+pub async fn bluetooth_scanner(tx: mpsc::UnboundedSender<AppMessage>, mut config: watch::Receiver<Config>, mut shutdown: watch::Receiver<bool>, mut paused: watch::Receiver<bool>) {
+    info!("Spouštím hlavní smyčku Bluetooth scanneru.");
+    let mut last_error = None;
+    // Nesledované teploměry v dosahu se do protokolu zapíšou jednou; na macOS je to jediný způsob, jak zjistit jejich UUID
+    let mut announced = HashSet::new();
+    // Rozložení dat podle senzoru se pamatuje přes jednotlivé skeny
+    let mut layouts = LayoutDetector::new();
+    while !*shutdown.borrow() {
+        if *paused.borrow_and_update() {
+            info!("Skenování pozastaveno.");
+            let _ = tx.send(AppMessage::StatusUpdate(tr("Skenování pozastaveno").into()));
+            tokio::select! {
+                _ = paused_is(&mut paused, false) => info!("Skenování obnoveno."),
+                _ = shutdown.changed() => break,
+            }
+            continue;
+        }
+        let current_config = config.borrow_and_update().clone();
+        let settings = scan_settings(&current_config);
+        // Na baterii se režim a pauza upraví (viz Config::scan_timing); přechod na baterii zapisuje do protokolu power::monitor
+        let (continuous, scan_duration, pause_duration) = current_config.scan_timing();
+        // Mimo časová okna skener nepracuje vůbec (adaptér se ani neotevře) a čeká na začátek dalšího okna
+        if !current_config.in_scan_window(Local::now().naive_local()) {
+            let next = current_config.next_scan_window_change(Local::now().naive_local()).map(|at| at.format("%H:%M").to_string()).unwrap_or_default();
+            info!("Mimo časové okno skenování, skener nečinný do {}.", next);
+            let _ = tx.send(AppMessage::StatusUpdate(trf("Mimo časové okno skenování, pokračuji v {}", &[&next])));
+            tokio::select! {
+                _ = scan_window_change(&current_config) => {},
+                _ = scan_settings_changed(&mut config, &settings) => info!("Nastavení skenování se změnilo."),
+                _ = paused_is(&mut paused, true) => {},
+                _ = shutdown.changed() => break,
+            }
+            continue;
+        }
+        let target_macs = settings.0.clone();
+        debug!("Nová iterace scanneru, MAC: {}", target_macs.join(", "));
+        let central = match Adapter::open().await {
+            Ok(central) => central,
+            Err(e) => {
+                report(&tx, &mut last_error, "Bluetooth adaptér není k dispozici", e);
+                if !pause(Duration::from_secs(pause_duration), &mut shutdown, &mut config, &settings, &mut paused).await { break; }
+                continue;
+            }
+        };
+        let status_msg = tr(if continuous { "Skenuji (kontinuální režim)..." } else if current_config.battery_saving() { "Skenuji (úspora baterie)..." } else { "Skenuji..." });
+        info!("Zahajuji skenování na adaptéru...");
+        let _ = tx.send(AppMessage::StatusUpdate(status_msg.into()));
+        if let Err(e) = central.start_scan().await {
+            report(&tx, &mut last_error, "Nelze zahájit skenování", e);
+        } else {
+            last_error = None;
+            let scan = tokio::time::timeout(Duration::from_secs(scan_duration), async {
+                // V nekontinuálním režimu skenování končí, jakmile se ozvou všechny senzory
+                let mut seen: Vec<String> = Vec::new();
+                let mut advertisements = match central.advertisements(&target_macs).await {
+                    Ok(advertisements) => advertisements,
+                    Err(e) => { report(&tx, &mut last_error, "Nelze odebírat události Bluetooth adaptéru", e); return; }
+                };
+                while let Some(ad) = advertisements.next().await {
+                    if !target_macs.contains(&ad.address) {
+                        if is_thermopro(ad.local_name.as_deref()) && announced.insert(ad.address.clone()) {
+                            info!("Nalezen nesledovaný teploměr {} s adresou {}; pro sledování ji zadejte v nastavení.", ad.local_name.as_deref().unwrap_or_default(), ad.address);
+                        }
+                        continue;
+                    }
+                    info!("Cílové zařízení nalezeno: {}", ad.address);
+                    let Some((company_id, data)) = ad.manufacturer_data.iter().next() else { continue; };
+                    let Some(reading) = layouts.decode(&ad.address, *company_id, data) else { continue; };
+                    let data_point = BleDataPoint { timestamp: Local::now(), temp: reading.temp, hum: reading.hum, device_id: ad.device_id.clone(), mac: ad.address.clone(), rssi: ad.rssi, battery: reading.battery, raw_data: data.clone() };
+                    info!(mac = ad.address.as_str(), temp = reading.temp, hum = reading.hum, rssi = ad.rssi, battery = reading.battery; "Úspěšně parsována data, posílám do procesoru: T={:.1}C, H={}%", reading.temp, reading.hum);
+                    if tx.send(AppMessage::NewData(data_point)).is_err() { break; }
+                    if !seen.contains(&ad.address) { seen.push(ad.address); }
+                    if !continuous && seen.len() >= target_macs.len() { return; }
+                }
+            });
+            tokio::select! {
+                _ = scan => info!("Skenování ukončeno (timeout)."),
+                _ = scan_settings_changed(&mut config, &settings) => info!("Nastavení skenování se změnilo, přerušuji sken."),
+                _ = shutdown.changed() => info!("Skenování přerušeno kvůli ukončení aplikace."),
+                _ = paused_is(&mut paused, true) => info!("Skenování přerušeno kvůli pozastavení."),
+                _ = scan_window_change(&current_config) => info!("Hranice časového okna skenování, přerušuji sken."),
+            }
+            central.stop_scan().await;
+            if *shutdown.borrow() { break; }
+        }
+        let _ = tx.send(AppMessage::StatusUpdate(tr("Čekám...").into()));
+        debug!("Pauza na {} sekund.", pause_duration);
+        if !pause(Duration::from_secs(pause_duration), &mut shutdown, &mut config, &settings, &mut paused).await { break; }
+    }
+    info!("Bluetooth scanner ukončen.");
+}
+
+// Každý cyklus skeneru pošle aspoň stav („Skenuji…“, „Čekám…“) nebo chybu; ticho po dobu tří cyklů (nejméně 5 minut)
+// znamená, že se zasekl v Bluetooth stacku (otevření adaptéru, zahájení či ukončení skenu se nevrátí)
+fn watchdog_timeout(config: &Config) -> Duration {
+    let (_, scan, pause) = config.scan_timing();
+    let cycle = scan + pause;
+    Duration::from_secs((3 * cycle).max(WATCHDOG_MIN_SECS))
+}
+
+// Skener pod dohledem: jeho zprávy předává procesoru a když se dlouho neozve (a není pozastavený) nebo skončí
+// pádem, spustí ho znovu s novým připojením k adaptéru, místo aby bylo nutné restartovat celou aplikaci.
+// Parametry jako u bluetooth_scanner.
+pub async fn supervised_scanner(tx: mpsc::UnboundedSender<AppMessage>, config: watch::Receiver<Config>, shutdown: watch::Receiver<bool>, paused: watch::Receiver<bool>) {
+    let mut restarts = 0u32;
+    loop {
+        let (scanner_tx, mut scanner_rx) = mpsc::unbounded_channel();
+        let scanner = tokio::spawn(bluetooth_scanner(scanner_tx, config.clone(), shutdown.clone(), paused.clone()));
+        let incident = loop {
+            let timeout = watchdog_timeout(&config.borrow());
+            match tokio::time::timeout(timeout, scanner_rx.recv()).await {
+                Ok(Some(message)) => { let _ = tx.send(message); },
+                Ok(None) if *shutdown.borrow() => return,
+                Ok(None) => break "skončil neočekávaně".to_string(),
+                // Pozastavený skener a skener mimo časové okno mlčí záměrně
+                Err(_) if *paused.borrow() || !config.borrow().in_scan_window(Local::now().naive_local()) => continue,
+                Err(_) => break format!("nereagoval {} s", timeout.as_secs()),
+            }
+        };
+        scanner.abort();
+        restarts += 1;
+        warn!(incident = incident.as_str(), restarts; "Bluetooth skener {}, restartuji ho (restart č. {}).", incident, restarts);
+        let _ = tx.send(AppMessage::StatusUpdate(tr("Skener nereagoval, restartuji ho…").into()));
+        let mut shutdown = shutdown.clone();
+        tokio::select! {
+            _ = tokio::time::sleep(RESTART_DELAY) => {},
+            _ = shutdown.changed() => return,
+        }
+    }
+}
+
+// Údaje o zařízení (model, firmware, sériové číslo) přes vlastní připojení k adaptéru, nezávisle na běžícím skenování
+pub async fn read_device_info(address: &str) -> Result<DeviceInfo, Error> {
+    info!("Čtu údaje o zařízení {}.", address);
+    Adapter::open().await?.device_info(address).await
+}
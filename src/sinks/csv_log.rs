@@ -0,0 +1,19 @@
+// Denní CSV logy (středníky, desetinná čárka), každý senzor do vlastního souboru podle Config::log_filename
+use super::OutputSink;
+use crate::config::Config;
+use crate::error::Result;
+use crate::processing::BleDataPoint;
+use crate::storage::log_to_csv;
+use chrono::Local;
+
+pub struct CsvLogSink;
+
+impl OutputSink for CsvLogSink {
+    fn name(&self) -> &'static str { "csv" }
+
+    fn essential(&self) -> bool { true }
+
+    fn on_reading(&mut self, reading: &BleDataPoint, config: &Config) -> Result<()> {
+        log_to_csv(&config.log_filename(&reading.mac, Local::now().date_naive()), reading.temp, reading.hum, config.emc_enabled)
+    }
+}
@@ -0,0 +1,43 @@
+// Jeden JSON objekt na řádek (měření i upozornění) pro další zpracování skripty, Logstash, Vector apod.
+use super::{Alert, OutputSink};
+use crate::config::Config;
+use crate::error::{Error, Result};
+use crate::processing::BleDataPoint;
+use serde_json::json;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+
+pub struct JsonLinesSink { path: String, file: Option<BufWriter<File>> }
+
+impl JsonLinesSink {
+    pub fn new(path: &str) -> Self { Self { path: path.to_owned(), file: None } }
+
+    // Soubor se otevře (pro připojování) až při prvním zápisu
+    fn write_line(&mut self, value: serde_json::Value) -> Result<()> {
+        let file = match &mut self.file {
+            Some(file) => file,
+            None => self.file.insert(BufWriter::new(OpenOptions::new().append(true).create(true).open(&self.path).map_err(|e| Error::io(&self.path, e))?)),
+        };
+        serde_json::to_writer(&mut *file, &value)?;
+        file.write_all(b"\n").map_err(|e| Error::io(&self.path, e))
+    }
+}
+
+impl OutputSink for JsonLinesSink {
+    fn name(&self) -> &'static str { "json_lines" }
+
+    fn on_reading(&mut self, reading: &BleDataPoint, config: &Config) -> Result<()> {
+        self.write_line(json!({
+            "timestamp": reading.timestamp.to_rfc3339(), "mac": reading.mac, "name": config.device_name(&reading.mac),
+            "temp": reading.temp, "hum": reading.hum, "rssi": reading.rssi, "battery": reading.battery,
+        }))
+    }
+
+    fn on_alert(&mut self, alert: &Alert, config: &Config) -> Result<()> {
+        self.write_line(json!({ "timestamp": alert.timestamp.to_rfc3339(), "mac": alert.mac, "name": config.device_name(&alert.mac), "alert": alert.message }))
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        match &mut self.file { Some(file) => file.flush().map_err(|e| Error::io(&self.path, e)), None => Ok(()) }
+    }
+}
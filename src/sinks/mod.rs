@@ -0,0 +1,114 @@
+// --- Výstupy měření ---
+// Každý cíl (denní CSV log, soubor JSON Lines, …) implementuje OutputSink a procesor je volá přes registr sestavený
+// ze seznamu `outputs` v config.json. Nová integrace = nový modul v této složce a nová varianta OutputConfig.
+mod csv_log;
+mod json_lines;
+
+use crate::config::Config;
+use crate::error::{Error, Result};
+use crate::processing::BleDataPoint;
+use chrono::{DateTime, Local};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+pub use csv_log::CsvLogSink;
+pub use json_lines::JsonLinesSink;
+
+// Měření, které překročilo meze upozornění (temp_warn_*, hum_warn_*)
+pub struct Alert { pub timestamp: DateTime<Local>, pub mac: String, pub message: String }
+
+pub trait OutputSink: Send {
+    fn name(&self) -> &'static str;
+    // Uložené měření (po filtru nesmyslných hodnot a duplikátů)
+    fn on_reading(&mut self, reading: &BleDataPoint, config: &Config) -> Result<()>;
+    fn on_alert(&mut self, _alert: &Alert, _config: &Config) -> Result<()> { Ok(()) }
+    // Nepostradatelný výstup (denní CSV log) zapisuje i při úspoře na baterii, ostatním se měření do připojení k síti odkládají
+    fn essential(&self) -> bool { false }
+    // Volá se, když procesor vyprázdní frontu, a před ukončením nebo přestavbou registru
+    fn flush(&mut self) -> Result<()> { Ok(()) }
+}
+
+// Položka seznamu `outputs`, např. {"type": "json_lines", "path": "mereni.jsonl"}
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OutputConfig { Csv, JsonLines { path: String } }
+
+impl OutputConfig {
+    fn build(&self) -> Box<dyn OutputSink> {
+        match self {
+            OutputConfig::Csv => Box::new(CsvLogSink),
+            OutputConfig::JsonLines { path } => Box::new(JsonLinesSink::new(path)),
+        }
+    }
+}
+
+pub fn default_outputs() -> Vec<OutputConfig> { vec![OutputConfig::Csv] }
+
+// Chyby jednotlivých výstupů; selhání jednoho nebrání zápisu do ostatních
+pub type SinkErrors = Vec<(&'static str, Error)>;
+
+// Nejvíc měření odložených při úspoře na baterii (přes den po sekundě na jeden senzor); nejstarší se pak zahazují
+const MAX_DEFERRED: usize = 100_000;
+
+// Při úspoře na baterii zapisují hned jen nepostradatelné výstupy; ostatním se měření odloží a dopíšou se v původním
+// pořadí po připojení k síti, před přestavbou registru nebo před ukončením. Upozornění se doručují vždy hned.
+pub struct SinkRegistry { configs: Vec<OutputConfig>, sinks: Vec<Box<dyn OutputSink>>, deferred: VecDeque<BleDataPoint> }
+
+impl SinkRegistry {
+    pub fn new(configs: &[OutputConfig]) -> Self { Self { configs: configs.to_vec(), sinks: configs.iter().map(OutputConfig::build).collect(), deferred: VecDeque::new() } }
+
+    // Po změně `outputs` v konfiguraci dopíše staré výstupy (i odložená měření) a sestaví nové
+    pub fn update(&mut self, config: &Config) -> SinkErrors {
+        if config.outputs == self.configs { return Vec::new(); }
+        let errors = self.close(config);
+        *self = Self::new(&config.outputs);
+        errors
+    }
+
+    pub fn on_reading(&mut self, reading: &BleDataPoint, config: &Config) -> SinkErrors {
+        if config.battery_saving() && self.sinks.iter().any(|sink| !sink.essential()) {
+            if self.deferred.len() == MAX_DEFERRED {
+                self.deferred.pop_front();
+                warn!("Příliš mnoho měření odložených při úspoře baterie, nejstarší se do výstupů nezapíše.");
+            }
+            self.deferred.push_back(reading.clone());
+            return self.each(|sink| if sink.essential() { sink.on_reading(reading, config) } else { Ok(()) });
+        }
+        let mut errors = self.write_deferred(config);
+        errors.extend(self.each(|sink| sink.on_reading(reading, config)));
+        errors
+    }
+
+    pub fn on_alert(&mut self, alert: &Alert, config: &Config) -> SinkErrors {
+        let mut errors = if config.battery_saving() { Vec::new() } else { self.write_deferred(config) };
+        errors.extend(self.each(|sink| sink.on_alert(alert, config)));
+        errors
+    }
+
+    pub fn flush(&mut self) -> SinkErrors { self.each(|sink| sink.flush()) }
+
+    // Před ukončením procesoru: dopíše odložená měření bez ohledu na napájení a vyprázdní výstupy
+    pub fn close(&mut self, config: &Config) -> SinkErrors {
+        let mut errors = self.write_deferred(config);
+        errors.extend(self.flush());
+        errors
+    }
+
+    // Odložená měření do výstupů, které je při úspoře přeskočily; z opakované chyby výstupu se ohlásí jen první
+    fn write_deferred(&mut self, config: &Config) -> SinkErrors {
+        if self.deferred.is_empty() { return Vec::new(); }
+        info!("Dopisuji {} měření odložených při úspoře baterie.", self.deferred.len());
+        let mut errors: SinkErrors = Vec::new();
+        for reading in std::mem::take(&mut self.deferred) {
+            for (name, e) in self.each(|sink| if sink.essential() { Ok(()) } else { sink.on_reading(&reading, config) }) {
+                if !errors.iter().any(|(reported, _)| *reported == name) { errors.push((name, e)); }
+            }
+        }
+        errors
+    }
+
+    fn each(&mut self, mut action: impl FnMut(&mut dyn OutputSink) -> Result<()>) -> SinkErrors {
+        self.sinks.iter_mut().filter_map(|sink| action(sink.as_mut()).err().map(|e| (sink.name(), e))).collect()
+    }
+}
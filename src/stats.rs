@@ -0,0 +1,234 @@
+// --- Statistické pomocné funkce ---
+// Výpočty nad měřeními bez vykreslování: souhrny dnů, čas v mezích, anomálie, trend a kvalita záznamu.
+use crate::Period;
+use chrono::{DateTime, Local, Timelike};
+use std::collections::VecDeque;
+use temp_monitor::config::Config;
+use temp_monitor::storage::HistoryPoint;
+
+// Nejdelší doba, kterou jeden vzorek "zastupuje" při výpočtu času (delší mezera = výpadek, ne stav)
+pub const MAX_SAMPLE_GAP_SECS: f64 = 15.0 * 60.0;
+// Univerzální plynová konstanta v kJ/(mol·K) pro výpočet MKT
+const GAS_CONSTANT_KJ: f64 = 0.008_314_462_618;
+// Minimální směrodatná odchylka pro z-skóre (jinak by u stabilního senzoru byla anomálií každá změna o 0,1 °C)
+const ANOMALY_MIN_STD_TEMP: f64 = 0.2;
+const ANOMALY_MIN_STD_HUM: f64 = 1.0;
+
+// Souhrn jednoho dne z denního CSV logu
+#[derive(Clone, Debug)]
+pub struct DaySummary { pub count: usize, pub min_temp: f64, pub max_temp: f64, pub avg_temp: f64, pub min_hum: f64, pub max_hum: f64, pub avg_hum: f64 }
+
+// Kvalita záznamu jednoho dne: pokrytí, mezery a nečitelné řádky v logu
+#[derive(Clone, Debug)]
+pub struct DataQuality { pub received: usize, pub span_secs: f64, pub longest_gap: f64, pub typical_interval: Option<u64>, pub parse_errors: usize }
+
+impl DataQuality {
+    // Počet měření, která měla přijít mezi prvním a posledním měřením dne
+    pub fn expected(&self, interval_secs: u64) -> usize { (self.span_secs / interval_secs as f64).floor() as usize + 1 }
+    pub fn avg_interval(&self) -> Option<f64> { (self.received > 1).then(|| self.span_secs / (self.received - 1) as f64) }
+    pub fn error_rate(&self) -> f64 {
+        let total = self.received + self.parse_errors;
+        if total == 0 { 0.0 } else { self.parse_errors as f64 / total as f64 * 100.0 }
+    }
+}
+
+// Rozdělení času na úseky nad / v / pod mezemi (v sekundách)
+pub struct TimeInRange { pub above: f64, pub within: f64, pub below: f64 }
+
+impl TimeInRange {
+    pub fn total(&self) -> f64 { self.above + self.within + self.below }
+}
+
+// Měření, které se výrazně odchyluje od předchozích hodnot
+#[derive(Clone, Debug)]
+pub struct Anomaly { pub timestamp: DateTime<Local>, pub is_temp: bool, pub value: f64, pub z_score: f64 }
+
+// Souvislý úsek, kdy byla teplota mimo nastavené meze
+pub struct Excursion { pub start: DateTime<Local>, pub end: DateTime<Local>, pub above: bool, pub extreme: f32 }
+
+// Průměr a rozsah hodnot v jedné hodině dne (přes všechny dny období)
+pub struct HourProfile { pub hour: u32, pub min: f64, pub max: f64, pub mean: f64 }
+
+// Lineární trend posledních měření: sklon za sekundu a poslední bod, od kterého se extrapoluje
+pub struct Trend { pub slope_per_sec: f64, pub last_x: f64, pub last_y: f64 }
+
+impl Trend {
+    pub fn value_at(&self, x: f64) -> f64 { self.last_y + self.slope_per_sec * (x - self.last_x) }
+    // Za kolik sekund trend dosáhne hodnoty `target` (jen pokud se k ní blíží)
+    pub fn secs_to_reach(&self, target: f64) -> Option<f64> {
+        let secs = (target - self.last_y) / self.slope_per_sec;
+        (secs.is_finite() && secs > 0.0).then_some(secs)
+    }
+}
+
+// Min/max za posledních 24 h a od dnešní půlnoci
+pub struct RecentExtremes { pub last_24h: Option<(f64, f64)>, pub today: Option<(f64, f64)> }
+
+fn zscore(window: &[f64], value: f64, min_std: f64) -> f64 {
+    let n = window.len() as f64;
+    let mean = window.iter().sum::<f64>() / n;
+    let std = (window.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt().max(min_std);
+    (value - mean) / std
+}
+
+// Porovná nový bod s předchozím oknem měření (zvlášť teplota a vlhkost)
+pub fn check_anomalies<'a>(window: impl Iterator<Item = &'a HistoryPoint>, point: &HistoryPoint, config: &Config) -> Vec<Anomaly> {
+    let (temps, hums): (Vec<f64>, Vec<f64>) = window.map(|p| (p.temp as f64, p.hum as f64)).unzip();
+    // Na krátkém okně nemá z-skóre vypovídající hodnotu
+    if temps.len() < config.anomaly_window.clamp(5, 10) { return Vec::new(); }
+    let threshold = config.anomaly_z_threshold as f64;
+    [(true, point.temp as f64, &temps, ANOMALY_MIN_STD_TEMP), (false, point.hum as f64, &hums, ANOMALY_MIN_STD_HUM)].into_iter()
+        .filter_map(|(is_temp, value, series, min_std)| {
+            let z = zscore(series, value, min_std);
+            (z.abs() >= threshold).then_some(Anomaly { timestamp: point.timestamp, is_temp, value, z_score: z })
+        }).collect()
+}
+
+// Projde celou historii (po načtení z CSV)
+pub fn detect_anomalies(history: &VecDeque<HistoryPoint>, config: &Config) -> Vec<Anomaly> {
+    (1..history.len()).flat_map(|i| {
+        let start = i.saturating_sub(config.anomaly_window);
+        check_anomalies(history.range(start..i), &history[i], config)
+    }).collect()
+}
+
+// Metoda nejmenších čtverců přes body z posledních `window_secs` sekund
+pub fn linear_trend(history: &VecDeque<HistoryPoint>, window_secs: f64, value: impl Fn(&HistoryPoint) -> f64) -> Option<Trend> {
+    let last = history.back()?;
+    let last_x = last.timestamp.timestamp() as f64;
+    let recent: Vec<(f64, f64)> = history.iter().rev()
+        .take_while(|p| last_x - (p.timestamp.timestamp() as f64) <= window_secs)
+        .map(|p| (p.timestamp.timestamp() as f64 - last_x, value(p))).collect();
+    // Příliš málo bodů nebo příliš krátký úsek dává nesmyslné sklony
+    if recent.len() < 3 || recent.iter().map(|(x, _)| -x).fold(0.0, f64::max) < 300.0 { return None; }
+    let n = recent.len() as f64;
+    let mean_x = recent.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = recent.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let sxx: f64 = recent.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    let sxy: f64 = recent.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    if sxx <= f64::EPSILON { return None; }
+    Some(Trend { slope_per_sec: sxy / sxx, last_x, last_y: value(last) })
+}
+
+pub fn points_in_period(history: &VecDeque<HistoryPoint>, period: Period) -> Vec<&HistoryPoint> {
+    match period.start() {
+        Some(start) => history.iter().filter(|p| p.timestamp >= start).collect(),
+        None => history.iter().collect(),
+    }
+}
+
+// Doba (v sekundách), kterou jednotlivé vzorky zastupují: mezera k dalšímu vzorku, omezená MAX_SAMPLE_GAP_SECS
+pub fn sample_durations(points: &[&HistoryPoint]) -> Vec<f64> {
+    let now = Local::now();
+    points.iter().enumerate().map(|(i, p)| {
+        let next = points.get(i + 1).map_or(now, |n| n.timestamp);
+        ((next - p.timestamp).num_seconds() as f64).clamp(0.0, MAX_SAMPLE_GAP_SECS)
+    }).collect()
+}
+
+// Histogram vážený časem: (střed třídy, hodiny strávené v třídě)
+pub fn time_histogram(points: &[&HistoryPoint], durations: &[f64], bin: f64, value: impl Fn(&HistoryPoint) -> f64) -> Vec<(f64, f64)> {
+    let mut bins: std::collections::BTreeMap<i64, f64> = std::collections::BTreeMap::new();
+    for (p, secs) in points.iter().zip(durations) {
+        *bins.entry((value(p) / bin).floor() as i64).or_insert(0.0) += secs / 3600.0;
+    }
+    bins.into_iter().map(|(index, hours)| ((index as f64 + 0.5) * bin, hours)).collect()
+}
+
+pub fn summarize_points(points: &[HistoryPoint]) -> Option<DaySummary> {
+    if points.is_empty() { return None; }
+    let n = points.len() as f64;
+    let temps = points.iter().map(|p| p.temp as f64);
+    let hums = points.iter().map(|p| p.hum as f64);
+    Some(DaySummary {
+        count: points.len(),
+        min_temp: temps.clone().fold(f64::INFINITY, f64::min), max_temp: temps.clone().fold(f64::NEG_INFINITY, f64::max), avg_temp: temps.sum::<f64>() / n,
+        min_hum: hums.clone().fold(f64::INFINITY, f64::min), max_hum: hums.clone().fold(f64::NEG_INFINITY, f64::max), avg_hum: hums.sum::<f64>() / n,
+    })
+}
+
+// Střední kinetická teplota (°C): Tk = (ΔH/R) / −ln(Σ wᵢ·e^(−ΔH/(R·Tᵢ)) / Σ wᵢ), váhy = doba zastoupená vzorkem
+pub fn mean_kinetic_temperature(points: &[HistoryPoint], activation_energy_kj: f64) -> Option<f64> {
+    let refs: Vec<&HistoryPoint> = points.iter().collect();
+    let ratio = activation_energy_kj / GAS_CONSTANT_KJ;
+    let (mut weighted, mut total) = (0.0, 0.0);
+    for (p, secs) in refs.iter().zip(sample_durations(&refs)) {
+        // Minimální váha 1 s zajistí výsledek i pro jediný vzorek nebo vzorky se stejným časem
+        let weight = secs.max(1.0);
+        weighted += weight * (-ratio / (p.temp as f64 + 273.15)).exp();
+        total += weight;
+    }
+    if total <= 0.0 || weighted <= 0.0 { return None; }
+    Some(ratio / -(weighted / total).ln() - 273.15)
+}
+
+// Časově vážené rozdělení měření podle mezí
+pub fn time_in_range(points: &[HistoryPoint], low: f32, high: f32) -> TimeInRange {
+    let refs: Vec<&HistoryPoint> = points.iter().collect();
+    let mut result = TimeInRange { above: 0.0, within: 0.0, below: 0.0 };
+    for (p, secs) in refs.iter().zip(sample_durations(&refs)) {
+        if p.temp > high { result.above += secs; } else if p.temp < low { result.below += secs; } else { result.within += secs; }
+    }
+    result
+}
+
+// Denostupně z průměrné denní teploty: (vytápění, chlazení)
+pub fn degree_days(avg_temp: f64, base: f64) -> (f64, f64) {
+    ((base - avg_temp).max(0.0), (avg_temp - base).max(0.0))
+}
+
+// Najde souvislé úseky mimo meze; úsek končí prvním bodem zpět v rozsahu
+pub fn find_excursions(points: &[HistoryPoint], low: f32, high: f32) -> Vec<Excursion> {
+    let mut excursions: Vec<Excursion> = Vec::new();
+    let mut current: Option<Excursion> = None;
+    for p in points {
+        let state = if p.temp > high { Some(true) } else if p.temp < low { Some(false) } else { None };
+        match (&mut current, state) {
+            (Some(e), Some(above)) if e.above == above => {
+                e.end = p.timestamp;
+                e.extreme = if above { e.extreme.max(p.temp) } else { e.extreme.min(p.temp) };
+            }
+            (_, state) => {
+                if let Some(mut finished) = current.take() { finished.end = p.timestamp; excursions.push(finished); }
+                current = state.map(|above| Excursion { start: p.timestamp, end: p.timestamp, above, extreme: p.temp });
+            }
+        }
+    }
+    excursions.extend(current);
+    excursions
+}
+
+pub fn hourly_profile(points: &[&HistoryPoint], value: impl Fn(&HistoryPoint) -> f64) -> Vec<HourProfile> {
+    let mut hours: Vec<(f64, f64, f64, usize)> = vec![(f64::INFINITY, f64::NEG_INFINITY, 0.0, 0); 24];
+    for p in points {
+        let v = value(p);
+        let entry = &mut hours[p.timestamp.hour() as usize];
+        entry.0 = entry.0.min(v); entry.1 = entry.1.max(v); entry.2 += v; entry.3 += 1;
+    }
+    hours.into_iter().enumerate().filter(|(_, h)| h.3 > 0).map(|(hour, (min, max, sum, count))| HourProfile { hour: hour as u32, min, max, mean: sum / count as f64 }).collect()
+}
+
+pub fn min_max(values: impl Iterator<Item = f64>) -> Option<(f64, f64)> {
+    values.fold(None, |acc, v| Some(acc.map_or((v, v), |(lo, hi): (f64, f64)| (lo.min(v), hi.max(v)))))
+}
+
+pub fn recent_extremes(recent: &VecDeque<HistoryPoint>, value: impl Fn(&HistoryPoint) -> f64) -> RecentExtremes {
+    let now = Local::now();
+    let day_ago = now - chrono::Duration::hours(24);
+    let today = now.date_naive();
+    RecentExtremes {
+        last_24h: min_max(recent.iter().filter(|p| p.timestamp >= day_ago).map(&value)),
+        today: min_max(recent.iter().filter(|p| p.timestamp.date_naive() == today).map(&value)),
+    }
+}
+
+pub fn data_quality(points: &[HistoryPoint], parse_errors: usize) -> Option<DataQuality> {
+    if points.is_empty() && parse_errors == 0 { return None; }
+    let span_secs = match (points.first(), points.last()) { (Some(first), Some(last)) => (last.timestamp - first.timestamp).num_seconds() as f64, _ => 0.0 };
+    let longest_gap = points.windows(2).map(|w| (w[1].timestamp - w[0].timestamp).num_seconds() as f64).fold(0.0, f64::max);
+    // Medián rozestupů: interval, se kterým se ten den skutečně zapisovalo (jednotlivé mezery ho nezkreslí)
+    let mut gaps: Vec<i64> = points.windows(2).map(|w| (w[1].timestamp - w[0].timestamp).num_seconds()).collect();
+    gaps.sort_unstable();
+    let typical_interval = gaps.get(gaps.len() / 2).map(|&gap| gap.max(1) as u64);
+    Some(DataQuality { received: points.len(), span_secs, longest_gap, typical_interval, parse_errors })
+}
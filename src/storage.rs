@@ -0,0 +1,369 @@
+// --- Ukládání měření ---
+// Denní CSV logy (středníky, desetinná čárka), import cizích CSV, export výběru a poznámky na časové ose.
+use crate::error::{Error, Result};
+use crate::i18n::tr;
+use crate::processing::equilibrium_moisture_content;
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveDateTime, NaiveTime};
+use log::{debug, info, warn};
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::thread;
+use std::time::Instant;
+
+pub const MAX_HISTORY_POINTS: usize = 200;
+// Nejvýše tolik starších dní stránkovaných z disku zůstává v paměti (víc jen pokud je graf právě zobrazuje)
+pub const PAGED_DAYS_CAPACITY: usize = 31;
+// Denních logů načtených při jednom dotazu; posun grafu hluboko do minulosti tak okno nezasekne, data přibudou postupně
+const PAGE_LOADS_PER_CALL: usize = 4;
+pub const ANNOTATIONS_FILE: &str = "poznamky.csv";
+// Binární snímek historie v paměti z posledního ukončení aplikace, viz save_history_snapshot
+pub const HISTORY_SNAPSHOT_FILE: &str = "historie.snapshot";
+const SNAPSHOT_MAGIC: &[u8; 8] = b"TP357HS1";
+
+#[derive(Clone, Debug)]
+pub struct HistoryPoint { pub timestamp: DateTime<Local>, pub temp: f32, pub hum: u8, pub rssi: Option<i16>, }
+
+// Uživatelská poznámka na časové ose ("otevřené okno", "zapnutý odvlhčovač")
+#[derive(Clone, Debug)]
+pub struct Annotation { pub timestamp: DateTime<Local>, pub text: String }
+
+// Formát názvu denního logu hlavního senzoru pro chrono
+pub const LOG_FILE_FORMAT: &str = "log_%Y-%m-%d.csv";
+pub fn log_filename_for_date(date: NaiveDate) -> String { date.format(LOG_FILE_FORMAT).to_string() }
+pub fn get_daily_log_filename() -> String { log_filename_for_date(Local::now().date_naive()) }
+
+// Seznam dní, pro které existuje denní CSV log (seřazeno od nejstaršího)
+pub fn list_log_dates() -> Vec<NaiveDate> { list_log_dates_with(LOG_FILE_FORMAT) }
+
+// Totéž pro libovolný formát názvu, např. logy dalších senzorů (viz Config::log_file_format)
+pub fn list_log_dates_with(format: &str) -> Vec<NaiveDate> {
+    let mut dates: Vec<NaiveDate> = fs::read_dir(".").map(|entries| {
+        entries.filter_map(std::result::Result::ok)
+            .filter_map(|entry| NaiveDate::parse_from_str(&entry.file_name().to_string_lossy(), format).ok())
+            .collect()
+    }).unwrap_or_default();
+    dates.sort();
+    dates
+}
+
+// Sloupec EMC se zapisuje jen do souborů, které jej mají v hlavičce (změna nastavení se projeví od nového dne)
+pub fn log_to_csv(filename: &str, temp: f32, hum: u8, log_emc: bool) -> Result<()> {
+    let file_exists = Path::new(&filename).exists();
+    let with_emc = if file_exists { fs::File::open(filename).ok().and_then(|f| std::io::BufRead::lines(std::io::BufReader::new(f)).next()?.ok()).is_some_and(|h| h.split(';').any(|col| col == "EMC")) } else { log_emc };
+    let file = fs::OpenOptions::new().append(true).create(true).open(filename).map_err(|e| Error::io(filename, e))?;
+    let mut wtr = csv::WriterBuilder::new().delimiter(b';').flexible(true).from_writer(file);
+    if !file_exists { if with_emc { wtr.write_record(["Datum", "Cas", "Teplota", "Vlhkost", "EMC"])?; } else { wtr.write_record(["Datum", "Cas", "Teplota", "Vlhkost"])?; } }
+    let now = Local::now(); let temp_str = format!("{:.1}", temp).replace('.', ",");
+    let mut record = vec![ now.format("%Y.%m.%d").to_string(), now.format("%H:%M:%S").to_string(), temp_str, hum.to_string() ];
+    if with_emc { record.push(format!("{:.1}", equilibrium_moisture_content(temp as f64, hum as f64)).replace('.', ",")); }
+    wtr.write_record(&record)?;
+    wtr.flush().map_err(|e| Error::io(filename, e))
+}
+
+pub fn read_history_file(filename: &str) -> Option<Vec<HistoryPoint>> {
+    read_history_file_counted(filename).map(|(points, _)| points)
+}
+
+// Desetinné číslo z logu (desetinná čárka) bez alokace nového řetězce
+fn parse_decimal(field: &[u8]) -> Option<f32> {
+    let mut buf = [0u8; 16];
+    let buf = buf.get_mut(..field.len())?;
+    for (b, &c) in buf.iter_mut().zip(field) { *b = if c == b',' { b'.' } else { c }; }
+    std::str::from_utf8(buf).ok()?.parse().ok()
+}
+
+// Jeden řádek denního logu (datum;čas;teplota;vlhkost…), jen s výpůjčkami z načteného záznamu
+fn parse_log_record(record: &csv::ByteRecord) -> Option<HistoryPoint> {
+    let field = |i| record.get(i).and_then(|f| std::str::from_utf8(f).ok());
+    let date = NaiveDate::parse_from_str(field(0)?, "%Y.%m.%d").ok()?;
+    let time = NaiveTime::parse_from_str(field(1)?, "%H:%M:%S").ok()?;
+    let (temp, hum) = (parse_decimal(record.get(2)?)?, field(3)?.parse().ok()?);
+    // RSSI se do logu nezapisuje, známe ho jen u měření přijatých za běhu
+    Some(HistoryPoint { timestamp: date.and_time(time).and_local_timezone(Local).earliest()?, temp, hum, rssi: None })
+}
+
+// Načte denní log a spočítá i řádky, které nešlo přečíst. Záznamy se čtou po jednom do opakovaně použitého bufferu,
+// takže vícesetmegabajtový log nezabere v paměti navíc ještě všechny řádky jako text. Uzavřené logy minulých dní se
+// mapují do paměti; log změněný dnes (do něj může právě zapisovat procesor) se čte obyčejně, protože zkrácení nebo
+// nahrazení namapovaného souboru by při čtení skončilo pádem (SIGBUS).
+pub fn read_history_file_counted(filename: &str) -> Option<(Vec<HistoryPoint>, usize)> {
+    let file = fs::File::open(filename).ok()?;
+    let metadata = file.metadata().ok()?;
+    if metadata.len() == 0 { return Some((Vec::new(), 0)); }
+    let closed = metadata.modified().is_ok_and(|modified| DateTime::<Local>::from(modified).date_naive() < Local::now().date_naive());
+    let capacity = metadata.len() as usize / 32;
+    let (points, records, parse_errors) = if closed {
+        // SAFETY: soubor se naposledy změnil před dneškem, takže do něj aplikace už nezapisuje (denní logy se po půlnoci
+        // jen čtou); mapování žije jen během tohoto volání
+        let map = unsafe { memmap2::Mmap::map(&file) }.map_err(|e| warn!("Soubor '{}' nelze namapovat do paměti: {}", filename, e)).ok()?;
+        parse_log(&map[..], capacity)
+    } else {
+        parse_log(io::BufReader::new(file), capacity)
+    };
+    info!("Nalezeno {} záznamů v souboru '{}'.", records, filename);
+    if parse_errors > 0 { warn!("Soubor '{}' obsahuje {} nečitelných řádků.", filename, parse_errors); }
+    Some((points, parse_errors))
+}
+
+// Měření, počet záznamů a počet nečitelných řádků denního logu
+fn parse_log(source: impl io::Read, capacity: usize) -> (Vec<HistoryPoint>, usize, usize) {
+    let mut rdr = csv::ReaderBuilder::new().delimiter(b';').flexible(true).from_reader(source);
+    let (mut record, mut records, mut parse_errors) = (csv::ByteRecord::new(), 0, 0);
+    let mut points = Vec::with_capacity(capacity);
+    loop {
+        match rdr.read_byte_record(&mut record) {
+            Ok(false) => break,
+            Ok(true) => { records += 1; match parse_log_record(&record) { Some(p) => points.push(p), None => parse_errors += 1 } },
+            Err(_) => parse_errors += 1,
+        }
+    }
+    (points, records, parse_errors)
+}
+
+// Kompletní historie je celý dnešní log, jinak jeho posledních MAX_HISTORY_POINTS měření. Se staršími logy
+// (`older_logs`) se do paměti načte posledních `memory_days` dní (0 = vše), starší dny stránkuje HistoryPager.
+pub fn load_history_from_csv(load_all: bool, older_logs: bool, memory_days: u32) -> VecDeque<HistoryPoint> {
+    info!("Načítám historii z CSV. Načíst vše: {}, starší logy: {}, dní v paměti: {}", load_all, older_logs, memory_days);
+    let started = Instant::now();
+    let mut history = VecDeque::new();
+    if load_all && older_logs {
+        let first = memory_window_start(memory_days);
+        let dates: Vec<NaiveDate> = list_log_dates().into_iter().filter(|date| first.is_none_or(|first| *date >= first)).collect();
+        history.extend(read_history_with_snapshot(&dates));
+    } else {
+        let filename = get_daily_log_filename();
+        if let Some(points) = read_history_file(&filename) {
+            let start_index = if load_all { 0 } else { points.len().saturating_sub(MAX_HISTORY_POINTS) };
+            history.extend(points.into_iter().skip(start_index));
+        } else { warn!("Soubor s historií '{}' nenalezen.", filename); }
+    }
+    info!(points = history.len(), ms = started.elapsed().as_millis() as u64; "Načteno {} bodů do historie grafu za {} ms.", history.len(), started.elapsed().as_millis());
+    history
+}
+
+// Přečte denní logy souběžně (každé vlákno souvislý úsek dní) a vrátí jejich měření seřazená podle času;
+// chybějící soubory se přeskočí
+pub fn read_history_files(filenames: &[String]) -> Vec<HistoryPoint> {
+    let threads = thread::available_parallelism().map_or(1, |n| n.get()).min(filenames.len()).max(1);
+    let chunk_len = filenames.len().div_ceil(threads).max(1);
+    let mut points: Vec<HistoryPoint> = thread::scope(|scope| {
+        let workers: Vec<_> = filenames.chunks(chunk_len).map(|chunk| scope.spawn(move || chunk.iter().filter_map(|f| read_history_file(f)).flatten().collect::<Vec<_>>())).collect();
+        workers.into_iter().flat_map(|worker| worker.join().unwrap_or_else(|_| { warn!("Čtení části historie selhalo."); Vec::new() })).collect()
+    });
+    // Úseky navazují podle data, řazení (stabilní, nad téměř seřazenými daty rychlé) srovná jen přesahy, např. po změně času
+    points.sort_by_key(|p| p.timestamp);
+    points
+}
+
+// Velikost a čas poslední změny denního logu; snímek platí jen pro den, jehož log se od uložení nezměnil
+fn log_stamp(filename: &str) -> Option<(u64, u64)> {
+    let metadata = fs::metadata(filename).ok()?;
+    let modified = metadata.modified().ok()?.duration_since(std::time::UNIX_EPOCH).ok()?;
+    Some((metadata.len(), modified.as_nanos() as u64))
+}
+
+// Uloží historii v paměti po dnech spolu s velikostí a časem změny jejich denních logů. Zapisuje se při řádném
+// ukončení, až procesor dopsal všechna měření; dny bez logu (výstup CSV vypnutý) se vynechají.
+// Tvar: hlavička, den uložení, počet dní a pro každý den datum, razítko logu a body (čas, teplota, vlhkost, RSSI).
+pub fn save_history_snapshot(history: &VecDeque<HistoryPoint>) -> Result<()> {
+    let started = Instant::now();
+    let mut days: Vec<(NaiveDate, Vec<&HistoryPoint>)> = Vec::new();
+    for point in history {
+        let date = point.timestamp.date_naive();
+        match days.last_mut() { Some((last, points)) if *last == date => points.push(point), _ => days.push((date, vec![point])) }
+    }
+    let days: Vec<_> = days.into_iter().filter_map(|(date, points)| Some((date, log_stamp(&log_filename_for_date(date))?, points))).collect();
+    let mut out = Vec::with_capacity(32 + history.len() * 15);
+    out.extend_from_slice(SNAPSHOT_MAGIC);
+    out.extend_from_slice(&Local::now().date_naive().num_days_from_ce().to_le_bytes());
+    out.extend_from_slice(&(days.len() as u32).to_le_bytes());
+    for (date, (len, modified), points) in &days {
+        out.extend_from_slice(&date.num_days_from_ce().to_le_bytes());
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&modified.to_le_bytes());
+        out.extend_from_slice(&(points.len() as u32).to_le_bytes());
+        for p in points {
+            out.extend_from_slice(&p.timestamp.timestamp().to_le_bytes());
+            out.extend_from_slice(&p.temp.to_le_bytes());
+            out.push(p.hum);
+            out.extend_from_slice(&p.rssi.unwrap_or(i16::MIN).to_le_bytes());
+        }
+    }
+    // Přes dočasný soubor, aby přerušený zápis nezanechal useknutý snímek
+    let temp = format!("{}.tmp", HISTORY_SNAPSHOT_FILE);
+    fs::write(&temp, &out).and_then(|_| fs::rename(&temp, HISTORY_SNAPSHOT_FILE)).map_err(|e| Error::io(HISTORY_SNAPSHOT_FILE, e))?;
+    info!("Snímek historie uložen ({} dní, {} bodů, {} kB) za {} ms.", days.len(), history.len(), out.len() / 1024, started.elapsed().as_millis());
+    Ok(())
+}
+
+// Den ze snímku: razítko logu v době uložení a body
+struct SnapshotDay { stamp: (u64, u64), points: Vec<HistoryPoint> }
+
+// Den uložení a dny snímku; poškozený nebo cizí soubor se ohlásí a nepoužije
+fn read_history_snapshot() -> Option<(NaiveDate, HashMap<NaiveDate, SnapshotDay>)> {
+    let data = fs::read(HISTORY_SNAPSHOT_FILE).ok()?;
+    let mut pos = 0;
+    let mut take = |n: usize| { let bytes = data.get(pos..pos + n); pos += n; bytes };
+    let parsed = (|| {
+        if take(8)? != SNAPSHOT_MAGIC { return None; }
+        let date = |bytes: &[u8]| NaiveDate::from_num_days_from_ce_opt(i32::from_le_bytes(bytes.try_into().ok()?));
+        let saved_on = date(take(4)?)?;
+        let count = u32::from_le_bytes(take(4)?.try_into().ok()?);
+        let mut days = HashMap::new();
+        for _ in 0..count {
+            let day = date(take(4)?)?;
+            let stamp = (u64::from_le_bytes(take(8)?.try_into().ok()?), u64::from_le_bytes(take(8)?.try_into().ok()?));
+            let len = u32::from_le_bytes(take(4)?.try_into().ok()?) as usize;
+            let mut points = Vec::with_capacity(len.min(1 << 20));
+            for _ in 0..len {
+                let timestamp = DateTime::from_timestamp(i64::from_le_bytes(take(8)?.try_into().ok()?), 0)?.with_timezone(&Local);
+                let temp = f32::from_le_bytes(take(4)?.try_into().ok()?);
+                let hum = take(1)?[0];
+                let rssi = Some(i16::from_le_bytes(take(2)?.try_into().ok()?)).filter(|rssi| *rssi != i16::MIN);
+                points.push(HistoryPoint { timestamp, temp, hum, rssi });
+            }
+            days.insert(day, SnapshotDay { stamp, points });
+        }
+        Some((saved_on, days))
+    })();
+    if parsed.is_none() { warn!("Snímek historie '{}' je poškozený, historie se načte z CSV.", HISTORY_SNAPSHOT_FILE); }
+    parsed
+}
+
+// Dny, jejichž log se od uložení snímku nezměnil, se převezmou ze snímku; ostatní (a den uložení, do jehož logu
+// mohla jít i měření, která se do okna už nedostala) se přečtou z CSV
+fn read_history_with_snapshot(dates: &[NaiveDate]) -> Vec<HistoryPoint> {
+    let Some((saved_on, mut snapshot)) = read_history_snapshot() else { return read_history_files(&dates.iter().map(|d| log_filename_for_date(*d)).collect::<Vec<_>>()); };
+    let mut reused = Vec::new();
+    let mut stale = Vec::new();
+    for date in dates {
+        let filename = log_filename_for_date(*date);
+        match snapshot.remove(date) {
+            Some(day) if *date < saved_on && log_stamp(&filename) == Some(day.stamp) => reused.push(day.points),
+            _ => stale.push(filename),
+        }
+    }
+    info!("Ze snímku historie převzato {} dní, z CSV se načte {} dní.", reused.len(), stale.len());
+    let mut points: Vec<HistoryPoint> = reused.into_iter().flatten().collect();
+    points.extend(read_history_files(&stale));
+    points.sort_by_key(|p| p.timestamp);
+    points
+}
+
+// První den historie držené v paměti (None = vše)
+pub fn memory_window_start(memory_days: u32) -> Option<NaiveDate> {
+    (memory_days > 0).then(|| Local::now().date_naive() - chrono::Duration::days(memory_days as i64 - 1))
+}
+
+// Starší část kompletní historie: denní logy se čtou až ve chvíli, kdy graf zobrazí období před historií v paměti,
+// a nejdéle nepoužité dny se zase zahazují
+#[derive(Default)]
+pub struct HistoryPager { dates: Vec<NaiveDate>, listed_on: Option<NaiveDate>, pages: HashMap<NaiveDate, Vec<HistoryPoint>>, used: VecDeque<NaiveDate> }
+
+impl HistoryPager {
+    // Měření s časem v intervalu [from, to) (unixové sekundy); druhá hodnota říká, že některé dny se teprve načtou
+    pub fn points(&mut self, from: i64, to: i64) -> (Vec<&HistoryPoint>, bool) {
+        let local_date = |ts: i64| DateTime::from_timestamp(ts, 0).map(|t| t.with_timezone(&Local).date_naive());
+        let (Some(first), Some(last)) = (local_date(from), local_date(to)) else { return (Vec::new(), false); };
+        // Seznam logů se obnoví jednou denně, aby do něj přibyly dny, které mezitím vypadly z paměti
+        let today = Local::now().date_naive();
+        if self.listed_on != Some(today) { self.dates = list_log_dates(); self.listed_on = Some(today); }
+        let wanted: Vec<NaiveDate> = self.dates.iter().copied().filter(|date| (first..=last).contains(date)).collect();
+        let (mut loads, mut pending) = (0, false);
+        for date in &wanted {
+            if !self.pages.contains_key(date) {
+                if loads == PAGE_LOADS_PER_CALL { pending = true; continue; }
+                loads += 1;
+                debug!("Načítám z disku starší den historie {}.", date);
+                self.pages.insert(*date, read_history_file(&log_filename_for_date(*date)).unwrap_or_default());
+            }
+            self.used.retain(|used| used != date);
+            self.used.push_back(*date);
+        }
+        while self.used.len() > PAGED_DAYS_CAPACITY.max(wanted.len()) {
+            if let Some(oldest) = self.used.pop_front() { self.pages.remove(&oldest); }
+        }
+        let points = wanted.iter().filter_map(|date| self.pages.get(date)).flatten().filter(|p| (from..to).contains(&p.timestamp.timestamp())).collect();
+        (points, pending)
+    }
+
+    pub fn paged_points(&self) -> usize { self.pages.values().map(Vec::len).sum() }
+
+    pub fn clear(&mut self) { *self = Self::default(); }
+}
+
+// Import CSV přetaženého do okna: denní log této aplikace (středníky, datum a čas zvlášť), nebo export z aplikace
+// ThermoPro (čárky, datum a čas v jednom sloupci, teplota v °C nebo °F). Sloupce se hledají podle názvů v hlavičce.
+pub fn read_import_file(path: &Path) -> Result<(Vec<HistoryPoint>, usize)> {
+    pub const DATETIME_FORMATS: [&str; 9] = ["%Y.%m.%d %H:%M:%S", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M:%S", "%Y/%m/%d %H:%M:%S", "%Y/%m/%d %H:%M", "%d.%m.%Y %H:%M:%S", "%d.%m.%Y %H:%M", "%m/%d/%Y %H:%M"];
+    let content = fs::read_to_string(path).map_err(|e| Error::io(path, e))?;
+    let content = content.trim_start_matches('\u{feff}');
+    let delimiter = if content.lines().next().unwrap_or_default().contains(';') { b';' } else { b',' };
+    let mut rdr = csv::ReaderBuilder::new().delimiter(delimiter).flexible(true).from_reader(content.as_bytes());
+    let columns: Vec<String> = rdr.headers()?.iter().map(|c| c.trim().to_lowercase()).collect();
+    let find = |keys: &[&str]| columns.iter().position(|c| keys.iter().any(|key| c.contains(key)));
+    let (Some(date_col), Some(temp_col), Some(hum_col)) = (find(&["datum", "date", "time", "čas"]), find(&["teplota", "temp"]), find(&["vlhkost", "hum"])) else {
+        return Err(Error::Import(tr("v hlavičce chybí sloupce s časem, teplotou nebo vlhkostí").to_string()));
+    };
+    // Čas může být v samostatném sloupci vedle data (Datum;Cas, Date,Time)
+    let time_col = columns.iter().enumerate().position(|(i, c)| i != date_col && (c == "cas" || c == "čas" || c == "time"));
+    let fahrenheit = ["°f", "℉", "fahrenheit", "(f)"].iter().any(|unit| columns[temp_col].contains(unit));
+    let number = |s: &str| s.trim().trim_end_matches(|c: char| c.is_alphabetic() || c == '°' || c == '℃' || c == '℉' || c == '%').trim().replace(',', ".").parse::<f32>().ok();
+    let (mut points, mut skipped) = (Vec::new(), 0);
+    for record in rdr.records() {
+        let point = record.ok().and_then(|r| {
+            let datetime = match time_col { Some(col) => format!("{} {}", r.get(date_col)?.trim(), r.get(col)?.trim()), None => r.get(date_col)?.trim().to_owned() };
+            let naive_dt = DATETIME_FORMATS.iter().find_map(|format| NaiveDateTime::parse_from_str(&datetime, format).ok())?;
+            let temp = number(r.get(temp_col)?)?;
+            let hum = number(r.get(hum_col)?)?;
+            Some(HistoryPoint { timestamp: naive_dt.and_local_timezone(Local).earliest()?, temp: if fahrenheit { (temp - 32.0) * 5.0 / 9.0 } else { temp }, hum: hum.round().clamp(0.0, 100.0) as u8, rssi: None })
+        });
+        match point { Some(p) => points.push(p), None => skipped += 1 }
+    }
+    if points.is_empty() { return Err(Error::Import(tr("soubor neobsahuje žádná čitelná měření").to_string())); }
+    points.sort_by_key(|p| p.timestamp);
+    Ok((points, skipped))
+}
+
+// Poznámky se ukládají vedle denních logů ve stejném formátu data a času
+pub fn load_annotations() -> Vec<Annotation> {
+    let Ok(mut rdr) = csv::ReaderBuilder::new().delimiter(b';').from_path(ANNOTATIONS_FILE) else { return Vec::new(); };
+    let mut annotations: Vec<Annotation> = rdr.records().filter_map(std::result::Result::ok).filter_map(|r| {
+        let naive_dt = NaiveDateTime::parse_from_str(&format!("{} {}", r.get(0)?, r.get(1)?), "%Y.%m.%d %H:%M:%S").ok()?;
+        Some(Annotation { timestamp: naive_dt.and_local_timezone(Local).earliest()?, text: r.get(2)?.to_owned() })
+    }).collect();
+    annotations.sort_by_key(|a| a.timestamp);
+    info!("Načteno {} poznámek.", annotations.len());
+    annotations
+}
+
+// Vybraná měření ve formátu denního logu (°C) doplněném o RSSI; vrací název souboru
+pub fn export_readings(history: &VecDeque<HistoryPoint>, indices: &[usize]) -> Result<String> {
+    let filename = format!("vyber_{}.csv", Local::now().format("%Y-%m-%d_%H%M%S"));
+    let mut wtr = csv::WriterBuilder::new().delimiter(b';').from_path(&filename)?;
+    wtr.write_record(["Datum", "Cas", "Teplota", "Vlhkost", "RSSI"])?;
+    for p in indices.iter().map(|&i| &history[i]) {
+        wtr.write_record([p.timestamp.format("%Y.%m.%d").to_string(), p.timestamp.format("%H:%M:%S").to_string(), format!("{:.1}", p.temp).replace('.', ","), p.hum.to_string(), p.rssi.map_or(String::new(), |rssi| rssi.to_string())])?;
+    }
+    wtr.flush().map_err(|e| Error::io(&filename, e))?;
+    Ok(filename)
+}
+
+pub fn write_annotations(annotations: &[Annotation]) -> Result<()> {
+    let mut wtr = csv::WriterBuilder::new().delimiter(b';').from_path(ANNOTATIONS_FILE)?;
+    wtr.write_record(["Datum", "Cas", "Poznamka"])?;
+    for a in annotations { wtr.write_record([a.timestamp.format("%Y.%m.%d").to_string(), a.timestamp.format("%H:%M:%S").to_string(), a.text.clone()])?; }
+    wtr.flush().map_err(|e| Error::io(ANNOTATIONS_FILE, e))
+}
+
+// Měření za posledních 24 h ze včerejšího a dnešního logu senzoru (bez ohledu na nastavení historie)
+pub fn load_recent_points(filename_for_date: impl Fn(NaiveDate) -> String) -> VecDeque<HistoryPoint> {
+    let now = Local::now();
+    let cutoff = now - chrono::Duration::hours(24);
+    let today = now.date_naive();
+    [today.pred_opt(), Some(today)].into_iter().flatten()
+        .filter_map(|date| read_history_file(&filename_for_date(date)))
+        .flatten().filter(|p| p.timestamp >= cutoff).collect()
+}
@@ -0,0 +1,133 @@
+// --- Ikona v oznamovací oblasti (jen Windows) ---
+// Ikona běží ve vlastním vlákně s vlastní smyčkou zpráv: skryté nebo minimalizované okno eframe
+// se nepřekresluje, takže jeho update() by ikonu ani popisek neobsluhoval.
+use temp_monitor::config::Config;
+use temp_monitor::i18n::{tr, trf};
+use temp_monitor::processing::BleDataPoint;
+use temp_monitor::units::format_temp;
+use log::{error, info};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+use tokio::sync::watch;
+use tray_icon::menu::{CheckMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem, Submenu};
+use tray_icon::{Icon, MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use windows_sys::Win32::Foundation::HWND;
+use windows_sys::Win32::UI::WindowsAndMessaging::{DispatchMessageW, IsIconic, IsWindowVisible, PeekMessageW, SetForegroundWindow, ShowWindow, TranslateMessage, MSG, PM_REMOVE, SW_HIDE, SW_RESTORE};
+
+const ICON_SIZE: u32 = 32;
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+pub enum TrayAction { Open, Settings, Profile(String), Quit }
+
+pub struct Tray {
+    // Kopie přijatých měření hlavního senzoru pro popisek ikony
+    pub readings: mpsc::Sender<BleDataPoint>,
+    actions: mpsc::Receiver<TrayAction>,
+    hwnd: Option<isize>,
+    stop: Arc<AtomicBool>,
+}
+
+impl Tray {
+    pub fn spawn(hwnd: Option<isize>, ctx: eframe::egui::Context, shared_config: watch::Receiver<Config>) -> Self {
+        let (readings_tx, readings_rx) = mpsc::channel();
+        let (actions_tx, actions_rx) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        thread::spawn(move || run_tray(hwnd, ctx, shared_config, readings_rx, actions_tx, thread_stop));
+        Self { readings: readings_tx, actions: actions_rx, hwnd, stop }
+    }
+
+    pub fn poll(&self) -> Option<TrayAction> { self.actions.try_recv().ok() }
+
+    // Schová okno, aplikace dál běží jen v oznamovací oblasti
+    pub fn hide_window(&self) {
+        if let Some(hwnd) = self.hwnd { unsafe { ShowWindow(hwnd as HWND, SW_HIDE); } }
+    }
+}
+
+impl Drop for Tray {
+    fn drop(&mut self) { self.stop.store(true, Ordering::Relaxed); }
+}
+
+// Obnoví skryté nebo minimalizované okno a přenese ho do popředí
+pub fn show_window(hwnd: Option<isize>) {
+    if let Some(hwnd) = hwnd { unsafe { ShowWindow(hwnd as HWND, SW_RESTORE); SetForegroundWindow(hwnd as HWND); } }
+}
+
+fn run_tray(hwnd: Option<isize>, ctx: eframe::egui::Context, shared_config: watch::Receiver<Config>, readings: mpsc::Receiver<BleDataPoint>, actions: mpsc::Sender<TrayAction>, stop: Arc<AtomicBool>) {
+    let open = MenuItem::new(tr("Otevřít"), true, None);
+    let settings = MenuItem::new(tr("Nastavení"), true, None);
+    let quit = MenuItem::new(tr("Ukončit"), true, None);
+    // Profily podle konfigurace při sestavení; po jejich změně okno ikonu vytvoří znovu
+    let (profile_names, active_profile) = { let config = shared_config.borrow(); (config.profiles.iter().map(|p| p.name.clone()).collect::<Vec<_>>(), config.active_profile.clone()) };
+    let profile_items: Vec<CheckMenuItem> = profile_names.iter().map(|name| CheckMenuItem::new(name, true, *name == active_profile, None)).collect();
+    let profiles = Submenu::new(tr("Profil"), !profile_items.is_empty());
+    for item in &profile_items {
+        if let Err(e) = profiles.append(item) { error!("Nelze přidat profil do menu ikony: {}", e); }
+    }
+    let menu = Menu::new();
+    if let Err(e) = menu.append_items(&[&open, &settings, &profiles, &PredefinedMenuItem::separator(), &quit]) { error!("Nelze sestavit menu ikony v oznamovací oblasti: {}", e); return; }
+    let mut color = [128, 128, 128];
+    let Some(initial_icon) = circle_icon(color) else { return; };
+    let icon = match TrayIconBuilder::new().with_menu(Box::new(menu)).with_tooltip(tr("Teploměr")).with_icon(initial_icon).build() {
+        Ok(icon) => icon,
+        Err(e) => { error!("Nelze vytvořit ikonu v oznamovací oblasti: {}", e); return; }
+    };
+    info!("Ikona v oznamovací oblasti vytvořena.");
+    while !stop.load(Ordering::Relaxed) {
+        let mut msg: MSG = unsafe { std::mem::zeroed() };
+        while unsafe { PeekMessageW(&mut msg, std::ptr::null_mut(), 0, 0, PM_REMOVE) } != 0 {
+            unsafe { TranslateMessage(&msg); DispatchMessageW(&msg); }
+        }
+        let config = shared_config.borrow().clone();
+        // Mění popisek a barvu jen při novém měření, aby se ikona zbytečně nepřekreslovala
+        if let Some(data) = readings.try_iter().last() {
+            let tooltip = trf("Teploměr\n{}, {} %\nMěřeno {}", &[&format_temp(data.temp as f64, 1), &data.hum, &data.timestamp.format("%H:%M:%S")]);
+            if let Err(e) = icon.set_tooltip(Some(tooltip)) { error!("Nelze nastavit popisek ikony: {}", e); }
+            let new_color = if data.temp > config.temp_warn_high { [255, 215, 0] } else if data.temp < config.temp_warn_low { [120, 180, 255] } else { [255, 100, 100] };
+            if new_color != color {
+                if let Err(e) = icon.set_icon(circle_icon(new_color)) { error!("Nelze změnit ikonu: {}", e); }
+                color = new_color;
+            }
+        }
+        // Minimalizaci eframe nezachytí (minimalizované okno nevolá update), proto se hlídá tady
+        if let Some(hwnd) = hwnd.filter(|_| config.minimize_to_tray) {
+            unsafe { if IsIconic(hwnd as HWND) != 0 && IsWindowVisible(hwnd as HWND) != 0 { ShowWindow(hwnd as HWND, SW_HIDE); } }
+        }
+        let mut action = None;
+        while let Ok(event) = TrayIconEvent::receiver().try_recv() {
+            if let TrayIconEvent::Click { button: MouseButton::Left, button_state: MouseButtonState::Up, .. } = event { action = Some(TrayAction::Open); }
+        }
+        while let Ok(event) = MenuEvent::receiver().try_recv() {
+            if event.id == *open.id() { action = Some(TrayAction::Open); }
+            else if event.id == *settings.id() { action = Some(TrayAction::Settings); }
+            else if event.id == *quit.id() { action = Some(TrayAction::Quit); }
+            else if let Some(i) = profile_items.iter().position(|item| event.id == *item.id()) { action = Some(TrayAction::Profile(profile_names[i].clone())); }
+        }
+        if let Some(action) = action {
+            // Okno se musí nejdřív ukázat, jinak by se update() nezavolal a akci by nevyřídil
+            show_window(hwnd);
+            if actions.send(action).is_err() { break; }
+            ctx.request_repaint();
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+    info!("Ikona v oznamovací oblasti odstraněna.");
+}
+
+// Vyplněné kolečko s tmavým okrajem, vykreslené přímo do RGBA
+fn circle_icon(color: [u8; 3]) -> Option<Icon> {
+    let center = (ICON_SIZE as f32 - 1.0) / 2.0;
+    let radius = ICON_SIZE as f32 / 2.0 - 1.0;
+    let mut rgba = Vec::with_capacity((ICON_SIZE * ICON_SIZE * 4) as usize);
+    for y in 0..ICON_SIZE {
+        for x in 0..ICON_SIZE {
+            let distance = ((x as f32 - center).powi(2) + (y as f32 - center).powi(2)).sqrt();
+            let pixel = if distance > radius { [0, 0, 0, 0] } else if distance > radius - 2.0 { [30, 30, 30, 255] } else { [color[0], color[1], color[2], 255] };
+            rgba.extend_from_slice(&pixel);
+        }
+    }
+    Icon::from_rgba(rgba, ICON_SIZE, ICON_SIZE).map_err(|e| error!("Neplatná ikona: {}", e)).ok()
+}
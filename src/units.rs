@@ -0,0 +1,48 @@
+// --- Jednotky teploty ---
+// Měření se všude uchovávají ve °C; převod a formátování pro zobrazení ve zvolené jednotce.
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+// Jednotka pro zobrazení a exporty; měření, logy i meze v konfiguraci zůstávají vždy ve °C
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum TempUnit { #[default] Celsius, Fahrenheit, Kelvin }
+
+impl TempUnit {
+    pub const ALL: [TempUnit; 3] = [TempUnit::Celsius, TempUnit::Fahrenheit, TempUnit::Kelvin];
+    pub fn label(self) -> &'static str {
+        match self { TempUnit::Celsius => "°C (Celsius)", TempUnit::Fahrenheit => "°F (Fahrenheit)", TempUnit::Kelvin => "K (Kelvin)" }
+    }
+    pub fn symbol(self) -> &'static str {
+        match self { TempUnit::Celsius => "°C", TempUnit::Fahrenheit => "°F", TempUnit::Kelvin => "K" }
+    }
+    pub fn convert(self, c: f64) -> f64 {
+        match self { TempUnit::Celsius => c, TempUnit::Fahrenheit => c * 1.8 + 32.0, TempUnit::Kelvin => c + 273.15 }
+    }
+    pub fn to_celsius(self, v: f64) -> f64 {
+        match self { TempUnit::Celsius => v, TempUnit::Fahrenheit => (v - 32.0) / 1.8, TempUnit::Kelvin => v - 273.15 }
+    }
+    // Rozdíl teplot (skok, rozkmit, rychlost změny) se posouvá jen měřítkem, ne nulovým bodem
+    pub fn delta(self, d: f64) -> f64 {
+        match self { TempUnit::Fahrenheit => d * 1.8, TempUnit::Celsius | TempUnit::Kelvin => d }
+    }
+}
+
+// Globální jednotka, aby ji mohly použít i volné funkce pro grafy a report (stejně jako jazyk)
+static TEMP_UNIT: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_temp_unit(unit: TempUnit) { TEMP_UNIT.store(unit as u8, Ordering::Relaxed); }
+
+pub fn temp_unit() -> TempUnit {
+    match TEMP_UNIT.load(Ordering::Relaxed) { 1 => TempUnit::Fahrenheit, 2 => TempUnit::Kelvin, _ => TempUnit::Celsius }
+}
+
+// Teplota ve °C převedená a naformátovaná ve zvolené jednotce
+pub fn format_temp(celsius: f64, decimals: usize) -> String {
+    let unit = temp_unit();
+    format!("{:.*}{}", decimals, unit.convert(celsius), unit.symbol())
+}
+
+pub fn format_temp_delta(delta: f64, decimals: usize) -> String {
+    let unit = temp_unit();
+    format!("{:+.*}{}", decimals, unit.delta(delta), unit.symbol())
+}
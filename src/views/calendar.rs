@@ -0,0 +1,86 @@
+// --- Kalendář ---
+// Měsíční přehled dnů obarvený podle zvolené hodnoty a okno s podrobnostmi dne.
+use crate::{CalendarMetric, Severity, TempMonitorApp};
+use crate::views::plots::draw_simple_history_plot;
+use chrono::{Datelike, Local, NaiveDate};
+use eframe::egui;
+use log::info;
+use temp_monitor::i18n::{tr, trf};
+use temp_monitor::storage::{log_filename_for_date, read_history_file};
+use temp_monitor::units::{format_temp, temp_unit};
+
+impl TempMonitorApp {
+    pub fn draw_day_detail_window(&mut self, ctx: &egui::Context) {
+        let Some((date, points)) = &self.detail_day else { return; };
+        let mut is_open = true;
+        let mut set_baseline = false;
+        egui::Window::new(trf("Den {}", &[&date.format("%d.%m.%Y")])).id(egui::Id::new("day_detail")).open(&mut is_open).default_size([600.0, 400.0]).show(ctx, |ui| {
+            if points.is_empty() { ui.label(tr("Pro tento den nejsou k dispozici žádná data.")); return; }
+            set_baseline = ui.add_enabled(self.baseline_day != Some(*date), egui::Button::new(tr("📏 Použít jako referenční den"))).clicked();
+            let height = (ui.available_height() / 2.0 - 20.0).max(120.0);
+            ui.label(egui::RichText::new(tr("Teplota")).strong());
+            draw_simple_history_plot(ui, "day_detail_temp", points, |p| temp_unit().convert(p.temp as f64), egui::Color32::from_rgb(255, 100, 100), temp_unit().symbol(), height);
+            ui.label(egui::RichText::new(tr("Vlhkost")).strong());
+            draw_simple_history_plot(ui, "day_detail_hum", points, |p| p.hum as f64, egui::Color32::from_rgb(100, 100, 255), "%", height);
+        });
+        if set_baseline {
+            let date = *date;
+            info!("Referenční den nastaven na {}.", date);
+            self.baseline_day = Some(date);
+            self.toast(Severity::Info, trf("Referenční den nastaven na {}.", &[&date.format("%d.%m.%Y")]));
+        }
+        if !is_open { self.detail_day = None; }
+    }
+}
+
+pub fn draw_calendar_view(app: &mut TempMonitorApp, ui: &mut egui::Ui) {
+    let today = Local::now().date_naive();
+    let month = app.calendar_month.unwrap_or_else(|| today.with_day(1).unwrap_or(today));
+    ui.horizontal(|ui| {
+        if ui.button("◀").clicked() { app.calendar_month = month.checked_sub_months(chrono::Months::new(1)); }
+        ui.label(egui::RichText::new(month.format("%m / %Y").to_string()).size(18.0).strong());
+        if ui.button("▶").clicked() { app.calendar_month = month.checked_add_months(chrono::Months::new(1)); }
+        if ui.button(tr("Dnes")).clicked() { app.calendar_month = None; }
+        ui.separator();
+        egui::ComboBox::from_id_source("calendar_metric").selected_text(app.calendar_metric.label()).show_ui(ui, |ui| {
+            for metric in CalendarMetric::ALL { ui.selectable_value(&mut app.calendar_metric, metric, metric.label()); }
+        });
+    });
+    let next_month = month.checked_add_months(chrono::Months::new(1)).unwrap_or(month);
+    let days: Vec<NaiveDate> = month.iter_days().take_while(|d| *d < next_month).collect();
+    // Souhrny se počítají z denních CSV souborů a ukládají do mezipaměti
+    for day in &days { app.day_summary(*day); }
+    let leading = month.weekday().num_days_from_monday() as usize;
+    let rows = (leading + days.len()).div_ceil(7);
+    let spacing = 4.0;
+    let cell_w = ((ui.available_width() - 6.0 * spacing) / 7.0).max(40.0);
+    let cell_h = ((ui.available_height() - 30.0 - rows as f32 * spacing) / rows as f32).clamp(30.0, 90.0);
+    egui::Grid::new("calendar_grid").spacing([spacing, spacing]).show(ui, |ui| {
+        for name in ["Po", "Út", "St", "Čt", "Pá", "So", "Ne"] {
+            ui.allocate_ui(egui::vec2(cell_w, 20.0), |ui| ui.centered_and_justified(|ui| ui.label(egui::RichText::new(tr(name)).color(egui::Color32::GRAY))));
+        }
+        ui.end_row();
+        for slot in 0..rows * 7 {
+            let day = slot.checked_sub(leading).and_then(|i| days.get(i));
+            let (rect, response) = ui.allocate_exact_size(egui::vec2(cell_w, cell_h), egui::Sense::click());
+            if let Some(day) = day {
+                let summary = app.day_summaries.get(day).cloned().flatten();
+                let fill = summary.as_ref().map_or(egui::Color32::from_gray(40), |s| app.calendar_metric.color(app.calendar_metric.value(s)).gamma_multiply(0.8));
+                let painter = ui.painter();
+                painter.rect_filled(rect, 4.0, fill);
+                if *day == today { painter.rect_stroke(rect, 4.0, egui::Stroke::new(2.0, egui::Color32::WHITE)); }
+                painter.text(rect.left_top() + egui::vec2(4.0, 2.0), egui::Align2::LEFT_TOP, day.day().to_string(), egui::FontId::proportional(13.0), egui::Color32::WHITE);
+                if let Some(s) = &summary {
+                    painter.text(rect.center(), egui::Align2::CENTER_CENTER, app.calendar_metric.format(app.calendar_metric.value(s)), egui::FontId::proportional(15.0), egui::Color32::BLACK);
+                    let response = response.on_hover_text(format!("{}\n{}: {} – {} (ø {})\n{}: {:.0} – {:.0}% (ø {:.0}%)\n{}: {}",
+                        day.format("%d.%m.%Y"), tr("Teplota"), format_temp(s.min_temp, 1), format_temp(s.max_temp, 1), format_temp(s.avg_temp, 1), tr("Vlhkost"), s.min_hum, s.max_hum, s.avg_hum, tr("Měření"), s.count));
+                    if response.clicked() {
+                        info!("Otevírám detail dne {}.", day);
+                        app.detail_day = Some((*day, read_history_file(&log_filename_for_date(*day)).unwrap_or_default()));
+                    }
+                }
+            }
+            if (slot + 1) % 7 == 0 { ui.end_row(); }
+        }
+    });
+}
@@ -0,0 +1,210 @@
+// --- Porovnání ---
+// Dva dny přes sebe, odchylka od referenčního dne a rozdíl dvou senzorů.
+use crate::{CompareData, TempMonitorApp};
+use crate::stats::{summarize_points, MAX_SAMPLE_GAP_SECS};
+use crate::views::plots::{format_time_axis, points_for_view};
+use chrono::{DateTime, Local, Timelike};
+use eframe::egui;
+use std::collections::VecDeque;
+use log::debug;
+use temp_monitor::i18n::{tr, trf};
+use temp_monitor::storage::{log_filename_for_date, read_history_file, HistoryPoint};
+use temp_monitor::units::{format_temp_delta, temp_unit};
+
+pub fn draw_compare_view(app: &mut TempMonitorApp, ui: &mut egui::Ui) {
+    use egui_plot::{Legend, Line, Plot, PlotPoints};
+    let today = Local::now().date_naive();
+    ui.horizontal(|ui| {
+        ui.label(tr("Den A:"));
+        ui.add(egui_extras::DatePickerButton::new(&mut app.compare_days.0).id_source("compare_day_a").format("%d.%m.%Y"));
+        ui.label(tr("Den B:"));
+        ui.add(egui_extras::DatePickerButton::new(&mut app.compare_days.1).id_source("compare_day_b").format("%d.%m.%Y"));
+        ui.separator();
+        if ui.button(tr("Dnes × včera")).clicked() { app.compare_days = (today, today.pred_opt().unwrap_or(today)); }
+        if ui.button(tr("Dnes × před týdnem")).clicked() { app.compare_days = (today, today - chrono::Duration::days(7)); }
+    });
+    // Pokud se porovnává dnešek, data se znovu načtou s každým novým měřením
+    let (day_a, day_b) = app.compare_days;
+    let live_marker = if day_a == today || day_b == today { app.live_readings } else { 0 };
+    let key = (day_a, day_b, live_marker);
+    if app.compare_cache.as_ref().map(|c| c.key) != Some(key) {
+        debug!("Načítám data pro porovnání dnů {} a {}.", day_a, day_b);
+        let day_a = read_history_file(&log_filename_for_date(day_a)).unwrap_or_default();
+        let day_b = read_history_file(&log_filename_for_date(day_b)).unwrap_or_default();
+        app.compare_cache = Some(CompareData { key, day_a, day_b });
+    }
+    let Some(CompareData { day_a: points_a, day_b: points_b, .. }) = &app.compare_cache else { return; };
+    let label_a = day_a.format("%d.%m.%Y").to_string();
+    let label_b = day_b.format("%d.%m.%Y").to_string();
+    let series = |points: &[HistoryPoint], value: fn(&HistoryPoint) -> f64| -> Vec<[f64; 2]> { points.iter().map(|p| [hours_since_midnight(p.timestamp), value(p)]).collect() };
+    let height = (ui.available_height() / 2.0 - 20.0).max(100.0);
+    for (title, id, unit, value, color_a, color_b) in [
+        ("Teplota", "compare_temp", temp_unit().symbol(), (|p: &HistoryPoint| temp_unit().convert(p.temp as f64)) as fn(&HistoryPoint) -> f64, egui::Color32::from_rgb(255, 100, 100), egui::Color32::from_rgb(255, 200, 120)),
+        ("Vlhkost", "compare_hum", "%", (|p: &HistoryPoint| p.hum as f64) as fn(&HistoryPoint) -> f64, egui::Color32::from_rgb(100, 100, 255), egui::Color32::from_rgb(120, 220, 255)),
+    ] {
+        ui.label(egui::RichText::new(tr(title)).size(14.0).strong());
+        Plot::new(id).height(height).legend(Legend::default()).include_x(0.0).include_x(24.0)
+            .link_axis(egui::Id::new("compare_plots"), true, false)
+            .x_axis_formatter(|mark, _, _| { let minutes = (mark.value * 60.0).round() as i64; format!("{:02}:{:02}", minutes / 60, minutes % 60) })
+            .y_axis_formatter(move |mark, _, _| format!("{:.1}{}", mark.value, unit))
+            .label_formatter(move |name, value| { let minutes = (value.x * 60.0).round() as i64; format!("{}\n{:02}:{:02} – {:.1}{}", name, minutes / 60, minutes % 60, value.y, unit) })
+            .show(ui, |plot_ui| {
+                plot_ui.line(Line::new(PlotPoints::new(series(points_a, value))).color(color_a).width(2.0).name(&label_a));
+                plot_ui.line(Line::new(PlotPoints::new(series(points_b, value))).color(color_b).width(2.0).name(&label_b));
+            });
+    }
+}
+
+// Převod časové značky na hodiny od půlnoci (společná osa 0–24 h pro porovnání dnů)
+fn hours_since_midnight(timestamp: DateTime<Local>) -> f64 {
+    timestamp.num_seconds_from_midnight() as f64 / 3600.0
+}
+
+// Hodnota referenčního dne ve stejnou denní dobu (lineární interpolace mezi sousedními měřeními, ne přes výpadek)
+fn value_at_time_of_day(points: &[HistoryPoint], hours: f64, value: impl Fn(&HistoryPoint) -> f64) -> Option<f64> {
+    let idx = points.partition_point(|p| hours_since_midnight(p.timestamp) < hours);
+    let after = points.get(idx)?;
+    let after_h = hours_since_midnight(after.timestamp);
+    if after_h == hours { return Some(value(after)); }
+    let before = points.get(idx.checked_sub(1)?)?;
+    let before_h = hours_since_midnight(before.timestamp);
+    if (after_h - before_h) * 3600.0 > MAX_SAMPLE_GAP_SECS { return None; }
+    let t = (hours - before_h) / (after_h - before_h);
+    Some(value(before) + (value(after) - value(before)) * t)
+}
+
+// Odchylka zvoleného dne od referenčního dne ve stejnou denní dobu
+pub fn draw_baseline_view(app: &mut TempMonitorApp, ui: &mut egui::Ui) {
+    use egui_plot::{HLine, Line, Plot, PlotPoints};
+    let today = Local::now().date_naive();
+    ui.horizontal(|ui| {
+        ui.label(tr("Den:"));
+        ui.add(egui_extras::DatePickerButton::new(&mut app.baseline_target).id_source("baseline_target").format("%d.%m.%Y"));
+        if ui.button(tr("Dnes")).clicked() { app.baseline_target = today; }
+        ui.separator();
+        ui.label(tr("Referenční den:"));
+        let mut baseline = app.baseline_day.unwrap_or(today.pred_opt().unwrap_or(today));
+        if ui.add(egui_extras::DatePickerButton::new(&mut baseline).id_source("baseline_day").format("%d.%m.%Y")).changed() || app.baseline_day.is_none() {
+            app.baseline_day = Some(baseline);
+        }
+    });
+    let (target, Some(baseline)) = (app.baseline_target, app.baseline_day) else { return; };
+    let live_marker = if target == today || baseline == today { app.live_readings } else { 0 };
+    let key = (target, baseline, live_marker);
+    if app.baseline_cache.as_ref().map(|c| c.key) != Some(key) {
+        debug!("Načítám data pro odchylku dne {} od referenčního dne {}.", target, baseline);
+        let day_a = read_history_file(&log_filename_for_date(target)).unwrap_or_default();
+        let day_b = read_history_file(&log_filename_for_date(baseline)).unwrap_or_default();
+        app.baseline_cache = Some(CompareData { key, day_a, day_b });
+    }
+    let Some(CompareData { day_a: points, day_b: reference, .. }) = &app.baseline_cache else { return; };
+    if reference.is_empty() { ui.label(tr("Pro referenční den nejsou k dispozici žádná data.")); return; }
+    // Denní rozkmit (max − min) – ukazuje, zda se změnila setrvačnost místnosti
+    if let (Some(day), Some(base)) = (summarize_points(points), summarize_points(reference)) {
+        let (swing_t, base_swing_t) = (day.max_temp - day.min_temp, base.max_temp - base.min_temp);
+        let (swing_h, base_swing_h) = (day.max_hum - day.min_hum, base.max_hum - base.min_hum);
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(tr("Denní rozkmit teploty:")).strong());
+            let unit = temp_unit();
+            ui.label(trf("{}{} (reference {}{}, rozdíl {})", &[&format!("{:.1}", unit.delta(swing_t)), &unit.symbol(), &format!("{:.1}", unit.delta(base_swing_t)), &unit.symbol(), &format_temp_delta(swing_t - base_swing_t, 1)]));
+            ui.separator();
+            ui.label(egui::RichText::new(tr("Rozkmit vlhkosti:")).strong());
+            ui.label(trf("{}% (reference {}%, rozdíl {}%)", &[&format!("{:.0}", swing_h), &format!("{:.0}", base_swing_h), &format!("{:+.0}", swing_h - base_swing_h)]));
+        });
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(tr("Průměr:")).strong());
+            ui.label(trf("{}, {}% oproti referenci", &[&format_temp_delta(day.avg_temp - base.avg_temp, 1), &format!("{:+.0}", day.avg_hum - base.avg_hum)]));
+        });
+    }
+    let height = (ui.available_height() / 2.0 - 20.0).max(100.0);
+    for (title, id, unit, value, color) in [
+        ("Odchylka teploty", "baseline_temp", temp_unit().symbol(), (|p: &HistoryPoint| temp_unit().convert(p.temp as f64)) as fn(&HistoryPoint) -> f64, egui::Color32::from_rgb(255, 100, 100)),
+        ("Odchylka vlhkosti", "baseline_hum", "%", (|p: &HistoryPoint| p.hum as f64) as fn(&HistoryPoint) -> f64, egui::Color32::from_rgb(100, 100, 255)),
+    ] {
+        ui.label(egui::RichText::new(tr(title)).size(14.0).strong());
+        Plot::new(id).height(height).include_x(0.0).include_x(24.0).include_y(0.0)
+            .link_axis(egui::Id::new("baseline_plots"), true, false)
+            .x_axis_formatter(|mark, _, _| { let minutes = (mark.value * 60.0).round() as i64; format!("{:02}:{:02}", minutes / 60, minutes % 60) })
+            .y_axis_formatter(move |mark, _, _| format!("{:+.1}{}", mark.value, unit))
+            .label_formatter(move |_, value| { let minutes = (value.x * 60.0).round() as i64; format!("{:02}:{:02} – {:+.1}{}", minutes / 60, minutes % 60, value.y, unit) })
+            .show(ui, |plot_ui| {
+                plot_ui.hline(HLine::new(0.0).color(egui::Color32::DARK_GRAY).width(1.0));
+                plot_ui.line(Line::new(PlotPoints::new(deviation_series(points, reference, value))).color(color).width(2.0));
+            });
+    }
+}
+
+fn deviation_series(points: &[HistoryPoint], baseline: &[HistoryPoint], value: fn(&HistoryPoint) -> f64) -> Vec<[f64; 2]> {
+    points.iter().filter_map(|p| {
+        let hours = hours_since_midnight(p.timestamp);
+        value_at_time_of_day(baseline, hours, value).map(|reference| [hours, value(p) - reference])
+    }).collect()
+}
+
+pub fn draw_differential_view(app: &mut TempMonitorApp, ui: &mut egui::Ui) {
+    use egui_plot::{HLine, Line, Plot, PlotPoints};
+    let devices = app.config.all_devices();
+    if devices.len() < 2 { ui.label(tr("Pro zobrazení rozdílu je potřeba nastavit alespoň dva senzory (Nastavení → Další senzory).")); return; }
+    if !devices.iter().any(|(_, mac)| mac.eq_ignore_ascii_case(&app.diff_devices.0)) { app.diff_devices.0 = devices[0].1.clone(); }
+    if !devices.iter().any(|(_, mac)| mac.eq_ignore_ascii_case(&app.diff_devices.1)) { app.diff_devices.1 = devices[1].1.clone(); }
+    ui.horizontal(|ui| {
+        for (label, id, selected) in [("Senzor A:", "diff_device_a", &mut app.diff_devices.0), ("Senzor B:", "diff_device_b", &mut app.diff_devices.1)] {
+            ui.label(tr(label));
+            egui::ComboBox::from_id_source(id).selected_text(app.config.device_name(selected)).show_ui(ui, |ui| {
+                for (name, mac) in &devices { ui.selectable_value(selected, mac.clone(), name); }
+            });
+        }
+        if ui.button("⇄").on_hover_text(tr("Prohodit senzory")).clicked() { app.diff_devices = (app.diff_devices.1.clone(), app.diff_devices.0.clone()); }
+    });
+    let (mac_a, mac_b) = app.diff_devices.clone();
+    let empty = VecDeque::new();
+    let points_a = app.device_points(&mac_a).unwrap_or(&empty);
+    let points_b = app.device_points(&mac_b).unwrap_or(&empty);
+    match (points_a.back(), points_b.back()) {
+        (Some(a), Some(b)) => {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(format!("ΔT = {}", format_temp_delta((a.temp - b.temp) as f64, 1))).size(32.0).color(egui::Color32::from_rgb(255, 100, 100)));
+                ui.add_space(30.0);
+                ui.label(egui::RichText::new(format!("ΔRH = {:+}%", a.hum as i16 - b.hum as i16)).size(32.0).color(egui::Color32::from_rgb(100, 100, 255)));
+                ui.add_space(30.0);
+                let age = (a.timestamp - b.timestamp).num_seconds().abs();
+                ui.label(egui::RichText::new(trf("Měření A {}, B {} (rozestup {} s)", &[&a.timestamp.format("%H:%M:%S"), &b.timestamp.format("%H:%M:%S"), &age])).color(egui::Color32::GRAY));
+            });
+        }
+        _ => { ui.label(tr("Zatím nejsou k dispozici měření obou senzorů.")); return; }
+    }
+    let height = (ui.available_height() / 2.0 - 20.0).max(100.0);
+    for (title, id, unit, value, color) in [
+        ("Rozdíl teploty (A − B)", "diff_temp", temp_unit().symbol(), (|p: &HistoryPoint| temp_unit().convert(p.temp as f64)) as fn(&HistoryPoint) -> f64, egui::Color32::from_rgb(255, 100, 100)),
+        ("Rozdíl vlhkosti (A − B)", "diff_hum", "%", (|p: &HistoryPoint| p.hum as f64) as fn(&HistoryPoint) -> f64, egui::Color32::from_rgb(100, 100, 255)),
+    ] {
+        ui.label(egui::RichText::new(tr(title)).size(14.0).strong());
+        let series = difference_series(points_a, points_b, value);
+        Plot::new(id).height(height).include_y(0.0)
+            .link_axis(egui::Id::new("diff_plots"), true, false)
+            .x_axis_formatter(|mark, _, range| format_time_axis(mark, range))
+            .y_axis_formatter(move |mark, _, _| format!("{:+.1}{}", mark.value, unit))
+            .label_formatter(move |_, value| { let time = DateTime::from_timestamp(value.x as i64, 0).unwrap_or_default().with_timezone(&Local); format!("{}\n{:+.1}{}", time.format("%H:%M:%S"), value.y, unit) })
+            .show(ui, |plot_ui| {
+                plot_ui.hline(HLine::new(0.0).color(egui::Color32::DARK_GRAY).width(1.0));
+                let points = points_for_view(&series, plot_ui);
+                plot_ui.line(Line::new(PlotPoints::new(points)).color(color).width(2.0));
+            });
+    }
+}
+
+// Hodnota v daném čase lineární interpolací mezi sousedními měřeními (ne přes výpadek)
+fn value_at_time(points: &VecDeque<HistoryPoint>, timestamp: DateTime<Local>, value: impl Fn(&HistoryPoint) -> f64) -> Option<f64> {
+    let idx = points.partition_point(|p| p.timestamp < timestamp);
+    let after = points.get(idx)?;
+    if after.timestamp == timestamp { return Some(value(after)); }
+    let before = points.get(idx.checked_sub(1)?)?;
+    let gap = (after.timestamp - before.timestamp).num_milliseconds() as f64;
+    if gap / 1000.0 > MAX_SAMPLE_GAP_SECS { return None; }
+    let t = (timestamp - before.timestamp).num_milliseconds() as f64 / gap;
+    Some(value(before) + (value(after) - value(before)) * t)
+}
+
+fn difference_series(a: &VecDeque<HistoryPoint>, b: &VecDeque<HistoryPoint>, value: fn(&HistoryPoint) -> f64) -> Vec<[f64; 2]> {
+    a.iter().filter_map(|p| value_at_time(b, p.timestamp, value).map(|other| [p.timestamp.timestamp() as f64, value(p) - other])).collect()
+}
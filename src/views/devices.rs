@@ -0,0 +1,220 @@
+// --- Senzory ---
+// Záložky senzorů, přehled všech senzorů a údaje o zařízení přečtené přes Bluetooth.
+use crate::{DeviceTab, Severity, TempMonitorApp, DASHBOARD_MIN_DEVICES};
+use crate::stats::min_max;
+use crate::views::plots::{draw_simple_history_plot, format_time_axis};
+use crate::views::readout::{draw_humidity_info, draw_scan_metadata, draw_temperature_info, STALE_INTERVALS};
+use chrono::{DateTime, Local};
+use eframe::egui;
+use std::collections::VecDeque;
+use std::sync::mpsc;
+use log::{info, warn};
+use temp_monitor::i18n::{tr, trf};
+use temp_monitor::scanner::read_device_info;
+use temp_monitor::storage::HistoryPoint;
+use temp_monitor::units::{format_temp, temp_unit};
+
+// Barvy senzorů ve společných grafech přehledu
+const DEVICE_COLORS: [egui::Color32; 6] = [
+    egui::Color32::from_rgb(255, 100, 100), egui::Color32::from_rgb(100, 160, 255), egui::Color32::from_rgb(120, 210, 120),
+    egui::Color32::from_rgb(255, 190, 80), egui::Color32::from_rgb(200, 120, 255), egui::Color32::from_rgb(80, 220, 220),
+];
+
+impl TempMonitorApp {
+    // Záložky jen při více senzorech; záložka odebraného senzoru se vrátí na hlavní
+    pub fn draw_device_tabs(&mut self, ui: &mut egui::Ui) {
+        let devices = self.config.all_devices();
+        if let DeviceTab::Device(mac) = &self.device_tab {
+            if !devices.iter().skip(1).any(|(_, m)| m.eq_ignore_ascii_case(mac)) { self.device_tab = DeviceTab::Primary; }
+        }
+        if devices.len() < 2 { self.device_tab = DeviceTab::Primary; return; }
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.device_tab, DeviceTab::All, tr("🏠 Vše"));
+            for (i, (name, mac)) in devices.into_iter().enumerate() {
+                let tab = if i == 0 { DeviceTab::Primary } else { DeviceTab::Device(mac) };
+                ui.selectable_value(&mut self.device_tab, tab, name);
+            }
+        });
+        ui.separator();
+    }
+
+    // Měření senzoru za posledních 24 h
+    pub fn device_points(&self, mac: &str) -> Option<&VecDeque<HistoryPoint>> {
+        if self.config.is_primary(mac) { Some(&self.recent_points) } else { self.device_history.get(&mac.to_uppercase()) }
+    }
+
+    // Připojení k senzoru trvá i desítky sekund, proto běží na pozadí v runtime skeneru
+    fn request_device_info(&mut self, address: String) {
+        let Some(runtime) = &self.runtime else { return; };
+        let (tx, rx) = mpsc::channel();
+        let target = address.clone();
+        runtime.spawn(async move { let _ = tx.send(read_device_info(&target).await); });
+        self.device_info_loader = Some((address, rx));
+    }
+
+    pub fn poll_device_info(&mut self) {
+        let Some((address, rx)) = &self.device_info_loader else { return; };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => { self.device_info_loader = None; return; }
+        };
+        let address = address.clone();
+        self.device_info_loader = None;
+        match result {
+            Ok(info) => { info!("Údaje o zařízení {}: {:?}", address, info); self.device_info.insert(address, info); },
+            Err(e) => { warn!("Údaje o zařízení {} nelze přečíst: {}", address, e); self.toast(Severity::Warning, trf("Údaje o zařízení nelze přečíst: {}", &[&e])); },
+        }
+    }
+
+    // Tlačítko pro čtení údajů o zařízení a jejich výpis pod podrobnostmi posledního měření
+    pub fn draw_device_info(&mut self, ui: &mut egui::Ui) {
+        let Some(address) = self.last_data_point.as_ref().map(|data| data.mac.clone()) else { return; };
+        if self.device_info_loader.as_ref().is_some_and(|(loading, _)| *loading == address) {
+            ui.horizontal(|ui| { ui.spinner(); ui.label(tr("Připojuji se k senzoru…")); });
+        } else if ui.add_enabled(self.device_info_loader.is_none(), egui::Button::new(tr("ℹ Údaje o zařízení")))
+            .on_hover_text(tr("Připojí se k senzoru a přečte model, firmware a sériové číslo (služba Device Information); hodí se při hlášení rozdílů mezi revizemi hardwaru")).clicked() {
+            self.request_device_info(address.clone());
+        }
+        let Some(info) = self.device_info.get(&address) else { return; };
+        for (label, value) in info.fields() {
+            let Some(value) = value else { continue; };
+            ui.horizontal(|ui| { ui.label(egui::RichText::new(tr(label)).color(egui::Color32::GRAY)); ui.label(value); });
+        }
+        if info.fields().iter().all(|(_, value)| value.is_none()) { ui.label(tr("Senzor službu Device Information neposkytuje.")); }
+    }
+}
+
+// Záložka dalšího senzoru: hodnoty, min/max a průměry za 24 h a grafy teploty a vlhkosti
+pub fn draw_device_view(app: &mut TempMonitorApp, ui: &mut egui::Ui, mac: &str) {
+    let empty = VecDeque::new();
+    let points = app.device_history.get(mac).unwrap_or(&empty);
+    let last = app.device_last.get(mac).cloned();
+    ui.columns(4, |columns| {
+        columns[0].vertical_centered(|ui| draw_temperature_info(ui, points, points, &app.config));
+        columns[1].vertical_centered(|ui| draw_humidity_info(ui, points, points, &app.config));
+        columns[2].vertical(|ui| draw_scan_metadata(ui, &last, &app.scan_status, &app.config));
+        columns[3].vertical(|ui| {
+            ui.label(egui::RichText::new(tr("Posledních 24 h")).color(egui::Color32::GRAY));
+            ui.label(trf("Počet měření: {}", &[&points.len()]));
+            if !points.is_empty() {
+                let count = points.len() as f64;
+                ui.label(trf("Průměr: {}", &[&format_temp(points.iter().map(|p| p.temp as f64).sum::<f64>() / count, 1)]));
+                ui.label(trf("Průměrná vlhkost: {} %", &[&format!("{:.0}", points.iter().map(|p| p.hum as f64).sum::<f64>() / count)]));
+            }
+        });
+    });
+    ui.separator();
+    let points: Vec<HistoryPoint> = points.iter().cloned().collect();
+    let height = (ui.available_height() / 2.0 - 20.0).max(100.0);
+    ui.label(egui::RichText::new(tr("Teplota")).size(14.0).strong());
+    draw_simple_history_plot(ui, &format!("device_temp_{}", mac), &points, |p| temp_unit().convert(p.temp as f64), egui::Color32::from_rgb(255, 100, 100), temp_unit().symbol(), height);
+    ui.label(egui::RichText::new(tr("Vlhkost")).size(14.0).strong());
+    draw_simple_history_plot(ui, &format!("device_hum_{}", mac), &points, |p| p.hum as f64, egui::Color32::from_rgb(100, 100, 255), "%", height);
+}
+
+// Záložka „Vše“: aktuální hodnoty všech senzorů a jejich průběh za 24 h ve společných grafech
+pub fn draw_all_devices_view(app: &mut TempMonitorApp, ui: &mut egui::Ui) {
+    use egui_plot::{Legend, Line, Plot, PlotPoints};
+    let devices = app.config.all_devices();
+    let mut cards = app.dashboard_cards.unwrap_or(devices.len() >= DASHBOARD_MIN_DEVICES);
+    ui.horizontal(|ui| {
+        if ui.selectable_label(cards, tr("▦ Karty")).clicked() { cards = true; app.dashboard_cards = Some(true); }
+        if ui.selectable_label(!cards, tr("🗠 Grafy")).clicked() { cards = false; app.dashboard_cards = Some(false); }
+        if cards { ui.add(egui::Slider::new(&mut app.dashboard_card_width, 160.0..=420.0).text(tr("Šířka karty"))); }
+    });
+    ui.separator();
+    if cards { return draw_dashboard_cards(app, ui, &devices); }
+    egui::Grid::new("all_devices_grid").num_columns(4).striped(true).spacing([30.0, 4.0]).show(ui, |ui| {
+        for (i, (name, mac)) in devices.iter().enumerate() {
+            ui.label(egui::RichText::new(name).strong().color(DEVICE_COLORS[i % DEVICE_COLORS.len()]));
+            match app.device_points(mac).and_then(|points| points.back()) {
+                Some(point) => {
+                    ui.label(egui::RichText::new(format_temp(point.temp as f64, 1)).size(20.0).color(egui::Color32::from_rgb(255, 100, 100)));
+                    ui.label(egui::RichText::new(format!("{}%", point.hum)).size(20.0).color(egui::Color32::from_rgb(100, 100, 255)));
+                    ui.label(egui::RichText::new(point.timestamp.format("%H:%M:%S").to_string()).color(egui::Color32::GRAY));
+                }
+                None => { ui.label(tr("N/A")); ui.label(""); ui.label(""); }
+            }
+            ui.end_row();
+        }
+    });
+    ui.separator();
+    let height = (ui.available_height() / 2.0 - 20.0).max(100.0);
+    for (title, id, unit, value) in [
+        ("Teplota", "all_devices_temp", temp_unit().symbol(), (|p: &HistoryPoint| temp_unit().convert(p.temp as f64)) as fn(&HistoryPoint) -> f64),
+        ("Vlhkost", "all_devices_hum", "%", (|p: &HistoryPoint| p.hum as f64) as fn(&HistoryPoint) -> f64),
+    ] {
+        ui.label(egui::RichText::new(tr(title)).size(14.0).strong());
+        Plot::new(id).height(height).legend(Legend::default())
+            .link_axis(egui::Id::new("all_devices_plots"), true, false)
+            .x_axis_formatter(|mark, _, range| format_time_axis(mark, range))
+            .y_axis_formatter(move |mark, _, _| format!("{:.1}{}", mark.value, unit))
+            .label_formatter(move |name, value| { let time = DateTime::from_timestamp(value.x as i64, 0).unwrap_or_default().with_timezone(&Local); format!("{}\n{}\n{:.1}{}", name, time.format("%H:%M:%S"), value.y, unit) })
+            .show(ui, |plot_ui| {
+                for (i, (name, mac)) in devices.iter().enumerate() {
+                    let Some(points) = app.device_points(mac) else { continue; };
+                    let series: Vec<[f64; 2]> = points.iter().map(|p| [p.timestamp.timestamp() as f64, value(p)]).collect();
+                    plot_ui.line(Line::new(PlotPoints::new(series)).color(DEVICE_COLORS[i % DEVICE_COLORS.len()]).width(2.0).name(name));
+                }
+            });
+    }
+}
+
+// Karta na senzor: název, hodnoty, průběh teploty za 24 h a stav vůči mezím; kliknutím se otevře jeho záložka
+fn draw_dashboard_cards(app: &mut TempMonitorApp, ui: &mut egui::Ui, devices: &[(String, String)]) {
+    let width = app.dashboard_card_width;
+    let stale_secs = (app.config.expected_interval_secs() * STALE_INTERVALS) as i64;
+    let mut open = None;
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        ui.horizontal_wrapped(|ui| {
+            ui.spacing_mut().item_spacing = egui::vec2(10.0, 10.0);
+            for (i, (name, mac)) in devices.iter().enumerate() {
+                let points = app.device_points(mac);
+                let last = points.and_then(|p| p.back());
+                let (state, color) = match last {
+                    None => (tr("Bez dat"), egui::Color32::GRAY),
+                    Some(p) if (Local::now() - p.timestamp).num_seconds() > stale_secs => (tr("Bez dat"), egui::Color32::GRAY),
+                    Some(p) if p.temp > app.config.temp_warn_high => (tr("Nad horní mezí"), egui::Color32::GOLD),
+                    Some(p) if p.temp < app.config.temp_warn_low => (tr("Pod spodní mezí"), egui::Color32::from_rgb(120, 180, 255)),
+                    Some(_) => (tr("V mezích"), egui::Color32::from_rgb(80, 200, 120)),
+                };
+                let response = egui::Frame::group(ui.style()).stroke(egui::Stroke::new(1.5, color)).show(ui, |ui| {
+                    ui.set_width(width);
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(name).strong().color(DEVICE_COLORS[i % DEVICE_COLORS.len()]));
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| { ui.label(egui::RichText::new(state).color(color)); });
+                    });
+                    match last {
+                        Some(point) => {
+                            ui.horizontal(|ui| {
+                                ui.label(egui::RichText::new(format_temp(point.temp as f64, 1)).size(28.0).color(egui::Color32::from_rgb(255, 100, 100)));
+                                ui.label(egui::RichText::new(format!("{}%", point.hum)).size(20.0).color(egui::Color32::from_rgb(100, 100, 255)));
+                            });
+                            ui.label(egui::RichText::new(point.timestamp.format("%d.%m. %H:%M:%S").to_string()).color(egui::Color32::GRAY));
+                        }
+                        None => { ui.label(egui::RichText::new(tr("N/A")).size(28.0)); }
+                    }
+                    draw_sparkline(ui, points, width);
+                }).response.interact(egui::Sense::click());
+                if response.on_hover_text(tr("Kliknutím otevřete záložku senzoru")).clicked() {
+                    open = Some(if i == 0 { DeviceTab::Primary } else { DeviceTab::Device(mac.clone()) });
+                }
+            }
+        });
+    });
+    if let Some(tab) = open { app.device_tab = tab; }
+}
+
+// Průběh teploty za 24 h bez os, přímo štětcem
+fn draw_sparkline(ui: &mut egui::Ui, points: Option<&VecDeque<HistoryPoint>>, width: f32) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(width, 40.0), egui::Sense::hover());
+    let Some(points) = points.filter(|p| p.len() >= 2) else { return; };
+    let (Some((t0, t1)), Some((lo, hi))) = (min_max(points.iter().map(|p| p.timestamp.timestamp() as f64)), min_max(points.iter().map(|p| p.temp as f64))) else { return; };
+    let (span_t, span_v) = ((t1 - t0).max(1.0), (hi - lo).max(0.5));
+    let line: Vec<egui::Pos2> = points.iter().map(|p| egui::pos2(
+        rect.left() + ((p.timestamp.timestamp() as f64 - t0) / span_t) as f32 * rect.width(),
+        rect.bottom() - ((p.temp as f64 - lo) / span_v) as f32 * rect.height(),
+    )).collect();
+    ui.painter().add(egui::Shape::line(line, egui::Stroke::new(1.5, egui::Color32::from_rgb(255, 100, 100))));
+}
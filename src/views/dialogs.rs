@@ -0,0 +1,154 @@
+// --- Dialogová okna ---
+// Dotaz při ukončení, lišta importu, report, O aplikaci, protokol a hlášení o pádu.
+use crate::{console, open_folder, Severity, TempMonitorApp};
+use crate::report::write_html_report;
+use eframe::egui;
+use std::time::Duration;
+use log::{info, warn, error};
+use temp_monitor::config::config_path;
+use temp_monitor::i18n::{tr, trf};
+use temp_monitor::storage::{get_daily_log_filename, ANNOTATIONS_FILE};
+
+impl TempMonitorApp {
+    // Dotaz při ukončení, když zápis dat nedoběhl; po doběhnutí se okno zavře samo (skener už je zastavený, návrat proto nenabízí)
+    pub fn draw_exit_prompt(&mut self, ctx: &egui::Context) {
+        if !self.exit_prompt { return; }
+        let finished = self.background_processor.as_ref().is_none_or(|p| p.is_finished());
+        let mut force = false;
+        egui::Window::new(tr("Ukončení")).collapsible(false).resizable(false).anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO).show(ctx, |ui| {
+            ui.label(tr("Zápis přijatých měření do CSV ještě neskončil."));
+            ui.horizontal(|ui| {
+                ui.spinner();
+                if ui.button(tr("Ukončit přesto")).on_hover_text(tr("Nezapsaná měření se ztratí")).clicked() { force = true; }
+            });
+        });
+        if finished || force {
+            if force { warn!("Ukončuji bez dokončení zápisu dat."); }
+            self.exit_prompt = false;
+            self.shutdown_done = true;
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        }
+        ctx.request_repaint_after(Duration::from_millis(100));
+    }
+
+    // Pruh nad obsahem okna, dokud se místo živých dat zobrazuje importovaný soubor
+    pub fn draw_import_banner(&mut self, ctx: &egui::Context) {
+        let Some(imported) = &self.imported else { return; };
+        let mut close = false;
+        egui::TopBottomPanel::top("import_banner").frame(egui::Frame::default().fill(egui::Color32::from_rgb(90, 70, 0)).inner_margin(6.0)).show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(trf("📂 Zobrazena importovaná data: {}", &[&imported.name])).strong().color(egui::Color32::WHITE));
+                if let (Some(first), Some(last)) = (self.history.front(), self.history.back()) {
+                    ui.label(format!("{} – {} ({})", first.timestamp.format("%d.%m.%Y %H:%M"), last.timestamp.format("%d.%m.%Y %H:%M"), trf("{} měření", &[&self.history.len()])));
+                }
+                ui.label(egui::RichText::new(tr("Živá měření se dál zaznamenávají.")).color(egui::Color32::LIGHT_GRAY));
+                if ui.button(tr("Zpět na živá data")).clicked() { close = true; }
+            });
+        });
+        if close { self.close_import(); }
+    }
+
+    pub fn draw_report_window(&mut self, ctx: &egui::Context) {
+        if !self.report_open { return; }
+        let mut is_open = true;
+        let mut create = false;
+        egui::Window::new(tr("Vytvořit report")).open(&mut is_open).resizable(false).show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr("Od:"));
+                ui.add(egui_extras::DatePickerButton::new(&mut self.report_range.0).id_source("report_from").format("%d.%m.%Y"));
+                ui.label(tr("Do:"));
+                ui.add(egui_extras::DatePickerButton::new(&mut self.report_range.1).id_source("report_to").format("%d.%m.%Y"));
+            });
+            ui.label(tr("Report se uloží jako samostatný HTML soubor (grafy, statistiky, překročení mezí)."));
+            ui.label(egui::RichText::new(tr("Do PDF jej lze převést tiskem z prohlížeče.")).color(egui::Color32::GRAY));
+            create = ui.button(tr("Vytvořit")).clicked();
+        });
+        if create {
+            let (from, to) = if self.report_range.0 <= self.report_range.1 { self.report_range } else { (self.report_range.1, self.report_range.0) };
+            let (severity, message) = match write_html_report(from, to, &self.config, &self.annotations) {
+                Ok(filename) => { info!("Report uložen do '{}'.", filename); (Severity::Success, trf("Report uložen do '{}'.", &[&filename])) },
+                Err(e) => { error!("Vytvoření reportu selhalo: {}", e); (Severity::Error, trf("Report se nepodařilo uložit: {}", &[&e])) },
+            };
+            self.toast(severity, message);
+            is_open = false;
+        }
+        self.report_open = is_open;
+    }
+
+    // Verze, datum sestavení a kde aplikace drží konfiguraci a logy
+    pub fn draw_about_window(&mut self, ctx: &egui::Context) {
+        if !self.about_open { return; }
+        let dir = std::env::current_dir().unwrap_or_default();
+        egui::Window::new(tr("O aplikaci")).open(&mut self.about_open).collapsible(false).resizable(false).show(ctx, |ui| {
+            ui.heading(tr("Teploměr"));
+            ui.label(trf("Verze {} (sestaveno {})", &[&env!("CARGO_PKG_VERSION"), &env!("BUILD_DATE")]));
+            ui.separator();
+            egui::Grid::new("about_paths").num_columns(2).show(ui, |ui| {
+                for (label, path) in [
+                    (tr("Složka dat:"), dir.clone()),
+                    (tr("Konfigurace:"), dir.join(config_path())),
+                    (tr("Dnešní log:"), dir.join(get_daily_log_filename())),
+                    (tr("Poznámky:"), dir.join(ANNOTATIONS_FILE)),
+                ] {
+                    ui.label(egui::RichText::new(label).color(egui::Color32::GRAY));
+                    ui.label(path.display().to_string());
+                    ui.end_row();
+                }
+            });
+            if ui.button(tr("📂 Otevřít složku s daty")).clicked() {
+                if let Err(e) = open_folder(&dir) { error!("Nelze otevřít složku {}: {}", dir.display(), e); }
+            }
+            ui.separator();
+            ui.label(trf("Autorem aplikace je {}", &[&"Soběslav Holec"]));
+            ui.label(tr("Licence Apache 2.0. Postaveno na knihovnách egui, btleplug a tokio."));
+            ui.hyperlink("https://github.com/crapper001/Thermopro-TP357-PC-app");
+        });
+    }
+
+    // Protokol aplikace od spuštění s filtrem úrovně a hledáním, aby šla diagnostika zkopírovat do hlášení chyby
+    pub fn draw_log_window(&mut self, ctx: &egui::Context) {
+        if !self.log_window_open { return; }
+        let level_label = |level: log::Level| match level { log::Level::Error => tr("Chyby"), log::Level::Warn => tr("Varování a chyby"), _ => tr("Vše") };
+        let entries = console::log_entries(self.log_level, &self.log_search);
+        egui::Window::new(tr("Protokol aplikace")).open(&mut self.log_window_open).default_size([640.0, 400.0]).show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_source("log_level").selected_text(level_label(self.log_level)).show_ui(ui, |ui| {
+                    for level in [log::Level::Error, log::Level::Warn, log::Level::Info] { ui.selectable_value(&mut self.log_level, level, level_label(level)); }
+                });
+                ui.add(egui::TextEdit::singleline(&mut self.log_search).hint_text(tr("Hledat…")).desired_width(200.0));
+                ui.label(trf("{} záznamů", &[&entries.len()]));
+                if ui.button(tr("Kopírovat")).clicked() {
+                    let text: Vec<String> = entries.iter().map(|e| format!("{} [{}] {}", e.timestamp.format("%d.%m.%Y %H:%M:%S"), e.level, e.message)).collect();
+                    ui.ctx().output_mut(|o| o.copied_text = text.join("\n"));
+                }
+            });
+            ui.separator();
+            // Řádky bez zalamování, aby stačilo vykreslit jen ty viditelné
+            let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+            egui::ScrollArea::both().auto_shrink([false, false]).stick_to_bottom(true).show_rows(ui, row_height, entries.len(), |ui, rows| {
+                for entry in &entries[rows] {
+                    let color = match entry.level { log::Level::Error => egui::Color32::from_rgb(255, 90, 90), log::Level::Warn => egui::Color32::GOLD, _ => ui.visuals().text_color() };
+                    let text = format!("{} {:<5} {}", entry.timestamp.format("%d.%m. %H:%M:%S"), entry.level, entry.message);
+                    ui.add(egui::Label::new(egui::RichText::new(text).monospace().color(color)).wrap(false));
+                }
+            });
+        });
+    }
+
+    pub fn draw_crash_report_window(&mut self, ctx: &egui::Context) {
+        let Some(path) = self.crash_report.clone() else { return; };
+        let mut close = false;
+        egui::Window::new(tr("Aplikace minule neočekávaně skončila")).collapsible(false).resizable(false).anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO).show(ctx, |ui| {
+            ui.label(tr("Hlášení o pádu bylo uloženo. Přiložte ho prosím k hlášení chyby."));
+            ui.label(egui::RichText::new(path.display().to_string()).monospace());
+            ui.horizontal(|ui| {
+                if ui.button(tr("📄 Otevřít hlášení")).clicked() {
+                    if let Err(e) = open_folder(&path) { error!("Nelze otevřít hlášení o pádu {}: {}", path.display(), e); }
+                    close = true;
+                }
+                if ui.button(tr("Zavřít")).clicked() { close = true; }
+            });
+        });
+        if close { self.crash_report = None; }
+    }
+}
@@ -0,0 +1,120 @@
+// --- Rozložení hodnot ---
+// Histogram doby v jednotlivých hodnotách, denní profil po hodinách a korelace teploty s vlhkostí.
+use crate::{Period, TempMonitorApp};
+use crate::stats::{hourly_profile, points_in_period, sample_durations, time_histogram};
+use chrono::Timelike;
+use eframe::egui;
+use temp_monitor::i18n::{tr, trf};
+use temp_monitor::storage::HistoryPoint;
+use temp_monitor::units::temp_unit;
+
+// Relativní vlhkost, od které hrozí kondenzace a plísně na chladnějších površích
+const CONDENSATION_RISK_HUM: f64 = 80.0;
+
+pub fn draw_histogram_view(app: &mut TempMonitorApp, ui: &mut egui::Ui) {
+    use egui_plot::{Bar, BarChart, Plot};
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_source("histogram_period").selected_text(app.histogram_period.label()).show_ui(ui, |ui| {
+            for period in Period::ALL { ui.selectable_value(&mut app.histogram_period, period, period.label()); }
+        });
+        ui.add(egui::DragValue::new(&mut app.histogram_temp_bin).prefix(trf("Třída teploty ({}): ", &[&temp_unit().symbol()])).speed(0.05).clamp_range(0.1..=5.0));
+        ui.add(egui::DragValue::new(&mut app.histogram_hum_bin).prefix(tr("Třída vlhkosti (%): ")).clamp_range(1..=20));
+        if !app.config.loads_older_logs() || app.config.history_memory_days > 0 { ui.label(egui::RichText::new(tr("Počítá se jen z historie načtené v paměti.")).color(egui::Color32::GRAY)); }
+    });
+    let points = points_in_period(&app.history, app.histogram_period);
+    let durations = sample_durations(&points);
+    let total_hours: f64 = durations.iter().sum::<f64>() / 3600.0;
+    ui.label(trf("Pokryto {} h z {} měření", &[&format!("{:.1}", total_hours), &points.len()]));
+    let temp_bin = app.histogram_temp_bin as f64;
+    let hum_bin = app.histogram_hum_bin as f64;
+    let unit = temp_unit();
+    let temp_bars: Vec<Bar> = time_histogram(&points, &durations, temp_bin, |p| unit.convert(p.temp as f64)).into_iter()
+        .map(|(center, hours)| Bar::new(center, hours).width(temp_bin * 0.95).name(format!("{:.1}–{:.1}{}", center - temp_bin / 2.0, center + temp_bin / 2.0, unit.symbol()))).collect();
+    let hum_bars: Vec<Bar> = time_histogram(&points, &durations, hum_bin, |p| p.hum as f64).into_iter()
+        .map(|(center, hours)| Bar::new(center, hours).width(hum_bin * 0.95).name(format!("{:.0}–{:.0}%", center - hum_bin / 2.0, center + hum_bin / 2.0))).collect();
+    let height = ui.available_height();
+    ui.columns(2, |columns| {
+        columns[0].label(egui::RichText::new(tr("Teplota – doba v hodinách")).size(14.0).strong());
+        Plot::new("temperature_histogram").height(height - 30.0).allow_drag(false).allow_scroll(false)
+            .x_axis_formatter(move |mark, _, _| format!("{:.1}{}", mark.value, unit.symbol())).y_axis_formatter(|mark, _, _| format!("{:.1} h", mark.value))
+            .show(&mut columns[0], |plot_ui| plot_ui.bar_chart(BarChart::new(temp_bars).color(egui::Color32::from_rgb(255, 100, 100))));
+        columns[1].label(egui::RichText::new(tr("Vlhkost – doba v hodinách")).size(14.0).strong());
+        Plot::new("humidity_histogram").height(height - 30.0).allow_drag(false).allow_scroll(false)
+            .x_axis_formatter(|mark, _, _| format!("{:.0}%", mark.value)).y_axis_formatter(|mark, _, _| format!("{:.1} h", mark.value))
+            .show(&mut columns[1], |plot_ui| plot_ui.bar_chart(BarChart::new(hum_bars).color(egui::Color32::from_rgb(100, 100, 255))));
+    });
+}
+
+// Průměrný průběh dne po hodinách s obálkou min–max
+pub fn draw_profile_view(app: &mut TempMonitorApp, ui: &mut egui::Ui) {
+    use egui_plot::{Line, Plot, PlotPoints, Points, Polygon};
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_source("profile_period").selected_text(app.profile_period.label()).show_ui(ui, |ui| {
+            for period in Period::ALL { ui.selectable_value(&mut app.profile_period, period, period.label()); }
+        });
+        if !app.config.loads_older_logs() || app.config.history_memory_days > 0 { ui.label(egui::RichText::new(tr("Počítá se jen z historie načtené v paměti.")).color(egui::Color32::GRAY)); }
+    });
+    let points = points_in_period(&app.history, app.profile_period);
+    if points.is_empty() { ui.label(tr("Žádná data pro zvolené období.")); return; }
+    let days = points.iter().map(|p| p.timestamp.date_naive()).collect::<std::collections::HashSet<_>>().len();
+    ui.label(trf("Profil z {} měření ({} dní)", &[&points.len(), &days]));
+    let height = (ui.available_height() / 2.0 - 20.0).max(100.0);
+    for (title, id, unit, value, color) in [
+        ("Teplota", "profile_temp", temp_unit().symbol(), (|p: &HistoryPoint| temp_unit().convert(p.temp as f64)) as fn(&HistoryPoint) -> f64, egui::Color32::from_rgb(255, 100, 100)),
+        ("Vlhkost", "profile_hum", "%", (|p: &HistoryPoint| p.hum as f64) as fn(&HistoryPoint) -> f64, egui::Color32::from_rgb(100, 100, 255)),
+    ] {
+        let profile = hourly_profile(&points, value);
+        // Hodnota hodiny se kreslí doprostřed intervalu (např. 14:00–15:00 → 14:30)
+        let mean: Vec<[f64; 2]> = profile.iter().map(|h| [h.hour as f64 + 0.5, h.mean]).collect();
+        ui.label(egui::RichText::new(tr(title)).size(14.0).strong());
+        Plot::new(id).height(height).include_x(0.0).include_x(24.0)
+            .link_axis(egui::Id::new("profile_plots"), true, false)
+            .x_axis_formatter(|mark, _, _| format!("{:02}:00", mark.value.floor() as i64))
+            .y_axis_formatter(move |mark, _, _| format!("{:.1}{}", mark.value, unit))
+            .label_formatter(move |_, value| format!("{:02}:00 – {:.1}{}", value.x.floor() as i64, value.y, unit))
+            .show(ui, |plot_ui| {
+                // Obálka po hodinových obdélnících (egui vyplňuje jen konvexní polygony)
+                for h in &profile {
+                    let (x0, x1) = (h.hour as f64, h.hour as f64 + 1.0);
+                    plot_ui.polygon(Polygon::new(PlotPoints::new(vec![[x0, h.min], [x1, h.min], [x1, h.max], [x0, h.max]])).fill_color(color.gamma_multiply(0.15)).stroke(egui::Stroke::new(1.0, color.gamma_multiply(0.4))));
+                }
+                plot_ui.line(Line::new(PlotPoints::new(mean.clone())).color(color).width(2.0).name(tr("Průměr")));
+                plot_ui.points(Points::new(PlotPoints::new(mean)).color(color).radius(3.0));
+            });
+    }
+}
+
+// Bodový graf vlhkost × teplota, body obarvené podle denní hodiny
+pub fn draw_scatter_view(app: &mut TempMonitorApp, ui: &mut egui::Ui) {
+    use egui_plot::{Legend, Plot, PlotPoints, Points, Polygon};
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_source("scatter_period").selected_text(app.scatter_period.label()).show_ui(ui, |ui| {
+            for period in Period::ALL { ui.selectable_value(&mut app.scatter_period, period, period.label()); }
+        });
+        ui.label(egui::RichText::new(tr("Barva bodu = hodina dne (modrá = noc, žlutá = poledne)")).color(egui::Color32::GRAY));
+    });
+    let points = points_in_period(&app.history, app.scatter_period);
+    let mut by_hour: Vec<Vec<[f64; 2]>> = vec![Vec::new(); 24];
+    let unit = temp_unit();
+    for p in &points { by_hour[p.timestamp.hour() as usize].push([unit.convert(p.temp as f64), p.hum as f64]); }
+    let (temp_min, temp_max) = by_hour.iter().flatten().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| (lo.min(p[0]), hi.max(p[0])));
+    Plot::new("scatter_plot").legend(Legend::default()).height(ui.available_height())
+        .x_axis_formatter(move |mark, _, _| format!("{:.1}{}", mark.value, unit.symbol())).y_axis_formatter(|mark, _, _| format!("{:.0}%", mark.value))
+        .label_formatter(move |_, value| format!("{}: {:.1}{}\n{}: {:.0}%", tr("Teplota"), value.x, unit.symbol(), tr("Vlhkost"), value.y))
+        .show(ui, |plot_ui| {
+            if temp_min.is_finite() {
+                let risk = vec![[temp_min - 1.0, CONDENSATION_RISK_HUM], [temp_max + 1.0, CONDENSATION_RISK_HUM], [temp_max + 1.0, 100.0], [temp_min - 1.0, 100.0]];
+                plot_ui.polygon(Polygon::new(PlotPoints::new(risk)).fill_color(egui::Color32::from_rgba_unmultiplied(220, 60, 60, 30))
+                    .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(220, 60, 60))).name(trf("Riziko kondenzace (≥ {} %)", &[&CONDENSATION_RISK_HUM])));
+            }
+            for (hour, series) in by_hour.into_iter().enumerate().filter(|(_, s)| !s.is_empty()) {
+                plot_ui.points(Points::new(PlotPoints::new(series)).radius(2.5).color(hour_color(hour as u32)).name(format!("{:02}:00", hour)));
+            }
+        });
+}
+
+// Cyklická barva podle hodiny: půlnoc tmavě modrá, poledne žlutá
+fn hour_color(hour: u32) -> egui::Color32 {
+    let t = (hour as f32 / 24.0 * std::f32::consts::TAU).cos() * -0.5 + 0.5;
+    egui::Color32::from(egui::ecolor::Hsva::new(0.66 - 0.5 * t, 0.8, 0.5 + 0.5 * t, 1.0))
+}
@@ -0,0 +1,96 @@
+// --- Kiosk a mini widget ---
+// Zjednodušená okna jen s aktuálními hodnotami: celá obrazovka, nebo malé okno navrchu.
+use crate::TempMonitorApp;
+use crate::views::plots::draw_simple_history_plot;
+use chrono::Local;
+use eframe::egui;
+use log::info;
+use temp_monitor::i18n::{tr, trf};
+use temp_monitor::storage::HistoryPoint;
+use temp_monitor::units::{format_temp, temp_unit};
+
+// Velikost okna v režimu mini widgetu
+const MINI_WIDGET_SIZE: egui::Vec2 = egui::vec2(240.0, 90.0);
+// Délka grafu, který je v kioskovém režimu vidět
+const KIOSK_CHART_HOURS: i64 = 6;
+
+impl TempMonitorApp {
+    // Malé okno bez rámečku nad ostatními okny; plná velikost se zapamatuje pro návrat
+    pub fn set_mini_mode(&mut self, ctx: &egui::Context, mini: bool) {
+        if mini {
+            self.full_size = ctx.input(|i| i.viewport().inner_rect).map(|rect| rect.size());
+            ctx.send_viewport_cmd(egui::ViewportCommand::Decorations(false));
+            ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(MINI_WIDGET_SIZE));
+            ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(egui::WindowLevel::AlwaysOnTop));
+        } else {
+            let level = if self.config.always_on_top { egui::WindowLevel::AlwaysOnTop } else { egui::WindowLevel::Normal };
+            ctx.send_viewport_cmd(egui::ViewportCommand::Decorations(true));
+            ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(self.full_size.unwrap_or(egui::vec2(850.0, 450.0))));
+            ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(level));
+        }
+        info!("Režim mini widgetu: {}", mini);
+        self.mini_mode = mini;
+    }
+
+    pub fn set_kiosk(&mut self, ctx: &egui::Context, kiosk: bool) {
+        if kiosk && self.mini_mode { self.set_mini_mode(ctx, false); }
+        ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(kiosk));
+        info!("Kioskový režim: {}", kiosk);
+        self.kiosk = kiosk;
+    }
+
+    // Kiosk: obří aktuální hodnoty hlavního senzoru, řádek s dalšími senzory a graf posledních hodin
+    pub fn draw_kiosk(&mut self, ctx: &egui::Context) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            let big = (ui.available_height() * 0.22).clamp(48.0, 220.0);
+            ui.columns(2, |columns| {
+                match self.history.back() {
+                    Some(point) => {
+                        let color = if point.temp > self.config.temp_warn_high { egui::Color32::GOLD } else if point.temp < self.config.temp_warn_low { egui::Color32::from_rgb(120, 180, 255) } else { egui::Color32::from_rgb(255, 100, 100) };
+                        columns[0].vertical_centered(|ui| ui.label(egui::RichText::new(format_temp(point.temp as f64, 1)).size(big).color(color)));
+                        columns[1].vertical_centered(|ui| ui.label(egui::RichText::new(format!("{}%", point.hum)).size(big).color(self.config.hum_color(point.hum))));
+                    }
+                    None => { columns[0].vertical_centered(|ui| ui.label(egui::RichText::new(tr("N/A")).size(big))); }
+                }
+            });
+            ui.vertical_centered(|ui| {
+                let measured = self.history.back().map_or(String::new(), |p| trf("Měřeno {}", &[&p.timestamp.format("%H:%M:%S")]));
+                ui.label(egui::RichText::new(measured).size(big * 0.2).color(egui::Color32::GRAY));
+            });
+            let others: Vec<(String, HistoryPoint)> = self.config.all_devices().into_iter().skip(1)
+                .filter_map(|(name, mac)| self.device_history.get(&mac).and_then(|p| p.back()).map(|p| (name, p.clone()))).collect();
+            if !others.is_empty() {
+                ui.horizontal_wrapped(|ui| {
+                    for (name, point) in others {
+                        ui.label(egui::RichText::new(format!("{}: {}  {}%", name, format_temp(point.temp as f64, 1), point.hum)).size(big * 0.25));
+                        ui.add_space(30.0);
+                    }
+                });
+            }
+            ui.separator();
+            let cutoff = Local::now() - chrono::Duration::hours(KIOSK_CHART_HOURS);
+            let points: Vec<HistoryPoint> = self.recent_points.iter().filter(|p| p.timestamp >= cutoff).cloned().collect();
+            let height = ui.available_height() - 10.0;
+            draw_simple_history_plot(ui, "kiosk_temp", &points, |p| temp_unit().convert(p.temp as f64), egui::Color32::from_rgb(255, 100, 100), temp_unit().symbol(), height);
+        }).response.on_hover_text(tr("F11 nebo Esc ukončí kioskový režim"));
+    }
+
+    pub fn draw_mini_widget(&mut self, ctx: &egui::Context) {
+        let response = egui::CentralPanel::default().show(ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                match &self.last_data_point {
+                    Some(data) => {
+                        let color = if data.temp > self.config.temp_warn_high { egui::Color32::GOLD } else if data.temp < self.config.temp_warn_low { egui::Color32::from_rgb(120, 180, 255) } else { egui::Color32::from_rgb(255, 100, 100) };
+                        // Popisky nesmí zachytávat myš (výběr textu), jinak by nešlo okno táhnout
+                        ui.add(egui::Label::new(egui::RichText::new(format_temp(data.temp as f64, 1)).size(30.0).color(color)).selectable(false));
+                        ui.add(egui::Label::new(egui::RichText::new(format!("{}%", data.hum)).size(20.0).color(self.config.hum_color(data.hum))).selectable(false));
+                    }
+                    None => { ui.add(egui::Label::new(egui::RichText::new(tr("N/A")).size(30.0)).selectable(false)); }
+                }
+            });
+        }).response.interact(egui::Sense::click_and_drag());
+        if response.double_clicked() { self.set_mini_mode(ctx, false); }
+        else if response.drag_started_by(egui::PointerButton::Primary) { ctx.send_viewport_cmd(egui::ViewportCommand::StartDrag); }
+        response.on_hover_text(tr("Dvojklikem obnovíte plné okno, tažením přesunete"));
+    }
+}
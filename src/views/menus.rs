@@ -0,0 +1,145 @@
+// --- Hlavní nabídka, profily a poznámky ---
+use crate::{AggregationMode, SettingsCategory, Severity, TempMonitorApp, TimeRange, View};
+use crate::report::DAILY_SUMMARY_FILE;
+use chrono::Local;
+use eframe::egui;
+use std::path::Path;
+use log::{info, error};
+use temp_monitor::config::{export_settings, ConfigProfile, SETTINGS_EXPORT_FILE};
+use temp_monitor::i18n::{tr, trf};
+use temp_monitor::storage::{write_annotations, Annotation};
+use temp_monitor::units::temp_unit;
+
+impl TempMonitorApp {
+    // Hlavní nabídka: Soubor, profily, přiblížení a rozsah grafů, přepínání pohledů a agregace
+    pub fn draw_menu_bar(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        ui.menu_button(tr("Soubor"), |ui| {
+            if ui.button(tr("Nastavení")).clicked() { self.settings_open = true; ui.close_menu(); }
+            if ui.button(tr("Vytvořit report…")).clicked() { self.report_open = true; ui.close_menu(); }
+            if ui.button(tr("Exportovat denní souhrn")).clicked() {
+                let (severity, message) = match self.export_daily_summary() {
+                    Ok(days) => (Severity::Success, trf("Denní souhrn ({} dní) uložen do '{}'.", &[&days, &DAILY_SUMMARY_FILE])),
+                    Err(e) => { error!("Export denního souhrnu selhal: {}", e); (Severity::Error, trf("Export se nezdařil: {}", &[&e])) }
+                };
+                self.toast(severity, message);
+                ui.close_menu();
+            }
+            ui.separator();
+            if ui.button(tr("Exportovat nastavení")).on_hover_text(tr("Meze, senzory, upozornění a vzhled do souboru pro další počítač")).clicked() {
+                match export_settings(&self.config, Path::new(SETTINGS_EXPORT_FILE)) {
+                    Ok(()) => { info!("Nastavení exportováno do '{}'.", SETTINGS_EXPORT_FILE); self.toast(Severity::Success, trf("Nastavení uloženo do '{}'.", &[&SETTINGS_EXPORT_FILE])); },
+                    Err(e) => { error!("Export nastavení selhal: {}", e); self.toast(Severity::Error, trf("Export se nezdařil: {}", &[&e])); }
+                }
+                ui.close_menu();
+            }
+            if ui.button(tr("Importovat nastavení")).on_hover_text(trf("Načte '{}' z pracovní složky; jiný export stačí přetáhnout do okna", &[&SETTINGS_EXPORT_FILE])).clicked() {
+                self.import_settings_file(Path::new(SETTINGS_EXPORT_FILE));
+                ui.close_menu();
+            }
+            ui.separator();
+            if ui.button(tr("Protokol aplikace")).clicked() { self.log_window_open = true; ui.close_menu(); }
+            if ui.button(tr("O aplikaci")).clicked() { self.about_open = true; ui.close_menu(); }
+            if ui.button(tr("Ukončit")).clicked() {
+                #[cfg(windows)]
+                { self.quitting = true; }
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            }
+        });
+        if !self.config.profiles.is_empty() {
+            let active = if self.config.active_profile.is_empty() { tr("Profil").to_string() } else { self.config.active_profile.clone() };
+            let modified = if self.config.profile_modified() { "*" } else { "" };
+            ui.menu_button(format!("🗂 {}{}", active, modified), |ui| self.draw_profiles_menu(ui));
+        }
+        ui.separator();
+        if ui.button("➖").on_hover_text(tr("Oddálit")).clicked() { self.zoom_factor = 0.7; }
+        if ui.button("➕").on_hover_text(tr("Přiblížit")).clicked() { self.zoom_factor = 1.25; }
+        if ui.button("⛶").on_hover_text(tr("Vycentrovat graf")).clicked() { self.reset_plot = true; }
+        for range in TimeRange::ALL {
+            if ui.small_button(range.label()).on_hover_text(tr("Zobrazit v grafech tento úsek do posledního měření")).clicked() { self.pending_range = Some(range); }
+        }
+        if ui.toggle_value(&mut self.config.always_on_top, "📌").on_hover_text(tr("Vždy navrchu")).changed() {
+            let level = if self.config.always_on_top { egui::WindowLevel::AlwaysOnTop } else { egui::WindowLevel::Normal };
+            ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(level));
+            self.config_changed = true;
+        }
+        if ui.button("🗕").on_hover_text(tr("Mini widget (dvojklikem zpět)")).clicked() { self.set_mini_mode(ctx, true); }
+        if ui.button("⛶ Kiosk").on_hover_text(tr("Celá obrazovka jen s aktuálními hodnotami (F11)")).clicked() { self.set_kiosk(ctx, true); }
+        ui.menu_button(tr("📝 Poznámky"), |ui| self.draw_annotations_menu(ui));
+        if ui.button("📷").on_hover_text(tr("Snímek okna do schránky a do souboru (F12)")).clicked() { self.request_window_screenshot(ctx); }
+        ui.separator();
+        for view in View::ALL { ui.selectable_value(&mut self.view, view, view.label()); }
+        ui.separator();
+        ui.toggle_value(&mut self.stats_open, tr("📋 Statistiky"));
+        ui.toggle_value(&mut self.combined_plot, tr("🗠 Společný graf")).on_hover_text(tr("Teplota i vlhkost v jednom grafu s osou vlevo a vpravo"));
+        ui.toggle_value(&mut self.show_extremes, tr("⇕ Min/max")).on_hover_text(tr("Vyznačit v grafech nejnižší a nejvyšší hodnotu viditelného úseku"));
+        ui.toggle_value(&mut self.show_rate_plot, tr("📐 Rychlost změny")).on_hover_text(trf("Zobrazit graf změny teploty ({}/h) a vlhkosti (%/h)", &[&temp_unit().symbol()]));
+        ui.separator();
+        let previous_mode = self.aggregation;
+        egui::ComboBox::from_id_source("aggregation_mode").selected_text(self.aggregation.label()).show_ui(ui, |ui| {
+            for mode in AggregationMode::ALL { ui.selectable_value(&mut self.aggregation, mode, mode.label()); }
+        });
+        if self.aggregation != previous_mode { info!("Přepínám zobrazení grafů na: {}", self.aggregation.label()); self.reset_plot = true; }
+    }
+
+    // Přepínání profilů; hvězdička v názvu menu značí, že se meze od přepnutí změnily
+    fn draw_profiles_menu(&mut self, ui: &mut egui::Ui) {
+        let names: Vec<String> = self.config.profiles.iter().map(|p| p.name.clone()).collect();
+        for name in names {
+            if ui.selectable_label(self.config.active_profile == name, &name).clicked() { self.switch_profile(&name); ui.close_menu(); }
+        }
+        if self.config.profile_modified() {
+            ui.separator();
+            if ui.button(trf("Uložit změny do profilu {}", &[&self.config.active_profile])).clicked() {
+                let mut config = self.config.clone();
+                let profile = ConfigProfile::capture(&config.active_profile, &config);
+                if let Some(existing) = config.profiles.iter_mut().find(|p| p.name == profile.name) { *existing = profile; }
+                self.settings_draft = None;
+                self.apply_settings(config);
+                ui.close_menu();
+            }
+        }
+        ui.separator();
+        if ui.button(tr("Upravit profily…")).clicked() { self.settings_open = true; self.settings_category = SettingsCategory::Device; self.settings_search.clear(); ui.close_menu(); }
+    }
+
+    // Přepnutí z menu nebo ikony; rozepsané nastavení se zahodí, aby po potvrzení nepřepsalo hodnoty profilu
+    pub fn switch_profile(&mut self, name: &str) {
+        let mut config = self.config.clone();
+        if !config.switch_profile(name) { return; }
+        info!("Přepnuto na profil '{}'.", name);
+        self.settings_draft = None;
+        self.apply_settings(config);
+        self.toast(Severity::Info, trf("Zapnut profil {}.", &[&name]));
+    }
+
+    // Přidání poznámky k aktuálnímu času a seznam posledních poznámek s možností smazání
+    fn draw_annotations_menu(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let edit = ui.add(egui::TextEdit::singleline(&mut self.annotation_draft).hint_text(tr("např. otevřené okno")).desired_width(220.0));
+            let submitted = edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if (ui.button(tr("Přidat")).clicked() || submitted) && !self.annotation_draft.trim().is_empty() {
+                self.annotations.push(Annotation { timestamp: Local::now(), text: self.annotation_draft.trim().to_owned() });
+                self.annotation_draft.clear();
+                self.save_annotations();
+            }
+        });
+        if self.annotations.is_empty() { return; }
+        ui.separator();
+        let mut remove = None;
+        for (i, annotation) in self.annotations.iter().enumerate().rev().take(10) {
+            ui.horizontal(|ui| {
+                if ui.small_button("🗑").on_hover_text(tr("Smazat poznámku")).clicked() { remove = Some(i); }
+                ui.label(egui::RichText::new(annotation.timestamp.format("%d.%m. %H:%M").to_string()).color(egui::Color32::GRAY));
+                ui.label(&annotation.text);
+            });
+        }
+        if let Some(i) = remove { self.annotations.remove(i); self.save_annotations(); }
+    }
+
+    pub fn save_annotations(&mut self) {
+        if let Err(e) = write_annotations(&self.annotations) {
+            error!("Uložení poznámek selhalo: {}", e);
+            self.toast(Severity::Error, trf("Poznámky se nepodařilo uložit: {}", &[&e]));
+        }
+    }
+}
@@ -0,0 +1,54 @@
+// --- Vykreslovací funkce ---
+// Pohledy hlavního okna; stav drží TempMonitorApp v main.rs, moduly přidávají jeho vykreslovací metody.
+pub mod calendar;
+pub mod compare;
+pub mod devices;
+pub mod dialogs;
+pub mod distribution;
+pub mod kiosk;
+pub mod menus;
+pub mod plots;
+pub mod readout;
+pub mod settings;
+pub mod stats;
+pub mod status;
+pub mod table;
+
+use eframe::egui;
+
+// 0.0 → modrá, ~0.33 → zelená, ~0.66 → oranžová, 1.0 → červená
+pub fn value_to_color(value: f64, min: f64, max: f64) -> egui::Color32 {
+    let t = ((value - min) / (max - min)).clamp(0.0, 1.0);
+
+    if t < 0.33 {
+        // modrá → zelená
+        egui::Color32::from_rgb(0, (t * 3.0 * 255.0) as u8, 255)
+    } else if t < 0.66 {
+        // zelená → oranžová
+        egui::Color32::from_rgb(0, 255, 255 - ((t - 0.33) * 3.0 * 255.0) as u8)
+    } else {
+        // oranžová → červená
+        egui::Color32::from_rgb(255, 255 - ((t - 0.66) * 3.0 * 255.0) as u8, 0)
+    }
+}
+
+pub fn humidity_to_color(value: f64, min: f64, max: f64) -> egui::Color32 {
+    let t = ((value - min) / (max - min)).clamp(0.0, 1.0);
+
+    if t < 0.33 {
+        // červená → oranžová
+        egui::Color32::from_rgb(255, (t * 3.0 * 255.0) as u8, 0)
+    } else if t < 0.66 {
+        // oranžová → zelená
+        egui::Color32::from_rgb(255 - ((t - 0.33) * 3.0 * 255.0) as u8, 255, 0)
+    } else {
+        // zelená → modrá
+        egui::Color32::from_rgb(0, 255 - ((t - 0.66) * 3.0 * 255.0) as u8, 255)
+    }
+}
+
+// Doba jako "12 min" nebo "3 h 5 min"
+pub fn format_duration_short(secs: f64) -> String {
+    let minutes = (secs / 60.0).round() as u64;
+    if minutes < 60 { format!("{} min", minutes) } else { format!("{} h {} min", minutes / 60, minutes % 60) }
+}
@@ -0,0 +1,685 @@
+// --- Grafy ---
+// Časové grafy teploty, vlhkosti a rychlosti změny včetně agregace, ředění bodů, značek a exportu do PNG.
+use crate::{AggregationMode, PlotExport, RangeStats, Severity, TempMonitorApp, TimeRange};
+use crate::stats::{linear_trend, min_max, Anomaly, Trend};
+use crate::views::{humidity_to_color, value_to_color};
+use crate::views::table::readings_tsv;
+use chrono::{DateTime, Local};
+use eframe::egui;
+use egui_extras::{StripBuilder, Size};
+use std::collections::{HashMap, VecDeque};
+use log::{info, error};
+use temp_monitor::i18n::{tr, trf};
+use temp_monitor::storage::{export_readings, Annotation, HistoryPoint};
+use temp_monitor::units::{format_temp, temp_unit};
+
+// Okno pro výpočet rychlosti změny (°C/h, %/h)
+const RATE_WINDOW_SECS: i64 = 10 * 60;
+// Nad tento počet dní ve viditelném úseku se čáry půlnocí nekreslí (splynuly by v šedou plochu)
+const MAX_DAY_LINES: usize = 92;
+
+struct AggregatedBucket { start: DateTime<Local>, width_secs: f64, min: f64, max: f64, mean: f64 }
+
+impl TempMonitorApp {
+    // Body grafu: starší dny z disku (jen když graf ukazuje období před historií v paměti), pak historie v paměti
+    fn plot_series(&mut self, ctx: &egui::Context, value: impl Fn(&HistoryPoint) -> f64) -> Vec<[f64; 2]> {
+        let point = |p: &HistoryPoint| [p.timestamp.timestamp() as f64, value(p)];
+        let mut series = Vec::new();
+        if let Some((from, to)) = self.paged_range() {
+            let (paged, pending) = self.history_pager.points(from, to);
+            series.extend(paged.into_iter().map(point));
+            if pending { ctx.request_repaint(); }
+        }
+        series.extend(self.history.iter().map(point));
+        series
+    }
+
+    fn paged_range(&self) -> Option<(i64, i64)> {
+        if !self.config.loads_older_logs() || self.config.history_memory_days == 0 || self.imported.is_some() { return None; }
+        let (from, to) = self.plot_view?;
+        let first = self.history.front().map_or(Local::now().timestamp(), |p| p.timestamp.timestamp());
+        (from < first as f64).then(|| (from as i64, (to as i64 + 1).min(first)))
+    }
+
+    pub fn draw_plots_view(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let main_share = if self.show_rate_plot { 0.38 } else { 0.5 };
+        let mut builder = StripBuilder::new(ui);
+        builder = if self.combined_plot { builder.size(Size::relative(main_share * 2.0)) } else { builder.size(Size::relative(main_share)).size(Size::relative(main_share)) };
+        if self.show_rate_plot { builder = builder.size(Size::remainder()); }
+        builder.vertical(|mut strip| {
+            if self.combined_plot {
+                strip.cell(|ui| { self.plot_title(ui, tr("Teplota a vlhkost"), "teplota_vlhkost"); draw_combined_graph(self, ui); self.track_plot_export(ui, "teplota_vlhkost"); });
+            } else {
+                strip.cell(|ui| { self.plot_title(ui, tr("Teplota"), "teplota"); draw_temperature_graph(self, ui, ctx); self.track_plot_export(ui, "teplota"); });
+                strip.cell(|ui| { self.plot_title(ui, tr("Vlhkost"), "vlhkost"); draw_humidity_graph(self, ui, ctx); self.track_plot_export(ui, "vlhkost"); });
+            }
+            if self.show_rate_plot {
+                strip.cell(|ui| { self.plot_title(ui, tr("Rychlost změny"), "rychlost_zmeny"); draw_rate_graph(self, ui); self.track_plot_export(ui, "rychlost_zmeny"); });
+            }
+        });
+    }
+
+    // Nadpis grafu; při exportu nese i čas pořízení
+    fn plot_title(&self, ui: &mut egui::Ui, title: &str, name: &str) {
+        let text = match &self.plot_export {
+            Some(export) if export.name == name => format!("{} – {}", title, export.taken_at.format("%d.%m.%Y %H:%M")),
+            _ => title.to_owned(),
+        };
+        ui.label(egui::RichText::new(text).size(14.0).strong());
+    }
+
+    // Zapamatuje si oblast exportovaného grafu (nadpis, osy i křivky) a teprve pak požádá o snímek okna
+    fn track_plot_export(&mut self, ui: &egui::Ui, name: &str) {
+        let Some(export) = self.plot_export.as_mut().filter(|e| e.name == name) else { return; };
+        export.rect = Some(ui.min_rect());
+        if !export.requested { ui.ctx().send_viewport_cmd(egui::ViewportCommand::Screenshot); export.requested = true; }
+    }
+
+    pub fn save_plot_export(&mut self, screenshot: &egui::ColorImage, pixels_per_point: f32) {
+        let Some(PlotExport { name, taken_at, rect: Some(rect), .. }) = self.plot_export.take() else { return; };
+        let image = screenshot.region(&rect, Some(pixels_per_point));
+        let filename = format!("graf_{}_{}.png", name, taken_at.format("%Y-%m-%d_%H%M%S"));
+        let (severity, message) = match save_png(&filename, &image) {
+            Ok(()) => { info!("Graf exportován do '{}'.", filename); (Severity::Success, trf("Graf uložen do '{}'.", &[&filename])) }
+            Err(e) => { error!("Export grafu selhal: {}", e); (Severity::Error, trf("Export se nezdařil: {}", &[&e])) }
+        };
+        self.toast(severity, message);
+    }
+}
+
+fn draw_temperature_graph(app: &mut TempMonitorApp, ui: &mut egui::Ui, ctx: &egui::Context) {
+    use egui_plot::{GridMark, Line, Plot, Points, PlotPoints};
+    let unit = temp_unit();
+    let temp_data_points = app.plot_series(ctx, |p| unit.convert(p.temp as f64));
+
+    let mut plot = Plot::new("temperature_plot").height(ui.available_height()).width(ui.available_width())
+        .link_axis(egui::Id::new("linked_plots"), true, false).show_background(false).allow_drag(!ui.input(|i| i.modifiers.shift))
+        .auto_bounds(egui::Vec2b::new(true, true)).show_x(false).legend(series_legend(&app.hidden_series, "temperature_plot"))
+        .link_cursor(egui::Id::new("linked_plots"), true, false).label_formatter(combined_readout(&app.history))
+        .x_axis_formatter(|mark: GridMark, _, range| format_time_axis(mark, range))
+        .y_axis_formatter(move |mark: GridMark, _, _| format!("{:.1}{}", mark.value, unit.symbol()));
+    plot = wheel_navigation(plot, ui);
+    if app.reset_plot { plot = plot.reset(); }
+    if let (Some(min), Some(max)) = (app.history.iter().map(|p| p.temp).min_by(|a, b| a.partial_cmp(b).unwrap()), app.history.iter().map(|p| p.temp).max_by(|a, b| a.partial_cmp(b).unwrap())) {
+        if (max - min).abs() < f32::EPSILON { plot = plot.include_y(unit.convert(min as f64) - 0.5).include_y(unit.convert(max as f64) + 0.5); }
+    }
+
+    let response = plot.show(ui, |plot_ui| {
+        draw_day_boundaries(plot_ui);
+        if app.config.show_comfort_band {
+            draw_comfort_band(plot_ui, &temp_data_points, unit.convert(app.config.comfort_temp_min as f64), unit.convert(app.config.comfort_temp_max as f64));
+        }
+        if app.aggregation != AggregationMode::Raw {
+            let buckets = aggregate_history(&app.history, app.aggregation, |p| unit.convert(p.temp as f64));
+            draw_aggregated_series(plot_ui, &buckets, app.config.temp_style.line_color(), unit.symbol(), tr("Teplota"));
+        } else {
+            // křivka (zředěná na šířku grafu v pixelech, aby velká historie nebrzdila vykreslování)
+            let shown_points = points_for_view(&temp_data_points, plot_ui);
+            let (style, scale) = (&app.config.temp_style, app.config.plot_scale());
+            plot_ui.line(Line::new(PlotPoints::new(shown_points.clone())).color(style.line_color()).width(2.0 * scale).name(tr("Teplota")));
+
+            // body v nastavené barvě, nebo podle hodnoty (0 až 40 °C, nezávisle na zobrazené jednotce)
+            if let Some(shape) = style.marker.shape() {
+                for [x, y] in shown_points {
+                    let color = style.point_color().unwrap_or_else(|| value_to_color(unit.to_celsius(y), 0.0, 40.0));
+                    let pp = PlotPoints::new(vec![[x, y]]);
+                    plot_ui.points(
+                        Points::new(pp)
+                            .radius(3.0 * scale)
+                            .shape(shape)
+                            .color(color)
+                            .highlight(true)
+                            .name(tr("Teplota"))
+                    );
+                }
+            }
+        }
+
+        draw_threshold_lines(plot_ui, unit.convert(app.config.temp_warn_low as f64), unit.convert(app.config.temp_warn_high as f64));
+        if app.show_extremes { draw_extreme_markers(plot_ui, &temp_data_points, app.config.temp_style.line_color(), |y| format!("{:.1}{}", y, unit.symbol())); }
+        draw_latest_label(plot_ui, &temp_data_points, app.config.temp_style.line_color(), |y| format!("{:.1}{}", y, unit.symbol()));
+        if let Some(selection) = app.table_selection().filter(|_| app.table_highlight) { draw_table_selection(plot_ui, &app.history, selection, |p| unit.convert(p.temp as f64)); }
+        if app.config.anomaly_detection { draw_anomaly_markers(plot_ui, &app.anomalies, true); }
+        draw_annotation_markers(plot_ui, &app.annotations);
+        if app.config.forecast_enabled {
+            if let Some(trend) = linear_trend(&app.history, app.config.forecast_window_minutes as f64 * 60.0, |p| unit.convert(p.temp as f64)) {
+                draw_forecast(plot_ui, &trend, app.config.forecast_minutes as f64 * 60.0, app.config.temp_style.line_color());
+            }
+        }
+
+        if app.zoom_factor != 1.0 { plot_ui.zoom_bounds(egui::vec2(app.zoom_factor, app.zoom_factor), plot_ui.plot_bounds().center()); }
+        if let Some(range) = app.pending_range { apply_time_range(plot_ui, range, &temp_data_points); }
+        track_range_selection(app, plot_ui);
+        
+        if plot_ui.response().clicked() {
+            if let Some(pos) = plot_ui.pointer_coordinate() {
+                let closest_point = app.history.iter().min_by_key(|p| (p.timestamp.timestamp() as f64 - pos.x).abs() as u64);
+                if let Some(point) = closest_point {
+                    if (unit.convert(point.temp as f64) - pos.y).abs() < unit.delta(1.0) {
+                        let text_to_copy = format!("{}: {}, {}: {}", tr("Čas"), point.timestamp.format("%H:%M:%S"), tr("Teplota"), format_temp(point.temp as f64, 1));
+                        ctx.output_mut(|o| o.copied_text = text_to_copy.clone());
+                        app.toast(Severity::Success, tr("Zkopírováno do schránky!"));
+                        info!("Zkopírováno do schránky: {}", text_to_copy);
+                    }
+                }
+            }
+        }
+    });
+    remember_hidden_series(app, "temperature_plot", &response);
+    // Graf vlhkosti má s teplotou propojenou časovou osu, stačí tedy rozsah tohoto grafu
+    app.plot_view = Some((response.transform.bounds().min()[0], response.transform.bounds().max()[0]));
+    plot_context_menu(app, &response, "teplota");
+}
+
+fn draw_humidity_graph(app: &mut TempMonitorApp, ui: &mut egui::Ui, ctx: &egui::Context) {
+    use egui_plot::{GridMark, Line, Plot, Points, PlotPoints};
+    let hum_data_points = app.plot_series(ctx, |p| p.hum as f64);
+
+    let mut plot = Plot::new("humidity_plot").height(ui.available_height()).width(ui.available_width())
+        .link_axis(egui::Id::new("linked_plots"), true, false).show_background(false).allow_drag(!ui.input(|i| i.modifiers.shift))
+        .auto_bounds(egui::Vec2b::new(true, true)).show_axes([true, true]).legend(series_legend(&app.hidden_series, "humidity_plot"))
+        .link_cursor(egui::Id::new("linked_plots"), true, false).label_formatter(combined_readout(&app.history))
+        .x_axis_formatter(|mark: GridMark, _, range| format_time_axis(mark, range))
+        .y_axis_formatter(|mark: GridMark, _, _| format!("{:.0}%", mark.value));
+    plot = wheel_navigation(plot, ui);
+    if app.reset_plot { plot = plot.reset(); }
+    if let (Some(min), Some(max)) = (app.history.iter().map(|p| p.hum).min(), app.history.iter().map(|p| p.hum).max()) {
+        if min == max { plot = plot.include_y(min as f64 - 1.0).include_y(max as f64 + 1.0); }
+    }
+    
+    let response = plot.show(ui, |plot_ui| {
+        draw_day_boundaries(plot_ui);
+        if app.config.show_comfort_band {
+            draw_comfort_band(plot_ui, &hum_data_points, app.config.comfort_hum_min as f64, app.config.comfort_hum_max as f64);
+        }
+        if app.aggregation != AggregationMode::Raw {
+            let buckets = aggregate_history(&app.history, app.aggregation, |p| p.hum as f64);
+            draw_aggregated_series(plot_ui, &buckets, app.config.hum_style.line_color(), "%", tr("Vlhkost"));
+        } else {
+            // křivka (zředěná na šířku grafu v pixelech)
+            let shown_points = points_for_view(&hum_data_points, plot_ui);
+            let (style, scale) = (&app.config.hum_style, app.config.plot_scale());
+            plot_ui.line(Line::new(PlotPoints::new(shown_points.clone())).color(style.line_color()).width(2.0 * scale).name(tr("Vlhkost")));
+
+            // body v nastavené barvě, nebo podle hodnoty (0 až 100 %)
+            if let Some(shape) = style.marker.shape() {
+                for [x, y] in shown_points {
+                    let color = style.point_color().unwrap_or_else(|| humidity_to_color(y, 0.0, 100.0));
+                    let pp = PlotPoints::new(vec![[x, y]]);
+                    plot_ui.points(
+                        Points::new(pp)
+                            .radius(3.0 * scale)
+                            .shape(shape)
+                            .color(color)
+                            .highlight(true)
+                            .name(tr("Vlhkost"))
+                    );
+                }
+            }
+        }
+
+        draw_threshold_lines(plot_ui, app.config.hum_warn_low as f64, app.config.hum_warn_high as f64);
+        if app.show_extremes { draw_extreme_markers(plot_ui, &hum_data_points, app.config.hum_style.line_color(), |y| format!("{:.0}%", y)); }
+        draw_latest_label(plot_ui, &hum_data_points, app.config.hum_style.line_color(), |y| format!("{:.0}%", y));
+        if let Some(selection) = app.table_selection().filter(|_| app.table_highlight) { draw_table_selection(plot_ui, &app.history, selection, |p| p.hum as f64); }
+        if app.config.anomaly_detection { draw_anomaly_markers(plot_ui, &app.anomalies, false); }
+        draw_annotation_markers(plot_ui, &app.annotations);
+        if app.config.forecast_enabled {
+            if let Some(trend) = linear_trend(&app.history, app.config.forecast_window_minutes as f64 * 60.0, |p| p.hum as f64) {
+                draw_forecast(plot_ui, &trend, app.config.forecast_minutes as f64 * 60.0, app.config.hum_style.line_color());
+            }
+        }
+
+        if app.zoom_factor != 1.0 { plot_ui.zoom_bounds(egui::vec2(app.zoom_factor, app.zoom_factor), plot_ui.plot_bounds().center()); }
+        if let Some(range) = app.pending_range { apply_time_range(plot_ui, range, &hum_data_points); }
+        track_range_selection(app, plot_ui);
+        
+        if plot_ui.response().clicked() {
+            if let Some(pos) = plot_ui.pointer_coordinate() {
+                let closest_point = app.history.iter().min_by_key(|p| (p.timestamp.timestamp() as f64 - pos.x).abs() as u64);
+                if let Some(point) = closest_point {
+                    if (point.hum as f64 - pos.y).abs() < 2.0 {
+                        let text_to_copy = format!("{}: {}, {}: {}%", tr("Čas"), point.timestamp.format("%H:%M:%S"), tr("Vlhkost"), point.hum);
+                        ctx.output_mut(|o| o.copied_text = text_to_copy.clone());
+                        app.toast(Severity::Success, tr("Zkopírováno do schránky!"));
+                        info!("Zkopírováno do schránky: {}", text_to_copy);
+                    }
+                }
+            }
+        }
+    });
+    remember_hidden_series(app, "humidity_plot", &response);
+    plot_context_menu(app, &response, "vlhkost");
+}
+
+// Společný graf: vlhkost se přepočítá do souřadnic teploty tak, aby oba rozsahy vyplnily stejnou výšku,
+// pravá osa pak popisky převádí zpět na %
+fn draw_combined_graph(app: &mut TempMonitorApp, ui: &mut egui::Ui) {
+    use egui_plot::{AxisHints, HPlacement, Line, Plot, PlotPoints};
+    let unit = temp_unit();
+    let (t_lo, t_hi) = min_max(app.history.iter().map(|p| unit.convert(p.temp as f64))).unwrap_or((0.0, 1.0));
+    let (h_lo, h_hi) = min_max(app.history.iter().map(|p| p.hum as f64)).unwrap_or((0.0, 100.0));
+    let (t_lo, t_hi) = if t_hi - t_lo < 1.0 { (t_lo - 0.5, t_hi + 0.5) } else { (t_lo, t_hi) };
+    let (h_lo, h_hi) = if h_hi - h_lo < 2.0 { (h_lo - 1.0, h_hi + 1.0) } else { (h_lo, h_hi) };
+    let scale = (t_hi - t_lo) / (h_hi - h_lo);
+    let temp_data_points = app.plot_series(ui.ctx(), |p| unit.convert(p.temp as f64));
+    let hum_data_points = app.plot_series(ui.ctx(), |p| t_lo + (p.hum as f64 - h_lo) * scale);
+
+    let temp_axis = AxisHints::new_y().label(tr("Teplota")).formatter(move |mark, _, _| format!("{:.1}{}", mark.value, unit.symbol()));
+    let hum_axis = AxisHints::new_y().label(tr("Vlhkost")).placement(HPlacement::Right).formatter(move |mark, _, _| format!("{:.0}%", h_lo + (mark.value - t_lo) / scale));
+    let mut plot = Plot::new("combined_plot").height(ui.available_height()).width(ui.available_width()).legend(series_legend(&app.hidden_series, "combined_plot"))
+        .link_axis(egui::Id::new("linked_plots"), true, false).link_cursor(egui::Id::new("linked_plots"), true, false)
+        .show_background(false).allow_drag(!ui.input(|i| i.modifiers.shift)).auto_bounds(egui::Vec2b::new(true, true))
+        .custom_y_axes(vec![temp_axis, hum_axis]).label_formatter(combined_readout(&app.history))
+        .x_axis_formatter(|mark, _, range| format_time_axis(mark, range));
+    plot = wheel_navigation(plot, ui);
+    if app.reset_plot { plot = plot.reset(); }
+    let response = plot.show(ui, |plot_ui| {
+        draw_day_boundaries(plot_ui);
+        let temp_points = points_for_view(&temp_data_points, plot_ui);
+        let hum_points = points_for_view(&hum_data_points, plot_ui);
+        let width = 2.0 * app.config.plot_scale();
+        plot_ui.line(Line::new(PlotPoints::new(temp_points)).color(app.config.temp_style.line_color()).width(width).name(tr("Teplota")));
+        plot_ui.line(Line::new(PlotPoints::new(hum_points)).color(app.config.hum_style.line_color()).width(width).name(tr("Vlhkost")));
+        draw_threshold_lines(plot_ui, unit.convert(app.config.temp_warn_low as f64), unit.convert(app.config.temp_warn_high as f64));
+        if app.show_extremes {
+            draw_extreme_markers(plot_ui, &temp_data_points, app.config.temp_style.line_color(), |y| format!("{:.1}{}", y, unit.symbol()));
+            draw_extreme_markers(plot_ui, &hum_data_points, app.config.hum_style.line_color(), |y| format!("{:.0}%", h_lo + (y - t_lo) / scale));
+        }
+        draw_latest_label(plot_ui, &temp_data_points, app.config.temp_style.line_color(), |y| format!("{:.1}{}", y, unit.symbol()));
+        draw_latest_label(plot_ui, &hum_data_points, app.config.hum_style.line_color(), |y| format!("{:.0}%", h_lo + (y - t_lo) / scale));
+        draw_annotation_markers(plot_ui, &app.annotations);
+        if app.zoom_factor != 1.0 { plot_ui.zoom_bounds(egui::vec2(app.zoom_factor, app.zoom_factor), plot_ui.plot_bounds().center()); }
+        if let Some(range) = app.pending_range { apply_time_range(plot_ui, range, &[temp_data_points.as_slice(), hum_data_points.as_slice()].concat()); }
+        track_range_selection(app, plot_ui);
+    });
+    remember_hidden_series(app, "combined_plot", &response);
+    app.plot_view = Some((response.transform.bounds().min()[0], response.transform.bounds().max()[0]));
+    plot_context_menu(app, &response, "teplota_vlhkost");
+}
+
+fn draw_rate_graph(app: &mut TempMonitorApp, ui: &mut egui::Ui) {
+    use egui_plot::{HLine, Line, Plot, PlotPoints};
+    let unit = temp_unit();
+    let temp_rates = rate_of_change(&app.history, |p| unit.delta(p.temp as f64));
+    let hum_rates = rate_of_change(&app.history, |p| p.hum as f64);
+    let mut plot = Plot::new("rate_plot").height(ui.available_height()).width(ui.available_width()).legend(series_legend(&app.hidden_series, "rate_plot"))
+        .link_axis(egui::Id::new("linked_plots"), true, false).link_cursor(egui::Id::new("linked_plots"), true, false).show_background(false)
+        .x_axis_formatter(|mark, _, range| format_time_axis(mark, range))
+        .y_axis_formatter(|mark, _, _| format!("{:+.1}/h", mark.value))
+        .label_formatter(|name, value| { let time = DateTime::from_timestamp(value.x as i64, 0).unwrap_or_default().with_timezone(&Local); format!("{}\n{}: {}\n{:+.2}/h", name, tr("Čas"), time.format("%H:%M:%S"), value.y) });
+    plot = wheel_navigation(plot, ui);
+    if app.reset_plot { plot = plot.reset(); }
+    let response = plot.show(ui, |plot_ui| {
+        plot_ui.hline(HLine::new(0.0).color(egui::Color32::DARK_GRAY).width(1.0));
+        draw_day_boundaries(plot_ui);
+        if let Some(range) = app.pending_range { apply_time_range(plot_ui, range, &[temp_rates.as_slice(), hum_rates.as_slice()].concat()); }
+        let temp_points = points_for_view(&temp_rates, plot_ui);
+        let hum_points = points_for_view(&hum_rates, plot_ui);
+        let width = 1.5 * app.config.plot_scale();
+        plot_ui.line(Line::new(PlotPoints::new(temp_points)).color(app.config.temp_style.line_color()).width(width).name(trf("Teplota {}/h", &[&unit.symbol()])));
+        plot_ui.line(Line::new(PlotPoints::new(hum_points)).color(app.config.hum_style.line_color()).width(width).name(tr("Vlhkost %/h")));
+        draw_latest_label(plot_ui, &temp_rates, app.config.temp_style.line_color(), |y| format!("{:+.2}{}/h", y, unit.symbol()));
+        draw_latest_label(plot_ui, &hum_rates, app.config.hum_style.line_color(), |y| format!("{:+.1}%/h", y));
+    });
+    remember_hidden_series(app, "rate_plot", &response);
+    plot_context_menu(app, &response, "rychlost_zmeny");
+}
+
+// Jednoduchý graf jedné veličiny (bez interakce s hlavními grafy)
+pub fn draw_simple_history_plot(ui: &mut egui::Ui, id: &str, points: &[HistoryPoint], value: impl Fn(&HistoryPoint) -> f64, color: egui::Color32, unit: &'static str, height: f32) {
+    use egui_plot::{Line, Plot, PlotPoints};
+    let series: Vec<[f64; 2]> = points.iter().map(|p| [p.timestamp.timestamp() as f64, value(p)]).collect();
+    Plot::new(id).height(height)
+        .x_axis_formatter(|mark, _, range| format_time_axis(mark, range))
+        .y_axis_formatter(move |mark, _, _| format!("{:.1}{}", mark.value, unit))
+        .label_formatter(move |_, value| { let time = DateTime::from_timestamp(value.x as i64, 0).unwrap_or_default().with_timezone(&Local); format!("{}\n{:.1}{}", time.format("%H:%M:%S"), value.y, unit) })
+        .show(ui, |plot_ui| plot_ui.line(Line::new(PlotPoints::new(series)).color(color).width(2.0)));
+}
+
+// Seskupí body do hodinových/denních intervalů; historie je seřazená podle času, takže stačí jeden průchod
+fn aggregate_history(history: &VecDeque<HistoryPoint>, mode: AggregationMode, value: impl Fn(&HistoryPoint) -> f64) -> Vec<AggregatedBucket> {
+    let mut buckets: Vec<AggregatedBucket> = Vec::new();
+    let (mut sum, mut count) = (0.0, 0usize);
+    for p in history {
+        let Some(start) = mode.bucket_start(p.timestamp) else { return Vec::new(); };
+        let v = value(p);
+        match buckets.last_mut() {
+            Some(bucket) if bucket.start == start => {
+                bucket.min = bucket.min.min(v); bucket.max = bucket.max.max(v);
+                sum += v; count += 1; bucket.mean = sum / count as f64;
+            }
+            _ => { buckets.push(AggregatedBucket { start, width_secs: mode.bucket_secs(), min: v, max: v, mean: v }); sum = v; count = 1; }
+        }
+    }
+    buckets
+}
+
+// Vykreslí agregované intervaly jako "svíčky" (rozsah min–max) a čáru průměrů
+fn draw_aggregated_series(plot_ui: &mut egui_plot::PlotUi, buckets: &[AggregatedBucket], color: egui::Color32, unit: &'static str, name: &str) {
+    use egui_plot::{BoxElem, BoxPlot, BoxSpread, Line, PlotPoints};
+    let candles: Vec<BoxElem> = buckets.iter().map(|b| {
+        let center = b.start.timestamp() as f64 + b.width_secs / 2.0;
+        BoxElem::new(center, BoxSpread::new(b.min, b.min, b.mean, b.max, b.max))
+            .box_width(b.width_secs * 0.8).whisker_width(0.0)
+            .fill(color.gamma_multiply(0.25)).stroke(egui::Stroke::new(1.0, color))
+    }).collect();
+    plot_ui.box_plot(BoxPlot::new(candles).name(name).element_formatter(Box::new(move |elem, _| {
+        let time = DateTime::from_timestamp(elem.argument as i64, 0).unwrap_or_default().with_timezone(&Local);
+        format!("{}\nMin: {:.1}{unit}\n{}: {:.1}{unit}\nMax: {:.1}{unit}", time.format("%d.%m. %H:%M"), elem.spread.lower_whisker, tr("Průměr"), elem.spread.median, elem.spread.upper_whisker)
+    })));
+    let means: Vec<[f64; 2]> = buckets.iter().map(|b| [b.start.timestamp() as f64 + b.width_secs / 2.0, b.mean]).collect();
+    plot_ui.line(Line::new(PlotPoints::new(means)).color(color).width(2.0).name(name));
+}
+
+// Zredukuje řadu na `threshold` bodů tak, aby zůstal zachován tvar křivky (vrcholy a propady)
+pub fn lttb_downsample(data: &[[f64; 2]], threshold: usize) -> Vec<[f64; 2]> {
+    if threshold < 3 || data.len() <= threshold { return data.to_vec(); }
+    let every = (data.len() - 2) as f64 / (threshold - 2) as f64;
+    let mut sampled = Vec::with_capacity(threshold);
+    sampled.push(data[0]);
+    let mut a = 0;
+    for i in 0..threshold - 2 {
+        // průměr následujícího kbelíku slouží jako třetí vrchol trojúhelníku
+        let avg_start = ((i + 1) as f64 * every) as usize + 1;
+        let avg_end = (((i + 2) as f64 * every) as usize + 1).min(data.len());
+        let avg_range = &data[avg_start.min(avg_end - 1)..avg_end];
+        let avg_x = avg_range.iter().map(|p| p[0]).sum::<f64>() / avg_range.len() as f64;
+        let avg_y = avg_range.iter().map(|p| p[1]).sum::<f64>() / avg_range.len() as f64;
+        let range_start = (i as f64 * every) as usize + 1;
+        let range_end = ((i + 1) as f64 * every) as usize + 1;
+        let point_a = data[a];
+        let (mut max_area, mut next_a) = (-1.0, range_start);
+        for (j, p) in data[range_start..range_end].iter().enumerate() {
+            let area = ((point_a[0] - avg_x) * (p[1] - point_a[1]) - (point_a[0] - p[0]) * (avg_y - point_a[1])).abs();
+            if area > max_area { max_area = area; next_a = range_start + j; }
+        }
+        sampled.push(data[next_a]);
+        a = next_a;
+    }
+    sampled.push(data[data.len() - 1]);
+    sampled
+}
+
+// Vybere body ve viditelném rozsahu (plus jeden soused na každé straně) a zředí je na šířku grafu.
+// Při automatickém rozsahu osy X se ořez nedělá – viditelné jsou beztak všechny body.
+pub fn points_for_view(points: &[[f64; 2]], plot_ui: &egui_plot::PlotUi) -> Vec<[f64; 2]> {
+    let visible = if plot_ui.auto_bounds().x { points } else {
+        let bounds = plot_ui.plot_bounds();
+        let start = points.partition_point(|p| p[0] < bounds.min()[0]).saturating_sub(1);
+        let end = (points.partition_point(|p| p[0] <= bounds.max()[0]) + 1).min(points.len());
+        &points[start..end.max(start)]
+    };
+    let threshold = plot_ui.response().rect.width().max(100.0) as usize;
+    lttb_downsample(visible, threshold)
+}
+
+// Zelený pás mezi mezemi komfortu; vodorovně pokrývá data (při ručním posunu celý viditelný rozsah)
+fn draw_comfort_band(plot_ui: &mut egui_plot::PlotUi, points: &[[f64; 2]], low: f64, high: f64) {
+    use egui_plot::{Polygon, PlotPoints};
+    let (Some(first), Some(last)) = (points.first(), points.last()) else { return; };
+    let (mut x_min, mut x_max) = (first[0], last[0]);
+    if !plot_ui.auto_bounds().x {
+        let bounds = plot_ui.plot_bounds();
+        x_min = x_min.min(bounds.min()[0]); x_max = x_max.max(bounds.max()[0]);
+    }
+    let color = egui::Color32::from_rgba_unmultiplied(80, 200, 120, 28);
+    plot_ui.polygon(Polygon::new(PlotPoints::new(vec![[x_min, low], [x_max, low], [x_max, high], [x_min, high]]))
+        .fill_color(color).stroke(egui::Stroke::new(0.0, color)).name(tr("Pásmo komfortu")));
+}
+
+fn draw_anomaly_markers(plot_ui: &mut egui_plot::PlotUi, anomalies: &[Anomaly], is_temp: bool) {
+    use egui_plot::{MarkerShape, PlotPoints, Points};
+    let marks: Vec<[f64; 2]> = anomalies.iter().filter(|a| a.is_temp == is_temp).map(|a| [a.timestamp.timestamp() as f64, if a.is_temp { temp_unit().convert(a.value) } else { a.value }]).collect();
+    if marks.is_empty() { return; }
+    plot_ui.points(Points::new(PlotPoints::new(marks)).shape(MarkerShape::Circle).filled(false).radius(7.0)
+        .color(egui::Color32::from_rgb(255, 0, 255)).name(tr("Anomálie")));
+}
+
+// Praporky u nejnižší a nejvyšší hodnoty viditelného úseku s hodnotou a časem; `label` převádí souřadnici y na text
+fn draw_extreme_markers(plot_ui: &mut egui_plot::PlotUi, points: &[[f64; 2]], color: egui::Color32, label: impl Fn(f64) -> String) {
+    use egui_plot::{MarkerShape, PlotPoint, PlotPoints, Points, Text};
+    let bounds = plot_ui.plot_bounds();
+    let visible = points.iter().filter(|p| (bounds.min()[0]..=bounds.max()[0]).contains(&p[0]));
+    let Some((min, max)) = visible.fold(None, |acc: Option<([f64; 2], [f64; 2])>, p| Some(match acc {
+        Some((min, max)) => (if p[1] < min[1] { *p } else { min }, if p[1] > max[1] { *p } else { max }),
+        None => (*p, *p),
+    })) else { return; };
+    if min[1] == max[1] { return; }
+    // Přes více dní už samotný čas nestačí
+    let time_format = if bounds.width() > 86400.0 { "%d.%m. %H:%M" } else { "%H:%M" };
+    let offset = bounds.height() * 0.03;
+    for (point, is_max) in [(max, true), (min, false)] {
+        let time = DateTime::from_timestamp(point[0] as i64, 0).unwrap_or_default().with_timezone(&Local);
+        let (shape, anchor, y) = if is_max { (MarkerShape::Down, egui::Align2::CENTER_BOTTOM, point[1] + offset) } else { (MarkerShape::Up, egui::Align2::CENTER_TOP, point[1] - offset) };
+        plot_ui.points(Points::new(PlotPoints::new(vec![point])).shape(shape).filled(true).radius(5.0).color(color));
+        let text = format!("{} {} ({})", if is_max { "max" } else { "min" }, label(point[1]), time.format(time_format));
+        plot_ui.text(Text::new(PlotPoint::new(point[0], y), egui::RichText::new(text).strong()).anchor(anchor).color(color));
+    }
+}
+
+// Štítek u posledního bodu s přesnou hodnotou a časem, aby byl živý údaj vidět přímo v grafu (např. při sdílení obrazovky)
+fn draw_latest_label(plot_ui: &mut egui_plot::PlotUi, points: &[[f64; 2]], color: egui::Color32, label: impl Fn(f64) -> String) {
+    use egui_plot::{MarkerShape, PlotPoint, PlotPoints, Points, Text};
+    let Some(&last) = points.last() else { return; };
+    let time = DateTime::from_timestamp(last[0] as i64, 0).unwrap_or_default().with_timezone(&Local);
+    plot_ui.points(Points::new(PlotPoints::new(vec![last])).shape(MarkerShape::Circle).filled(true).radius(4.5).color(color));
+    let text = egui::RichText::new(format!("{} · {} ", label(last[1]), time.format("%H:%M:%S"))).strong().background_color(egui::Color32::from_black_alpha(160));
+    plot_ui.text(Text::new(PlotPoint::new(last[0], last[1]), text).anchor(egui::Align2::RIGHT_BOTTOM).color(color));
+}
+
+// Nastavené meze jako čárkované vodorovné čáry v barvách hlavního údaje (nad mezí zlatá, pod mezí modrá)
+fn draw_threshold_lines(plot_ui: &mut egui_plot::PlotUi, low: f64, high: f64) {
+    use egui_plot::{HLine, LineStyle};
+    plot_ui.hline(HLine::new(high).color(egui::Color32::GOLD).width(1.0).style(LineStyle::dashed_loose()).name(tr("Mez pro varování")));
+    plot_ui.hline(HLine::new(low).color(egui::Color32::from_rgb(120, 180, 255)).width(1.0).style(LineStyle::dashed_loose()).name(tr("Spodní mez")));
+}
+
+// Výraznější svislé čáry o půlnoci s datem nahoře, pokud viditelný úsek přesahuje přes více dní
+fn draw_day_boundaries(plot_ui: &mut egui_plot::PlotUi) {
+    use egui_plot::{PlotPoint, Text, VLine};
+    let bounds = plot_ui.plot_bounds();
+    let to_local = |x: f64| DateTime::from_timestamp(x as i64, 0).map(|t| t.with_timezone(&Local));
+    let (Some(first), Some(last)) = (to_local(bounds.min()[0]), to_local(bounds.max()[0])) else { return; };
+    let midnights: Vec<DateTime<Local>> = first.date_naive().iter_days().skip(1).take_while(|d| *d <= last.date_naive()).take(MAX_DAY_LINES + 1)
+        .filter_map(|d| d.and_hms_opt(0, 0, 0)?.and_local_timezone(Local).earliest()).collect();
+    if midnights.is_empty() || midnights.len() > MAX_DAY_LINES { return; }
+    // Popisky jen u každého n-tého dne, aby se nepřekrývaly
+    let label_step = midnights.len().div_ceil(15);
+    for (i, midnight) in midnights.iter().enumerate() {
+        let x = midnight.timestamp() as f64;
+        plot_ui.vline(VLine::new(x).color(egui::Color32::from_gray(110)).width(1.5));
+        if i % label_step == 0 {
+            plot_ui.text(Text::new(PlotPoint::new(x, bounds.max()[1]), egui::RichText::new(midnight.format(" %d.%m.").to_string()).small())
+                .anchor(egui::Align2::LEFT_TOP).color(egui::Color32::from_gray(160)));
+        }
+    }
+}
+
+// Shift + tažení vybere úsek časové osy; po puštění se zobrazí jeho souhrn a měření se zkopírují do schránky jako TSV
+fn track_range_selection(app: &mut TempMonitorApp, plot_ui: &mut egui_plot::PlotUi) {
+    use egui_plot::VLine;
+    let response = plot_ui.response().clone();
+    let pointer = plot_ui.pointer_coordinate();
+    if response.drag_started_by(egui::PointerButton::Primary) && plot_ui.ctx().input(|i| i.modifiers.shift) { app.range_selection = pointer.map(|p| (p.x, p.x)); }
+    if let Some(stats) = &app.range_stats {
+        for x in [stats.from, stats.to] { plot_ui.vline(VLine::new(x).color(egui::Color32::LIGHT_BLUE).width(1.0).style(egui_plot::LineStyle::dashed_loose())); }
+    }
+    let Some((start, end)) = app.range_selection.as_mut() else { return; };
+    if response.dragged() { if let Some(p) = pointer { *end = p.x; } }
+    // Hranice se kreslí ve všech grafech, svislé čáry nemění automatický rozsah osy y
+    for x in [*start, *end] { plot_ui.vline(VLine::new(x).color(egui::Color32::LIGHT_BLUE).width(1.5)); }
+    if !response.drag_stopped() { return; }
+    let (from, to) = if start <= end { (*start, *end) } else { (*end, *start) };
+    app.range_selection = None;
+    let points: Vec<&HistoryPoint> = app.history.iter().filter(|p| (from..=to).contains(&(p.timestamp.timestamp() as f64))).collect();
+    if points.is_empty() { return; }
+    let count = points.len() as f64;
+    let temp = points.iter().fold((f32::MAX, f32::MIN, 0.0), |(lo, hi, sum), p| (lo.min(p.temp), hi.max(p.temp), sum + p.temp as f64));
+    let hum = points.iter().fold((u8::MAX, u8::MIN, 0.0), |(lo, hi, sum), p| (lo.min(p.hum), hi.max(p.hum), sum + p.hum as f64));
+    app.range_stats = Some(RangeStats { from, to, count: points.len(), temp: (temp.0, temp.1, temp.2 / count), hum: (hum.0, hum.1, hum.2 / count) });
+    plot_ui.ctx().output_mut(|o| o.copied_text = readings_tsv(points.iter().copied()));
+    let count = points.len();
+    info!("Do schránky zkopírováno {} měření z vybraného úseku.", count);
+    app.toast(Severity::Success, trf("Zkopírováno {} měření (TSV).", &[&count]));
+}
+
+// Měření vyfiltrovaná v tabulce jako výrazné kroužky
+fn draw_table_selection(plot_ui: &mut egui_plot::PlotUi, history: &VecDeque<HistoryPoint>, selection: &[usize], value: impl Fn(&HistoryPoint) -> f64) {
+    use egui_plot::{MarkerShape, PlotPoints, Points};
+    let marks: Vec<[f64; 2]> = selection.iter().map(|&i| [history[i].timestamp.timestamp() as f64, value(&history[i])]).collect();
+    plot_ui.points(Points::new(PlotPoints::new(marks)).shape(MarkerShape::Circle).filled(false).radius(5.0).color(egui::Color32::WHITE).name(tr("Výběr z tabulky")));
+}
+
+// Svislá čára s textem poznámky u horního okraje grafu
+fn draw_annotation_markers(plot_ui: &mut egui_plot::PlotUi, annotations: &[Annotation]) {
+    use egui_plot::{LineStyle, PlotPoint, Text, VLine};
+    let top = plot_ui.plot_bounds().max()[1];
+    let color = egui::Color32::from_rgb(230, 200, 80);
+    for annotation in annotations {
+        let x = annotation.timestamp.timestamp() as f64;
+        plot_ui.vline(VLine::new(x).color(color).width(1.0).style(LineStyle::dashed_dense()).name(tr("Poznámky")));
+        plot_ui.text(Text::new(PlotPoint::new(x, top), format!(" {}", annotation.text)).color(color).anchor(egui::Align2::LEFT_TOP).name(tr("Poznámky")));
+    }
+}
+
+// Přerušovaná čára předpovědi od posledního bodu
+fn draw_forecast(plot_ui: &mut egui_plot::PlotUi, trend: &Trend, horizon_secs: f64, color: egui::Color32) {
+    use egui_plot::{Line, LineStyle, PlotPoints};
+    let end_x = trend.last_x + horizon_secs;
+    let points = vec![[trend.last_x, trend.last_y], [end_x, trend.value_at(end_x)]];
+    plot_ui.line(Line::new(PlotPoints::new(points)).color(color.gamma_multiply(0.8)).width(2.0).style(LineStyle::dashed_loose()).name(tr("Předpověď")));
+}
+
+// Popisky osy X: při rozsahu přes více dní se zobrazuje datum místo času
+pub fn format_time_axis(mark: egui_plot::GridMark, range: &std::ops::RangeInclusive<f64>) -> String {
+    let time = DateTime::from_timestamp(mark.value as i64, 0).unwrap_or_default().with_timezone(&Local);
+    if range.end() - range.start() > 2.0 * 86400.0 { time.format("%d.%m.").to_string() } else { time.format("%H:%M").to_string() }
+}
+
+pub fn save_png(filename: &str, image: &egui::ColorImage) -> image::ImageResult<()> {
+    image::save_buffer(filename, image.as_raw(), image.width() as u32, image.height() as u32, image::ColorType::Rgba8)
+}
+
+// Nabídka po kliknutí pravým tlačítkem do grafu; akce "pod kurzorem" a "sem" platí pro místo, kde se nabídka otevřela
+fn plot_context_menu(app: &mut TempMonitorApp, plot: &egui_plot::PlotResponse<()>, name: &'static str) {
+    let response = &plot.response;
+    if response.secondary_clicked() { app.plot_menu_time = response.interact_pointer_pos().map(|pos| plot.transform.value_from_position(pos).x); }
+    let (visible_from, visible_to) = (plot.transform.bounds().min()[0], plot.transform.bounds().max()[0]);
+    response.context_menu(|ui| {
+        if let Some(time) = app.plot_menu_time {
+            let at = DateTime::from_timestamp(time as i64, 0).unwrap_or_default().with_timezone(&Local);
+            if let Some(point) = nearest_reading(&app.history, time) {
+                if ui.button(trf("Kopírovat měření z {}", &[&point.timestamp.format("%H:%M:%S")])).clicked() {
+                    let text = format!("{}: {}, {}: {}, {}: {}%", tr("Čas"), point.timestamp.format("%d.%m.%Y %H:%M:%S"), tr("Teplota"), format_temp(point.temp as f64, 1), tr("Vlhkost"), point.hum);
+                    info!("Zkopírováno do schránky: {}", text);
+                    ui.ctx().output_mut(|o| o.copied_text = text);
+                    app.toast(Severity::Success, tr("Zkopírováno do schránky!"));
+                    ui.close_menu();
+                }
+            }
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut app.annotation_draft).hint_text(tr("např. otevřené okno")).desired_width(160.0));
+                if ui.add_enabled(!app.annotation_draft.trim().is_empty(), egui::Button::new(trf("Přidat poznámku v {}", &[&at.format("%H:%M")]))).clicked() {
+                    // Poznámky jsou seřazené podle času (nabídka poznámek ukazuje poslední)
+                    let index = app.annotations.partition_point(|a| a.timestamp <= at);
+                    app.annotations.insert(index, Annotation { timestamp: at, text: app.annotation_draft.trim().to_owned() });
+                    app.annotation_draft.clear();
+                    app.save_annotations();
+                    ui.close_menu();
+                }
+            });
+            ui.separator();
+        }
+        if ui.button(tr("Přiblížit na dnešek")).clicked() { app.queued_range = Some(TimeRange::Today); ui.ctx().request_repaint(); ui.close_menu(); }
+        if ui.button(tr("Exportovat viditelný úsek do CSV")).clicked() {
+            let start = app.history.partition_point(|p| (p.timestamp.timestamp() as f64) < visible_from);
+            let end = app.history.partition_point(|p| (p.timestamp.timestamp() as f64) <= visible_to);
+            let indices: Vec<usize> = (start..end.max(start)).collect();
+            let (severity, message) = if indices.is_empty() { (Severity::Warning, tr("Ve viditelném úseku nejsou žádná měření.").to_owned()) } else {
+                match export_readings(&app.history, &indices) {
+                    Ok(filename) => { info!("Viditelný úsek grafu ({} měření) uložen do '{}'.", indices.len(), filename); (Severity::Success, trf("Výběr uložen do '{}'.", &[&filename])) }
+                    Err(e) => { error!("Export viditelného úseku selhal: {}", e); (Severity::Error, trf("Export se nezdařil: {}", &[&e])) }
+                }
+            };
+            app.toast(severity, message);
+            ui.close_menu();
+        }
+        if ui.button(tr("Exportovat graf do PNG")).clicked() {
+            app.plot_export = Some(PlotExport { name, taken_at: Local::now(), rect: None, requested: false });
+            ui.ctx().request_repaint();
+            ui.close_menu();
+        }
+        ui.label(egui::RichText::new(tr("Tip: Shift + tažení v grafu ukáže souhrn úseku a zkopíruje ho jako TSV")).color(egui::Color32::GRAY));
+        ui.label(egui::RichText::new(tr("Shift + kolečko posouvá časovou osu, Ctrl + kolečko ji přibližuje")).color(egui::Color32::GRAY));
+    });
+}
+
+// Kolečko myši v časových grafech: se Shiftem posouvá jen po časové ose, s Ctrl přibližuje jen časovou osu
+// (osa y se pak dál přizpůsobuje hodnotám); bez modifikátoru i gesta touchpadu fungují jako dřív
+fn wheel_navigation(plot: egui_plot::Plot, ui: &egui::Ui) -> egui_plot::Plot {
+    let modifiers = ui.input(|i| i.modifiers);
+    plot.allow_scroll(egui::Vec2b::new(true, !modifiers.shift)).allow_zoom(egui::Vec2b::new(true, !modifiers.command))
+}
+
+// Legenda grafu; kliknutím na položku se řada skryje, uložený stav přežije restart aplikace
+fn series_legend(hidden_series: &HashMap<String, Vec<String>>, plot_id: &str) -> egui_plot::Legend {
+    egui_plot::Legend::default().hidden_items(hidden_series.get(plot_id).cloned().unwrap_or_default())
+}
+
+fn remember_hidden_series(app: &mut TempMonitorApp, plot_id: &str, response: &egui_plot::PlotResponse<()>) {
+    let Some(memory) = egui_plot::PlotMemory::load(&response.response.ctx, response.response.id) else { return; };
+    let mut hidden: Vec<String> = memory.hidden_items.into_iter().collect();
+    hidden.sort();
+    if app.hidden_series.get(plot_id).map_or(hidden.is_empty(), |previous| *previous == hidden) { return; }
+    if hidden.is_empty() { app.hidden_series.remove(plot_id); } else { app.hidden_series.insert(plot_id.to_owned(), hidden); }
+}
+
+// Měření nejbližší danému času na ose x (historie je seřazená podle času)
+fn nearest_reading(history: &VecDeque<HistoryPoint>, x: f64) -> Option<&HistoryPoint> {
+    let index = history.partition_point(|p| (p.timestamp.timestamp() as f64) < x);
+    [index.checked_sub(1), Some(index)].into_iter().flatten().filter_map(|i| history.get(i))
+        .min_by(|a, b| (a.timestamp.timestamp() as f64 - x).abs().total_cmp(&(b.timestamp.timestamp() as f64 - x).abs()))
+}
+
+// Popisek kurzoru s teplotou i vlhkostí měření nejbližšího ukazovanému času; svislý kurzor
+// se díky link_cursor kreslí i v ostatních grafech
+fn combined_readout(history: &VecDeque<HistoryPoint>) -> impl Fn(&str, &egui_plot::PlotPoint) -> String {
+    let samples: Vec<(i64, f32, u8)> = history.iter().map(|p| (p.timestamp.timestamp(), p.temp, p.hum)).collect();
+    move |_name, value| {
+        let index = samples.partition_point(|s| (s.0 as f64) < value.x);
+        let nearest = [index.checked_sub(1), Some(index)].into_iter().flatten().filter_map(|i| samples.get(i))
+            .min_by(|a, b| (a.0 as f64 - value.x).abs().total_cmp(&(b.0 as f64 - value.x).abs()));
+        match nearest {
+            Some(&(timestamp, temp, hum)) => {
+                let time = DateTime::from_timestamp(timestamp, 0).unwrap_or_default().with_timezone(&Local);
+                format!("{}: {}\n{}: {}\n{}: {}%", tr("Čas"), time.format("%H:%M:%S"), tr("Teplota"), format_temp(temp as f64, 1), tr("Vlhkost"), hum)
+            }
+            None => String::new(),
+        }
+    }
+}
+
+// Nastaví osu x na zvolený úsek končící posledním měřením a osu y na rozsah hodnot v něm
+fn apply_time_range(plot_ui: &mut egui_plot::PlotUi, range: TimeRange, points: &[[f64; 2]]) {
+    let (Some(first), Some(last)) = (points.iter().map(|p| p[0]).reduce(f64::min), points.iter().map(|p| p[0]).reduce(f64::max)) else { return; };
+    let end = last.max(Local::now().timestamp() as f64);
+    let start = range.secs().map_or(first, |secs| end - secs);
+    let (low, high) = points.iter().filter(|p| p[0] >= start).fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| (lo.min(p[1]), hi.max(p[1])));
+    let current = plot_ui.plot_bounds();
+    let (low, high) = if low.is_finite() { let margin = ((high - low) * 0.1).max(0.5); (low - margin, high + margin) } else { (current.min()[1], current.max()[1]) };
+    plot_ui.set_plot_bounds(egui_plot::PlotBounds::from_min_max([start, low], [end, high]));
+}
+
+// Rychlost změny za hodinu: rozdíl oproti nejstaršímu bodu v klouzavém okně RATE_WINDOW_SECS
+fn rate_of_change(history: &VecDeque<HistoryPoint>, value: impl Fn(&HistoryPoint) -> f64) -> Vec<[f64; 2]> {
+    let mut rates = Vec::with_capacity(history.len());
+    let mut start = 0;
+    for p in history.iter() {
+        while (p.timestamp - history[start].timestamp).num_seconds() > RATE_WINDOW_SECS { start += 1; }
+        let dt = (p.timestamp - history[start].timestamp).num_seconds();
+        // příliš krátký úsek by zesílil šum kvantování (0,1 °C / 1 %)
+        if dt >= 60 { rates.push([p.timestamp.timestamp() as f64, (value(p) - value(&history[start])) / dt as f64 * 3600.0]); }
+    }
+    rates
+}
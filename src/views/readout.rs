@@ -0,0 +1,108 @@
+// --- Aktuální hodnoty ---
+// Řádek nad grafy: poslední teplota a vlhkost s extrémy, stav skeneru a podrobnosti posledního měření.
+use crate::stats::{linear_trend, min_max, recent_extremes, RecentExtremes};
+use crate::views::format_duration_short;
+use chrono::Local;
+use eframe::egui;
+use std::collections::VecDeque;
+use temp_monitor::config::Config;
+use temp_monitor::i18n::{tr, trf};
+use temp_monitor::processing::{equilibrium_moisture_content, BleDataPoint};
+use temp_monitor::storage::HistoryPoint;
+use temp_monitor::units::{format_temp, format_temp_delta};
+
+// Senzor bez měření po dobu tolika očekávaných intervalů se na kartě hlásí jako bez dat
+pub const STALE_INTERVALS: u64 = 3;
+
+pub fn draw_temperature_info(ui: &mut egui::Ui, history: &VecDeque<HistoryPoint>, recent: &VecDeque<HistoryPoint>, config: &Config) {
+    ui.label(egui::RichText::new(tr("Teplota")).size(22.0).color(egui::Color32::GRAY));
+    if let Some(point) = history.back() {
+        let current_temp = point.temp;
+        let mut color = egui::Color32::from_rgb(255, 100, 100);
+        if current_temp > config.temp_warn_high { color = egui::Color32::GOLD; } else if current_temp < config.temp_warn_low { color = egui::Color32::from_rgb(120, 180, 255); }
+        ui.label(egui::RichText::new(format_temp(current_temp as f64, 1)).size(42.0).color(color));
+    } else { ui.label(egui::RichText::new(tr("N/A")).size(32.0)); }
+    draw_extremes_labels(ui, &recent_extremes(recent, |p| p.temp as f64), min_max(history.iter().map(|p| p.temp as f64)), |v| format_temp(v, 1));
+    if config.forecast_enabled {
+        if let Some(trend) = linear_trend(history, config.forecast_window_minutes as f64 * 60.0, |p| p.temp as f64) {
+            // Odhad času do dosažení meze, ke které teplota směřuje (zobrazuje se jen do 24 h)
+            let target = if trend.slope_per_sec > 0.0 { config.temp_warn_high } else { config.temp_warn_low };
+            if let Some(secs) = trend.secs_to_reach(target as f64).filter(|secs| *secs < 86400.0) {
+                ui.label(egui::RichText::new(trf("{} za ~{}", &[&format_temp(target as f64, 1), &format_duration_short(secs)])).size(14.0).color(egui::Color32::GOLD))
+                    .on_hover_text(trf("Odhad z trendu za posledních {} min ({}/h)", &[&config.forecast_window_minutes, &format_temp_delta(trend.slope_per_sec * 3600.0, 2)]));
+            }
+        }
+    }
+}
+
+pub fn draw_humidity_info(ui: &mut egui::Ui, history: &VecDeque<HistoryPoint>, recent: &VecDeque<HistoryPoint>, config: &Config) {
+    ui.label(egui::RichText::new(tr("Vlhkost")).size(22.0).color(egui::Color32::GRAY));
+    if let Some(point) = history.back() {
+        ui.label(egui::RichText::new(format!("{}%", point.hum)).size(42.0).color(config.hum_color(point.hum)));
+        if config.emc_enabled {
+            ui.label(egui::RichText::new(trf("EMC dřeva: {} %", &[&format!("{:.1}", equilibrium_moisture_content(point.temp as f64, point.hum as f64))])).size(16.0).color(egui::Color32::from_rgb(200, 160, 110)))
+                .on_hover_text(tr("Rovnovážná vlhkost dřeva při aktuální teplotě a vlhkosti vzduchu"));
+        }
+    } else { ui.label(egui::RichText::new(tr("N/A")).size(32.0)); }
+    draw_extremes_labels(ui, &recent_extremes(recent, |p| p.hum as f64), min_max(history.iter().map(|p| p.hum as f64)), |v| format!("{:.0}%", v));
+}
+
+fn draw_extremes_labels(ui: &mut egui::Ui, extremes: &RecentExtremes, loaded: Option<(f64, f64)>, fmt: impl Fn(f64) -> String) {
+    let range = |r: Option<(f64, f64)>| r.map_or("N/A".to_string(), |(lo, hi)| format!("{} – {}", fmt(lo), fmt(hi)));
+    ui.label(egui::RichText::new(format!("24 h: {}", range(extremes.last_24h))).size(16.0).color(egui::Color32::WHITE))
+        .on_hover_text(trf("Klouzavých posledních 24 hodin\nNačtená historie: {}", &[&range(loaded)]));
+    ui.label(egui::RichText::new(trf("Dnes: {}", &[&range(extremes.today)])).size(16.0).color(egui::Color32::LIGHT_GRAY))
+        .on_hover_text(tr("Od dnešní půlnoci"));
+}
+
+pub fn draw_scan_metadata(ui: &mut egui::Ui, last_data: &Option<BleDataPoint>, status: &str, config: &Config) {
+    ui.horizontal(|ui| { ui.label(egui::RichText::new(tr("Stav:")).color(egui::Color32::GRAY)); ui.label(status); });
+    if let Some(data) = last_data {
+        // Stáří měření: oranžově po očekávaném intervalu, červeně když senzor už několik intervalů mlčí
+        let age = (Local::now() - data.timestamp).num_seconds().max(0);
+        let expected = config.expected_interval_secs() as i64;
+        let age_color = if age > expected * STALE_INTERVALS as i64 { egui::Color32::RED } else if age > expected { egui::Color32::from_rgb(255, 165, 0) } else { egui::Color32::GRAY };
+        let age_text = if age < 60 { trf("před {} s", &[&age]) } else { trf("před {}", &[&format_duration_short(age as f64)]) };
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(tr("Aktualizace:")).size(17.0).color(egui::Color32::GRAY));
+            ui.label(data.timestamp.format("%H:%M:%S").to_string());
+            ui.label(egui::RichText::new(age_text).color(age_color)).on_hover_text(trf("Očekávaný interval měření: {} s", &[&expected]));
+        });
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(tr("Signál:")).size(17.0).color(egui::Color32::GRAY));
+            if let Some(rssi) = data.rssi { draw_signal_bars(ui, rssi, &config.rssi_bar_thresholds).on_hover_text(format!("RSSI {} dBm", rssi)); } else { ui.label(tr("N/A")); }
+        });
+        if let Some(battery) = data.battery {
+            let color = if battery < config.battery_critical_level { egui::Color32::RED } else if battery < config.battery_warn_level { egui::Color32::YELLOW } else { egui::Color32::GREEN };
+            ui.horizontal(|ui| { ui.label(egui::RichText::new(tr("Baterie:")).size(17.0).color(egui::Color32::GRAY)); ui.label(egui::RichText::new(format!("🔋 {} %", battery)).color(color)); });
+        }
+    }
+}
+
+// Čtyři dílky jako u mobilu; barva podle počtu rozsvícených
+fn draw_signal_bars(ui: &mut egui::Ui, rssi: i16, thresholds: &[i16; 3]) -> egui::Response {
+    let lit = 1 + thresholds.iter().filter(|&&t| rssi >= t).count();
+    let color = match lit { 1 => egui::Color32::RED, 2 => egui::Color32::YELLOW, _ => egui::Color32::GREEN };
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(28.0, 16.0), egui::Sense::hover());
+    for i in 0..4 {
+        let height = rect.height() * (i + 1) as f32 / 4.0;
+        let bar = egui::Rect::from_min_max(egui::pos2(rect.left() + i as f32 * 7.0, rect.bottom() - height), egui::pos2(rect.left() + i as f32 * 7.0 + 5.0, rect.bottom()));
+        ui.painter().rect_filled(bar, 1.0, if i < lit { color } else { egui::Color32::DARK_GRAY });
+    }
+    response
+}
+
+pub fn draw_data_details(ui: &mut egui::Ui, last_data: &Option<BleDataPoint>, csv_ok: bool, rejected_count: usize, last_rejection: &Option<String>) {
+    if let Some(data) = last_data {
+        ui.horizontal(|ui| { ui.label(egui::RichText::new(tr("ID Zařízení:")).size(17.0).color(egui::Color32::GRAY)); ui.label(data.device_id.to_string()); });
+        ui.horizontal(|ui| { ui.label(egui::RichText::new(tr("Raw data:")).size(17.0).color(egui::Color32::GRAY)); ui.label(data.raw_data.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" ")); });
+        ui.horizontal(|ui| { ui.label(egui::RichText::new(tr("Zápis CSV:")).size(17.0).color(egui::Color32::GRAY)); if csv_ok { ui.label(egui::RichText::new(tr("OK")).color(egui::Color32::GREEN)); } else { ui.label(egui::RichText::new(tr("Chyba")).color(egui::Color32::RED)); } });
+    }
+    if rejected_count > 0 {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(tr("Odmítnuto:")).size(17.0).color(egui::Color32::GRAY));
+            let label = ui.label(egui::RichText::new(rejected_count.to_string()).color(egui::Color32::YELLOW));
+            if let Some(reason) = last_rejection { label.on_hover_text(trf("Poslední: {}", &[reason])); }
+        });
+    }
+}
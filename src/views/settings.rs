@@ -0,0 +1,526 @@
+// --- Okno nastavení ---
+// Kategorie nastavení a ovládací prvky pro hodnoty v konfiguraci.
+use crate::{SettingsCategory, TempMonitorApp};
+use chrono::{NaiveTime, Timelike};
+use eframe::egui;
+use log::info;
+use temp_monitor::config::{normalize_mac, Config, ConfigProfile, DeviceConfig, MarkerStyle, ScanWindow, StylePreset, BATTERY_SCAN_PAUSE_FACTOR, LOW_POWER_REFRESH_SECS};
+use temp_monitor::i18n::{tr, trf, Language};
+use temp_monitor::units::{temp_unit, TempUnit};
+
+// Popisek volby automatického spuštění podle systému
+#[cfg(windows)]
+const AUTOSTART_LABEL: &str = "Spouštět po přihlášení do Windows";
+#[cfg(target_os = "linux")]
+const AUTOSTART_LABEL: &str = "Spouštět po přihlášení";
+
+impl TempMonitorApp {
+    pub fn draw_settings_window(&mut self, ctx: &egui::Context) {
+        if self.settings_open {
+            let mut is_open = self.settings_open;
+            // Ovládací prvky níže upravují `self.config`; po dobu vykreslení okna v něm je rozpracovaná kopie
+            let draft = self.settings_draft.take().unwrap_or_else(|| self.config.clone());
+            let saved = std::mem::replace(&mut self.config, draft);
+            let (mut apply, mut close) = (false, false);
+            egui::Window::new(tr("Nastavení")).open(&mut is_open).show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("🔍");
+                    ui.add(egui::TextEdit::singleline(&mut self.settings_search).hint_text(tr("Hledat nastavení…")).desired_width(220.0));
+                    if !self.settings_search.is_empty() && ui.small_button("✖").clicked() { self.settings_search.clear(); }
+                });
+                ui.add_enabled_ui(self.settings_search.trim().is_empty(), |ui| {
+                    ui.horizontal(|ui| {
+                        for category in SettingsCategory::ALL { ui.selectable_value(&mut self.settings_category, category, category.label()); }
+                    });
+                });
+                ui.separator();
+                // Při hledání se místo vybrané kategorie ukážou oddíly ze všech kategorií, v jejichž popiscích se text vyskytuje
+                let needle = self.settings_search.trim().to_lowercase();
+                let (category, matched) = (self.settings_category, std::cell::Cell::new(false));
+                let show = |section: SettingsCategory, labels: &[&str]| {
+                    let visible = if needle.is_empty() { section == category } else { labels.iter().any(|label| label.to_lowercase().contains(&needle)) };
+                    if visible { matched.set(true); }
+                    visible
+                };
+                let defaults = Config::default();
+                egui::ScrollArea::vertical().max_height(380.0).auto_shrink([false, true]).show(ui, |ui| {
+                    // --- Zařízení ---
+                    if show(SettingsCategory::Device, &[tr("Senzory"), tr("Cílová MAC adresa:"), tr("Další senzory"), tr("Název")]) {
+                        ui.strong(tr("Senzory"));
+                        ui.label(tr("Cílová MAC adresa:"));
+                        if let Some(error) = mac_edit(ui, "target_mac", &mut self.config.target_mac, 200.0, true) { ui.colored_label(egui::Color32::RED, error); }
+                        if cfg!(target_os = "macos") { ui.label(tr("macOS adresu MAC neprozradí: zadejte UUID senzoru, které aplikace po jeho nalezení zapíše do protokolu.")); }
+                        ui.collapsing(tr("Další senzory"), |ui| {
+                            let mut remove = None;
+                            egui::Grid::new("extra_devices_grid").num_columns(4).show(ui, |ui| {
+                                for (i, device) in self.config.extra_devices.iter_mut().enumerate() {
+                                    ui.add(egui::TextEdit::singleline(&mut device.name).hint_text(tr("Název")).desired_width(120.0));
+                                    let error = mac_edit(ui, &format!("extra_mac_{}", i), &mut device.mac, 150.0, false);
+                                    if ui.button("🗑").on_hover_text(tr("Odebrat senzor")).clicked() { remove = Some(i); }
+                                    if let Some(error) = error { ui.colored_label(egui::Color32::RED, "⚠").on_hover_text(error); } else { ui.label(""); }
+                                    ui.end_row();
+                                }
+                            });
+                            if let Some(i) = remove {
+                                self.config.extra_devices.remove(i);
+                                // Rozepsané adresy patří k pořadí řádků, po odebrání by se posunuly k jinému senzoru
+                                ui.data_mut(|d| for j in i..=self.config.extra_devices.len() { d.remove::<String>(egui::Id::new(format!("extra_mac_{}", j))); });
+                            }
+                            if ui.button(tr("➕ Přidat senzor")).clicked() { self.config.extra_devices.push(DeviceConfig::default()); }
+                            ui.label(tr("Měření dalších senzorů se ukládají do souborů log_<MAC>_<datum>.csv."));
+                        });
+                        ui.separator();
+                    }
+                    if show(SettingsCategory::Device, &[tr("Skenování"), tr("Timeout skenování (s): "), tr("Pauza mezi skeny (s): "), tr("Kontinuální režim"), tr("Časová okna skenování")]) {
+                        ui.strong(tr("Skenování"));
+                        ui.horizontal(|ui| {
+                            ui.add(egui::DragValue::new(&mut self.config.scan_timeout_secs).prefix(tr("Timeout skenování (s): ")));
+                            reset_button(ui, &mut self.config.scan_timeout_secs, &defaults.scan_timeout_secs);
+                        });
+                        ui.horizontal(|ui| {
+                            ui.add(egui::DragValue::new(&mut self.config.scan_pause_secs).prefix(tr("Pauza mezi skeny (s): ")));
+                            reset_button(ui, &mut self.config.scan_pause_secs, &defaults.scan_pause_secs);
+                        });
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut self.config.continuous_mode, tr("Kontinuální režim"));
+                            reset_button(ui, &mut self.config.continuous_mode, &defaults.continuous_mode);
+                        });
+                        ui.label(tr("⚠️ Kontinuální režim pouze zrychluje skenování, stále platí interval pro duplikáty."));
+                        ui.collapsing(tr("Časová okna skenování"), |ui| {
+                            let mut remove = None;
+                            for (i, window) in self.config.scan_windows.iter_mut().enumerate() {
+                                ui.horizontal(|ui| {
+                                    time_edit(ui, &mut window.start);
+                                    ui.label("–");
+                                    time_edit(ui, &mut window.end);
+                                    if ui.button("🗑").on_hover_text(tr("Odebrat okno")).clicked() { remove = Some(i); }
+                                });
+                            }
+                            if let Some(i) = remove { self.config.scan_windows.remove(i); }
+                            if ui.button(tr("➕ Přidat okno")).clicked() { self.config.scan_windows.push(ScanWindow::default()); }
+                            ui.label(tr("Mimo okna skener nepracuje a Bluetooth nepoužívá; bez oken se skenuje nepřetržitě. Okno může přecházet přes půlnoc (22:00–06:00)."));
+                        });
+                        ui.separator();
+                    }
+                    if show(SettingsCategory::Device, &[tr("Profily"), tr("Název profilu")]) {
+                        ui.strong(tr("Profily"));
+                        ui.label(tr("Profil si pamatuje meze teploty a vlhkosti a intervaly skenování; přepíná se v menu nahoře nebo v menu ikony."));
+                        let (mut switch, mut remove) = (None, None);
+                        egui::Grid::new("profiles_grid").num_columns(4).show(ui, |ui| {
+                            let active = self.config.active_profile.clone();
+                            let current = ConfigProfile::capture("", &self.config);
+                            for (i, profile) in self.config.profiles.iter_mut().enumerate() {
+                                let old_name = profile.name.clone();
+                                ui.add(egui::TextEdit::singleline(&mut profile.name).hint_text(tr("Název profilu")).desired_width(120.0));
+                                if profile.name != old_name && active == old_name { switch = Some((i, false)); }
+                                if ui.add_enabled(active != profile.name, egui::Button::new(tr("Přepnout"))).clicked() { switch = Some((i, true)); }
+                                let unchanged = ConfigProfile { name: profile.name.clone(), ..current.clone() } == *profile;
+                                if ui.add_enabled(!unchanged, egui::Button::new("💾")).on_hover_text(tr("Uložit do profilu aktuální meze a intervaly")).clicked() { *profile = ConfigProfile { name: profile.name.clone(), ..current.clone() }; }
+                                if ui.button("🗑").on_hover_text(tr("Odebrat profil")).clicked() { remove = Some(i); }
+                                ui.end_row();
+                            }
+                        });
+                        // Přejmenovaný zapnutý profil zůstává zapnutý
+                        match switch {
+                            Some((i, true)) => { let name = self.config.profiles[i].name.clone(); self.config.switch_profile(&name); },
+                            Some((i, false)) => self.config.active_profile = self.config.profiles[i].name.clone(),
+                            None => {}
+                        }
+                        if let Some(i) = remove {
+                            if self.config.profiles.remove(i).name == self.config.active_profile { self.config.active_profile.clear(); }
+                        }
+                        if ui.button(tr("➕ Nový profil z aktuálních hodnot")).clicked() {
+                            let name = (1..).map(|n| trf("Profil {}", &[&n])).find(|name| self.config.profiles.iter().all(|p| p.name != *name)).unwrap_or_default();
+                            self.config.profiles.push(ConfigProfile::capture(&name, &self.config));
+                            self.config.active_profile = name;
+                        }
+                        ui.separator();
+                    }
+                    if show(SettingsCategory::Device, &[tr("Signál a baterie"), tr("Signál – dílky od (dBm):"), tr("Baterie – varování pod (%): "), tr("kriticky pod (%): ")]) {
+                        ui.strong(tr("Signál a baterie"));
+                        ui.horizontal(|ui| {
+                            ui.label(tr("Signál – dílky od (dBm):"));
+                            for threshold in &mut self.config.rssi_bar_thresholds { ui.add(egui::DragValue::new(threshold).clamp_range(-120..=0)); }
+                            reset_button(ui, &mut self.config.rssi_bar_thresholds, &defaults.rssi_bar_thresholds);
+                        });
+                        ui.horizontal(|ui| {
+                            ui.add(egui::DragValue::new(&mut self.config.battery_warn_level).prefix(tr("Baterie – varování pod (%): ")).clamp_range(0..=100));
+                            reset_button(ui, &mut self.config.battery_warn_level, &defaults.battery_warn_level);
+                            ui.add(egui::DragValue::new(&mut self.config.battery_critical_level).prefix(tr("kriticky pod (%): ")).clamp_range(0..=100));
+                            reset_button(ui, &mut self.config.battery_critical_level, &defaults.battery_critical_level);
+                        });
+                        ui.separator();
+                    }
+                    // --- Záznam ---
+                    if show(SettingsCategory::Logging, &[tr("Duplicitní záznamy"), tr("Interval pro duplikáty (s): ")]) {
+                        ui.strong(tr("Duplicitní záznamy"));
+                        ui.horizontal(|ui| {
+                            ui.add(egui::DragValue::new(&mut self.config.duplicate_threshold_secs).prefix(tr("Interval pro duplikáty (s): ")));
+                            reset_button(ui, &mut self.config.duplicate_threshold_secs, &defaults.duplicate_threshold_secs);
+                        });
+                        ui.label(tr("Záznamy ze stejného zařízení budou ignorovány po tuto dobu."));
+                        ui.separator();
+                    }
+                    if show(SettingsCategory::Logging, &[tr("Historie"), tr("Načíst kompletní historii z CSV při startu"), tr("Načíst i starší denní logy"), tr("Dní v paměti (0 = vše): ")]) {
+                        ui.strong(tr("Historie"));
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut self.config.load_all_history, tr("Načíst kompletní historii z CSV při startu"));
+                            reset_button(ui, &mut self.config.load_all_history, &defaults.load_all_history);
+                            if self.history_loader.is_some() { ui.spinner(); ui.label(tr("Načítám historii…")); }
+                        });
+                        ui.add_enabled_ui(self.config.load_all_history, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.checkbox(&mut self.config.load_older_logs, tr("Načíst i starší denní logy")).on_hover_text(tr("Bez toho se načte jen dnešní log; se staršími logy ukážou grafy i vícedenní přehled."));
+                                reset_button(ui, &mut self.config.load_older_logs, &defaults.load_older_logs);
+                            });
+                        });
+                        ui.add_enabled_ui(self.config.loads_older_logs(), |ui| {
+                            ui.horizontal(|ui| {
+                                ui.add(egui::DragValue::new(&mut self.config.history_memory_days).prefix(tr("Dní v paměti (0 = vše): ")).clamp_range(0..=3650));
+                                reset_button(ui, &mut self.config.history_memory_days, &defaults.history_memory_days);
+                            }).response.on_hover_text(tr("Starší dny se při posunu grafu načítají z denních logů."));
+                        });
+                        if self.config.load_all_history { ui.label(egui::RichText::new(tr("POZOR: Může zpomalit start.")).color(egui::Color32::YELLOW)); }
+                        ui.separator();
+                    }
+                    if show(SettingsCategory::Logging, &[tr("Filtr hodnot"), tr("Filtrovat nesmyslné hodnoty (poškozené pakety)"), tr("Platná teplota od"), tr("Platná teplota do"), tr("Max. skok teploty"), tr("Max. skok vlhkosti (%): ")]) {
+                        ui.strong(tr("Filtr hodnot"));
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut self.config.spike_filter_enabled, tr("Filtrovat nesmyslné hodnoty (poškozené pakety)"));
+                            reset_button(ui, &mut self.config.spike_filter_enabled, &defaults.spike_filter_enabled);
+                        });
+                        ui.add_enabled_ui(self.config.spike_filter_enabled, |ui| {
+                            ui.horizontal(|ui| {
+                                temp_drag_value(ui, &mut self.config.temp_valid_min, tr("Platná teplota od"), 0.5, false);
+                                reset_button(ui, &mut self.config.temp_valid_min, &defaults.temp_valid_min);
+                            });
+                            ui.horizontal(|ui| {
+                                temp_drag_value(ui, &mut self.config.temp_valid_max, tr("Platná teplota do"), 0.5, false);
+                                reset_button(ui, &mut self.config.temp_valid_max, &defaults.temp_valid_max);
+                            });
+                            ui.horizontal(|ui| {
+                                temp_drag_value(ui, &mut self.config.max_temp_jump, tr("Max. skok teploty"), 0.1, true);
+                                reset_button(ui, &mut self.config.max_temp_jump, &defaults.max_temp_jump);
+                            });
+                            ui.horizontal(|ui| {
+                                ui.add(egui::DragValue::new(&mut self.config.max_hum_jump).prefix(tr("Max. skok vlhkosti (%): ")).clamp_range(1..=100));
+                                reset_button(ui, &mut self.config.max_hum_jump, &defaults.max_hum_jump);
+                            });
+                        });
+                        ui.separator();
+                    }
+                    if show(SettingsCategory::Logging, &[tr("Vlhkost dřeva"), tr("Zobrazovat a zapisovat rovnovážnou vlhkost dřeva (EMC)")]) {
+                        ui.strong(tr("Vlhkost dřeva"));
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut self.config.emc_enabled, tr("Zobrazovat a zapisovat rovnovážnou vlhkost dřeva (EMC)"));
+                            reset_button(ui, &mut self.config.emc_enabled, &defaults.emc_enabled);
+                        });
+                        if self.config.emc_enabled { ui.label(tr("Sloupec EMC se do logu přidá od nového denního souboru.")); }
+                        ui.separator();
+                    }
+                    if show(SettingsCategory::Logging, &[tr("Protokol aplikace"), tr("Soubor JSON Lines:")]) {
+                        ui.strong(tr("Protokol aplikace"));
+                        ui.horizontal(|ui| {
+                            ui.label(tr("Soubor JSON Lines:"));
+                            ui.add(egui::TextEdit::singleline(&mut self.config.json_log_file).hint_text(tr("vypnuto")).desired_width(200.0))
+                                .on_hover_text(tr("Protokol aplikace (informace, varování a chyby) se navíc připisuje do tohoto souboru jako JSON na řádek, např. pro Loki nebo Elastic"));
+                            reset_button(ui, &mut self.config.json_log_file, &defaults.json_log_file);
+                        });
+                        ui.separator();
+                    }
+                    // --- Upozornění ---
+                    if show(SettingsCategory::Alerts, &[tr("Meze teploty"), tr("Mez pro varování"), tr("Spodní mez")]) {
+                        ui.strong(tr("Meze teploty"));
+                        ui.horizontal(|ui| {
+                            temp_drag_value(ui, &mut self.config.temp_warn_high, tr("Mez pro varování"), 0.1, false);
+                            reset_button(ui, &mut self.config.temp_warn_high, &defaults.temp_warn_high);
+                        });
+                        ui.horizontal(|ui| {
+                            temp_drag_value(ui, &mut self.config.temp_warn_low, tr("Spodní mez"), 0.1, false);
+                            reset_button(ui, &mut self.config.temp_warn_low, &defaults.temp_warn_low);
+                        });
+                        ui.separator();
+                    }
+                    if show(SettingsCategory::Alerts, &[tr("Meze vlhkosti"), tr("Vlhkost – horní mez (%): "), tr("spodní mez (%): ")]) {
+                        ui.strong(tr("Meze vlhkosti"));
+                        ui.horizontal(|ui| {
+                            ui.add(egui::DragValue::new(&mut self.config.hum_warn_high).prefix(tr("Vlhkost – horní mez (%): ")).clamp_range(0..=100));
+                            reset_button(ui, &mut self.config.hum_warn_high, &defaults.hum_warn_high);
+                            ui.add(egui::DragValue::new(&mut self.config.hum_warn_low).prefix(tr("spodní mez (%): ")).clamp_range(0..=100));
+                            reset_button(ui, &mut self.config.hum_warn_low, &defaults.hum_warn_low);
+                        });
+                        ui.separator();
+                    }
+                    if show(SettingsCategory::Alerts, &[tr("Anomálie"), tr("Označovat neobvyklá měření (z-skóre)"), tr("Porovnávat s posledními (měření): "), tr("Práh z-skóre: "), tr("Upozornit na anomálii")]) {
+                        ui.strong(tr("Anomálie"));
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut self.config.anomaly_detection, tr("Označovat neobvyklá měření (z-skóre)"));
+                            reset_button(ui, &mut self.config.anomaly_detection, &defaults.anomaly_detection);
+                        });
+                        ui.add_enabled_ui(self.config.anomaly_detection, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.add(egui::DragValue::new(&mut self.config.anomaly_window).prefix(tr("Porovnávat s posledními (měření): ")).clamp_range(5..=500));
+                                reset_button(ui, &mut self.config.anomaly_window, &defaults.anomaly_window);
+                            });
+                            ui.horizontal(|ui| {
+                                ui.add(egui::DragValue::new(&mut self.config.anomaly_z_threshold).prefix(tr("Práh z-skóre: ")).speed(0.1).clamp_range(1.5..=10.0));
+                                reset_button(ui, &mut self.config.anomaly_z_threshold, &defaults.anomaly_z_threshold);
+                            });
+                            ui.horizontal(|ui| {
+                                ui.checkbox(&mut self.config.anomaly_alert, tr("Upozornit na anomálii"));
+                                reset_button(ui, &mut self.config.anomaly_alert, &defaults.anomaly_alert);
+                            });
+                        });
+                        ui.separator();
+                    }
+                    if show(SettingsCategory::Alerts, &[tr("Předpověď"), tr("Zobrazit předpověď z trendu"), tr("Předpověď na (min): "), tr("Trend z posledních (min): ")]) {
+                        ui.strong(tr("Předpověď"));
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut self.config.forecast_enabled, tr("Zobrazit předpověď z trendu"));
+                            reset_button(ui, &mut self.config.forecast_enabled, &defaults.forecast_enabled);
+                        });
+                        ui.add_enabled_ui(self.config.forecast_enabled, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.add(egui::DragValue::new(&mut self.config.forecast_minutes).prefix(tr("Předpověď na (min): ")).clamp_range(10..=180));
+                                reset_button(ui, &mut self.config.forecast_minutes, &defaults.forecast_minutes);
+                            });
+                            ui.horizontal(|ui| {
+                                ui.add(egui::DragValue::new(&mut self.config.forecast_window_minutes).prefix(tr("Trend z posledních (min): ")).clamp_range(10..=240));
+                                reset_button(ui, &mut self.config.forecast_window_minutes, &defaults.forecast_window_minutes);
+                            });
+                        });
+                        ui.separator();
+                    }
+                    if show(SettingsCategory::Alerts, &[tr("Oznámení"), tr("Oznámení zobrazit (s): "), tr("Varování a chyby (s): ")]) {
+                        ui.strong(tr("Oznámení"));
+                        ui.horizontal(|ui| {
+                            ui.add(egui::DragValue::new(&mut self.config.toast_secs).prefix(tr("Oznámení zobrazit (s): ")).speed(0.5).clamp_range(1.0..=60.0));
+                            reset_button(ui, &mut self.config.toast_secs, &defaults.toast_secs);
+                            ui.add(egui::DragValue::new(&mut self.config.error_toast_secs).prefix(tr("Varování a chyby (s): ")).speed(0.5).clamp_range(1.0..=300.0));
+                            reset_button(ui, &mut self.config.error_toast_secs, &defaults.error_toast_secs);
+                        });
+                        ui.separator();
+                    }
+                    // --- Zobrazení ---
+                    if show(SettingsCategory::Display, &[tr("Jazyk a jednotky"), tr("Jazyk:"), tr("Jednotka teploty:")]) {
+                        ui.strong(tr("Jazyk a jednotky"));
+                        ui.horizontal(|ui| {
+                            ui.label(tr("Jazyk:"));
+                            egui::ComboBox::from_id_source("language").selected_text(self.config.language.label()).show_ui(ui, |ui| {
+                                for language in Language::ALL { ui.selectable_value(&mut self.config.language, language, language.label()); }
+                            });
+                            reset_button(ui, &mut self.config.language, &defaults.language);
+                            ui.label(tr("Jednotka teploty:"));
+                            egui::ComboBox::from_id_source("temp_unit").selected_text(self.config.temp_unit.label()).show_ui(ui, |ui| {
+                                for unit in TempUnit::ALL { ui.selectable_value(&mut self.config.temp_unit, unit, unit.label()); }
+                            });
+                            reset_button(ui, &mut self.config.temp_unit, &defaults.temp_unit);
+                        });
+                        ui.separator();
+                    }
+                    if show(SettingsCategory::Display, &[tr("Přístupnost"), tr("Vysoký kontrast")]) {
+                        ui.strong(tr("Přístupnost"));
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut self.config.high_contrast, tr("Vysoký kontrast")).on_hover_text(tr("Černé neprůhledné pozadí, bílý text, silnější čáry a větší body v grafech"));
+                            reset_button(ui, &mut self.config.high_contrast, &defaults.high_contrast);
+                        });
+                        ui.separator();
+                    }
+                    if show(SettingsCategory::Display, &[tr("Obnovování okna"), tr("Obnovit okno každých (s): "), tr("Úsporný režim"), tr("Šetřit energii při napájení z baterie")]) {
+                        ui.strong(tr("Obnovování okna"));
+                        ui.horizontal(|ui| {
+                            ui.add_enabled(!self.config.low_power, egui::DragValue::new(&mut self.config.refresh_secs).prefix(tr("Obnovit okno každých (s): ")).clamp_range(1..=300))
+                                .on_hover_text(tr("Nová měření se zobrazí hned; toto obnovení posouvá jen údaje závislé na čase (stáří měření, doba běhu)."));
+                            reset_button(ui, &mut self.config.refresh_secs, &defaults.refresh_secs);
+                            ui.checkbox(&mut self.config.low_power, tr("Úsporný režim")).on_hover_text(trf("Okno se samo obnoví jen jednou za {} s (šetří baterii notebooku)", &[&LOW_POWER_REFRESH_SECS]));
+                            reset_button(ui, &mut self.config.low_power, &defaults.low_power);
+                        });
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut self.config.battery_saver, tr("Šetřit energii při napájení z baterie"))
+                                .on_hover_text(trf("Notebook odpojený od sítě: skenování bez kontinuálního režimu a s {}× delší pauzou, obnova okna jako v úsporném režimu, bez obnovování teploty v ikoně na hlavním panelu; do jiných výstupů než denního CSV logu se měření zapíšou až po připojení k síti (upozornění hned). Po připojení k síti se vše vrátí.", &[&BATTERY_SCAN_PAUSE_FACTOR]));
+                            reset_button(ui, &mut self.config.battery_saver, &defaults.battery_saver);
+                        });
+                        if self.config.battery_saving() { ui.label(tr("🔋 Počítač teď běží z baterie, úspora je zapnutá.")); }
+                        ui.separator();
+                    }
+                    if show(SettingsCategory::Display, &[tr("Pásmo komfortu"), tr("Zobrazit pásmo komfortu v grafech"), tr("Teplota od"), tr("Vlhkost od (%): ")]) {
+                        ui.strong(tr("Pásmo komfortu"));
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut self.config.show_comfort_band, tr("Zobrazit pásmo komfortu v grafech"));
+                            reset_button(ui, &mut self.config.show_comfort_band, &defaults.show_comfort_band);
+                        });
+                        ui.add_enabled_ui(self.config.show_comfort_band, |ui| {
+                            ui.horizontal(|ui| {
+                                temp_drag_value(ui, &mut self.config.comfort_temp_min, tr("Teplota od"), 0.1, false);
+                                temp_drag_value(ui, &mut self.config.comfort_temp_max, tr("do"), 0.1, false);
+                                let mut range = (self.config.comfort_temp_min, self.config.comfort_temp_max);
+                                reset_button(ui, &mut range, &(defaults.comfort_temp_min, defaults.comfort_temp_max));
+                                (self.config.comfort_temp_min, self.config.comfort_temp_max) = range;
+                            });
+                            ui.horizontal(|ui| {
+                                ui.add(egui::DragValue::new(&mut self.config.comfort_hum_min).prefix(tr("Vlhkost od (%): ")).clamp_range(0..=100));
+                                ui.add(egui::DragValue::new(&mut self.config.comfort_hum_max).prefix(tr("do: ")).clamp_range(0..=100));
+                                let mut range = (self.config.comfort_hum_min, self.config.comfort_hum_max);
+                                reset_button(ui, &mut range, &(defaults.comfort_hum_min, defaults.comfort_hum_max));
+                                (self.config.comfort_hum_min, self.config.comfort_hum_max) = range;
+                            });
+                        });
+                        ui.separator();
+                    }
+                    if show(SettingsCategory::Display, &[tr("Výpočty"), tr("Základ denostupňů"), tr("Aktivační energie MKT (kJ/mol): ")]) {
+                        ui.strong(tr("Výpočty"));
+                        ui.horizontal(|ui| {
+                            temp_drag_value(ui, &mut self.config.degree_day_base, tr("Základ denostupňů"), 0.1, false);
+                            reset_button(ui, &mut self.config.degree_day_base, &defaults.degree_day_base);
+                        });
+                        ui.horizontal(|ui| {
+                            ui.add(egui::DragValue::new(&mut self.config.mkt_activation_energy).prefix(tr("Aktivační energie MKT (kJ/mol): ")).speed(0.1).clamp_range(10.0..=300.0));
+                            reset_button(ui, &mut self.config.mkt_activation_energy, &defaults.mkt_activation_energy);
+                        });
+                        ui.separator();
+                    }
+                    if show(SettingsCategory::Display, &[tr("Barvy grafů"), tr("Předvolba:"), tr("Barva čáry"), tr("Barva bodů"), tr("Body podle hodnoty")]) {
+                        ui.strong(tr("Barvy grafů"));
+                        ui.horizontal(|ui| {
+                            ui.label(tr("Předvolba:"));
+                            egui::ComboBox::from_id_source("style_preset").selected_text(tr("Vyberte…")).show_ui(ui, |ui| {
+                                for preset in StylePreset::ALL {
+                                    if ui.button(preset.label()).clicked() { (self.config.temp_style, self.config.hum_style) = preset.styles(); ui.close_menu(); }
+                                }
+                            });
+                        });
+                        egui::Grid::new("series_style_grid").num_columns(5).show(ui, |ui| {
+                            for (name, style, default) in [(tr("Teplota"), &mut self.config.temp_style, &defaults.temp_style), (tr("Vlhkost"), &mut self.config.hum_style, &defaults.hum_style)] {
+                                ui.label(name);
+                                ui.color_edit_button_srgb(&mut style.line).on_hover_text(tr("Barva čáry"));
+                                let mut by_value = style.points.is_none();
+                                if ui.checkbox(&mut by_value, tr("Body podle hodnoty")).changed() { style.points = if by_value { None } else { Some(style.line) }; }
+                                if let Some(color) = style.points.as_mut() { ui.color_edit_button_srgb(color).on_hover_text(tr("Barva bodů")); }
+                                egui::ComboBox::from_id_source(("marker", name)).selected_text(style.marker.label()).show_ui(ui, |ui| {
+                                    for marker in MarkerStyle::ALL { ui.selectable_value(&mut style.marker, marker, marker.label()); }
+                                });
+                                reset_button(ui, style, default);
+                                ui.end_row();
+                            }
+                        });
+                        ui.separator();
+                    }
+                    // --- Integrace ---
+                    #[cfg(windows)]
+                    {
+                        if show(SettingsCategory::Integrations, &[tr("Oznamovací oblast a hlavní panel"), tr("Zobrazit ikonu v oznamovací oblasti"), tr("Minimalizovat do oznamovací oblasti"), tr("Zavřením okna jen schovat do oznamovací oblasti"), tr("Zobrazit teplotu v ikoně na hlavním panelu")]) {
+                            ui.strong(tr("Oznamovací oblast a hlavní panel"));
+                            ui.horizontal(|ui| {
+                                ui.checkbox(&mut self.config.tray_icon, tr("Zobrazit ikonu v oznamovací oblasti"));
+                                reset_button(ui, &mut self.config.tray_icon, &defaults.tray_icon);
+                            });
+                            ui.add_enabled_ui(self.config.tray_icon, |ui| {
+                                ui.horizontal(|ui| {
+                                    ui.checkbox(&mut self.config.minimize_to_tray, tr("Minimalizovat do oznamovací oblasti"));
+                                    reset_button(ui, &mut self.config.minimize_to_tray, &defaults.minimize_to_tray);
+                                });
+                                ui.horizontal(|ui| {
+                                    ui.checkbox(&mut self.config.close_to_tray, tr("Zavřením okna jen schovat do oznamovací oblasti"));
+                                    reset_button(ui, &mut self.config.close_to_tray, &defaults.close_to_tray);
+                                });
+                            });
+                            if self.config.tray_icon && self.config.close_to_tray { ui.label(tr("Aplikaci pak ukončíte přes Soubor → Ukončit nebo menu ikony.")); }
+                            ui.horizontal(|ui| {
+                                ui.checkbox(&mut self.config.taskbar_badge, tr("Zobrazit teplotu v ikoně na hlavním panelu"));
+                                reset_button(ui, &mut self.config.taskbar_badge, &defaults.taskbar_badge);
+                            });
+                            ui.separator();
+                        }
+                    }
+                    #[cfg(any(windows, target_os = "linux"))]
+                    if show(SettingsCategory::Integrations, &[tr("Spouštění"), tr(AUTOSTART_LABEL), tr("Po přihlášení spustit schované v oznamovací oblasti")]) {
+                        ui.strong(tr("Spouštění"));
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut self.config.autostart, tr(AUTOSTART_LABEL));
+                            reset_button(ui, &mut self.config.autostart, &defaults.autostart);
+                        });
+                        // Schované spuštění potřebuje ikonu v oznamovací oblasti, ta je zatím jen na Windows
+                        #[cfg(windows)]
+                        ui.add_enabled_ui(self.config.autostart && self.config.tray_icon, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.checkbox(&mut self.config.start_minimized, tr("Po přihlášení spustit schované v oznamovací oblasti"));
+                                reset_button(ui, &mut self.config.start_minimized, &defaults.start_minimized);
+                            });
+                        });
+                        ui.separator();
+                    }
+                    #[cfg(not(any(windows, target_os = "linux")))]
+                    if show(SettingsCategory::Integrations, &[]) { ui.label(tr("V tomto systému nejsou k dispozici žádné integrace.")); }
+                    if !matched.get() { ui.label(tr("Hledanému textu neodpovídá žádné nastavení.")); }
+                });
+                ui.separator();
+                let modified = self.config != saved;
+                ui.horizontal(|ui| {
+                    if ui.button(tr("OK")).clicked() { apply = true; close = true; }
+                    if ui.add_enabled(modified, egui::Button::new(tr("Použít"))).clicked() { apply = true; }
+                    if ui.button(tr("Zrušit")).clicked() { close = true; }
+                    if ui.button(tr("Výchozí hodnoty")).on_hover_text(tr("Vrátí všechna nastavení kromě adres senzorů; uloží se až tlačítkem OK nebo Použít")).clicked() {
+                        self.config = Config { target_mac: self.config.target_mac.clone(), extra_devices: self.config.extra_devices.clone(), ..Config::default() };
+                    }
+                    if modified { ui.label(egui::RichText::new(tr("Neuložené změny")).color(egui::Color32::YELLOW)); }
+                });
+            });
+            let draft = std::mem::replace(&mut self.config, saved);
+            if apply { self.apply_settings(draft.clone()); }
+            // Zavřením křížkem se změny zahodí stejně jako tlačítkem Zrušit
+            if close || !is_open {
+                if !apply && draft != self.config { info!("Změny v nastavení zahozeny."); }
+                // Neplatné rozepsané MAC adresy se při dalším otevření nemají vrátit
+                ctx.data_mut(|d| {
+                    d.remove::<String>(egui::Id::new("target_mac"));
+                    for i in 0..=draft.extra_devices.len() { d.remove::<String>(egui::Id::new(format!("extra_mac_{}", i))); }
+                });
+                self.settings_open = false;
+            } else { self.settings_draft = Some(draft); }
+        }
+    }
+}
+
+// Tlačítko vedle položky nastavení, které ji vrátí na výchozí hodnotu; u nezměněné položky se nezobrazí
+fn reset_button<T: PartialEq + Clone>(ui: &mut egui::Ui, value: &mut T, default: &T) {
+    if *value != *default && ui.small_button("↺").on_hover_text(tr("Vrátit výchozí hodnotu")).clicked() { *value = default.clone(); }
+}
+
+// Pole pro MAC adresu: rozepsaný text drží v paměti egui a do `mac` zapíše až platnou, normalizovanou adresu.
+// Vrací chybu k zobrazení u pole (prázdné pole je chyba jen u povinné adresy).
+// Čas ve tvaru hodiny:minuty pro časová okna
+fn time_edit(ui: &mut egui::Ui, time: &mut NaiveTime) {
+    let (mut hour, mut minute) = (time.hour(), time.minute());
+    let hour_changed = ui.add(egui::DragValue::new(&mut hour).clamp_range(0..=23).custom_formatter(|n, _| format!("{:02}", n))).changed();
+    ui.label(":");
+    let minute_changed = ui.add(egui::DragValue::new(&mut minute).clamp_range(0..=59).custom_formatter(|n, _| format!("{:02}", n))).changed();
+    if hour_changed || minute_changed { if let Some(new_time) = NaiveTime::from_hms_opt(hour, minute, 0) { *time = new_time; } }
+}
+
+fn mac_edit(ui: &mut egui::Ui, id: &str, mac: &mut String, width: f32, required: bool) -> Option<String> {
+    let id = egui::Id::new(id);
+    let mut draft = ui.data_mut(|d| d.get_temp::<String>(id)).unwrap_or_else(|| mac.clone());
+    let normalized = normalize_mac(&draft);
+    let error = match &normalized {
+        None if !draft.trim().is_empty() || required => Some(tr(if cfg!(target_os = "macos") { "Neplatné UUID (např. 1B2C3D4E-5F60-7182-93A4-B5C6D7E8F901)" } else { "Neplatná MAC adresa (např. B8:59:CE:33:0F:93)" }).to_string()),
+        _ => None,
+    };
+    let text_color = if error.is_some() { egui::Color32::RED } else { ui.visuals().text_color() };
+    let response = ui.add(egui::TextEdit::singleline(&mut draft).hint_text(tr("MAC adresa")).text_color(text_color).desired_width(width));
+    if response.changed() {
+        if let Some(normalized) = normalize_mac(&draft) { *mac = normalized; } else if draft.trim().is_empty() && !required { mac.clear(); }
+    }
+    // Po opuštění pole se platná adresa zobrazí v normalizovaném tvaru; neplatný text zůstane k opravě
+    if !response.has_focus() && normalize_mac(&draft).is_some() { draft = mac.clone(); }
+    ui.data_mut(|d| d.insert_temp(id, draft));
+    error
+}
+
+// Posuvník pro hodnotu uloženou ve °C, který ji zobrazuje a upravuje ve zvolené jednotce
+fn temp_drag_value(ui: &mut egui::Ui, value: &mut f32, prefix: &str, speed: f64, is_delta: bool) -> egui::Response {
+    let unit = temp_unit();
+    let mut shown = if is_delta { unit.delta(*value as f64) } else { unit.convert(*value as f64) };
+    let response = ui.add(egui::DragValue::new(&mut shown).prefix(format!("{} ({}): ", prefix, unit.symbol())).speed(speed).max_decimals(2));
+    if response.changed() { *value = if is_delta { (shown / unit.delta(1.0)).max(0.1) as f32 } else { unit.to_celsius(shown) as f32 }; }
+    response
+}
@@ -0,0 +1,184 @@
+// --- Panel statistik ---
+// Statistiky zvoleného období z denních logů (načítané na pozadí) a souhrn úseku vybraného v grafu.
+use crate::{Period, StatsCache, TempMonitorApp};
+use crate::stats::{data_quality, degree_days, mean_kinetic_temperature, summarize_points, time_in_range, DataQuality, DaySummary, TimeInRange};
+use crate::views::format_duration_short;
+use chrono::{DateTime, Local, NaiveDate};
+use eframe::egui;
+use std::sync::mpsc;
+use std::thread;
+use log::error;
+use temp_monitor::i18n::{tr, trf};
+use temp_monitor::storage::{log_filename_for_date, read_history_file, read_history_file_counted, HistoryPoint};
+use temp_monitor::units::{format_temp, temp_unit};
+
+impl TempMonitorApp {
+    // Souhrn dne z denního logu (s mezipamětí sdílenou s kalendářem)
+    pub fn day_summary(&mut self, date: NaiveDate) -> Option<DaySummary> {
+        self.day_summaries.entry(date).or_insert_with(|| read_history_file(&log_filename_for_date(date)).and_then(|points| summarize_points(&points))).clone()
+    }
+
+    // Kvalita záznamu dne z denního logu (s mezipamětí)
+    fn day_quality(&mut self, date: NaiveDate) -> Option<DataQuality> {
+        self.day_quality.entry(date).or_insert_with(|| read_history_file_counted(&log_filename_for_date(date)).and_then(|(points, errors)| data_quality(&points, errors))).clone()
+    }
+
+    // Měření období statistik: denní logy se načtou na pozadí, nová měření se pak jen připojují
+    fn stats_points(&mut self) -> &[HistoryPoint] {
+        let period = self.stats_period;
+        if self.stats_cache.as_ref().is_none_or(|c| c.period != period) {
+            self.poll_stats_loader(period);
+            return self.stats_cache.as_ref().filter(|c| c.period == period).map_or(&[], |c| &c.points);
+        }
+        let start = period.start();
+        if let Some(cache) = self.stats_cache.as_mut() {
+            if self.recent_points.back().map(|p| p.timestamp) > cache.last {
+                cache.points.extend(self.recent_points.iter().filter(|p| cache.last.is_none_or(|last| p.timestamp > last)).cloned());
+                cache.last = cache.points.last().map(|p| p.timestamp);
+            }
+            if let Some(start) = start {
+                let old = cache.points.partition_point(|p| p.timestamp < start);
+                cache.points.drain(..old);
+            }
+        }
+        self.stats_cache.as_ref().map_or(&[], |c| &c.points)
+    }
+
+    // Spustí nebo dokončí načtení období statistik z denních logů
+    fn poll_stats_loader(&mut self, period: Period) {
+        if self.stats_loader.as_ref().is_none_or(|(p, _)| *p != period) {
+            let (tx, rx) = mpsc::channel();
+            thread::spawn(move || {
+                let start = period.start();
+                let points: Vec<HistoryPoint> = period.days().into_iter().filter_map(|d| read_history_file(&log_filename_for_date(d))).flatten()
+                    .filter(|p| start.is_none_or(|s| p.timestamp >= s)).collect();
+                let _ = tx.send(points);
+            });
+            self.stats_loader = Some((period, rx));
+        }
+        let Some((_, rx)) = &self.stats_loader else { return; };
+        match rx.try_recv() {
+            Ok(points) => {
+                self.stats_loader = None;
+                let last = points.last().map(|p| p.timestamp);
+                self.stats_cache = Some(StatsCache { period, last, points });
+            }
+            Err(mpsc::TryRecvError::Empty) => {}
+            Err(mpsc::TryRecvError::Disconnected) => {
+                error!("Načítání statistik na pozadí selhalo.");
+                self.stats_loader = None;
+                self.stats_cache = Some(StatsCache { period, last: None, points: Vec::new() });
+            }
+        }
+    }
+
+    pub fn draw_stats_panel(&mut self, ui: &mut egui::Ui) {
+        ui.heading(tr("Statistiky"));
+        egui::ComboBox::from_id_source("stats_period").selected_text(self.stats_period.label()).show_ui(ui, |ui| {
+            for period in Period::ALL { ui.selectable_value(&mut self.stats_period, period, period.label()); }
+        });
+        ui.separator();
+        let (low, high) = (self.config.temp_warn_low, self.config.temp_warn_high);
+        let activation_energy = self.config.mkt_activation_energy as f64;
+        let mkt = mean_kinetic_temperature(self.stats_points(), activation_energy);
+        if self.stats_loader.is_some() { ui.horizontal(|ui| { ui.spinner(); ui.label(tr("Načítám historii…")); }); }
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(tr("Střední kinetická teplota:")).strong());
+            ui.label(mkt.map_or("N/A".to_string(), |t| format_temp(t, 2)));
+        }).response.on_hover_text(trf("MKT podle Haynese, ΔH = {} kJ/mol, váženo dobou mezi měřeními", &[&format!("{:.3}", activation_energy)]));
+        ui.separator();
+        let tir = time_in_range(self.stats_points(), low, high);
+        ui.label(egui::RichText::new(trf("Čas v mezích ({} – {})", &[&format_temp(low as f64, 1), &format_temp(high as f64, 1)])).strong());
+        if tir.total() > 0.0 {
+            draw_time_in_range_bar(ui, &tir);
+            egui::Grid::new("time_in_range_grid").num_columns(3).show(ui, |ui| {
+                for (label, secs, color) in [("Nad horní mezí", tir.above, egui::Color32::GOLD), ("V mezích", tir.within, egui::Color32::from_rgb(80, 200, 120)), ("Pod spodní mezí", tir.below, egui::Color32::from_rgb(120, 180, 255))] {
+                    ui.label(egui::RichText::new(tr(label)).color(color));
+                    ui.label(format!("{:.1} %", secs / tir.total() * 100.0));
+                    ui.label(format!("{:.1} h", secs / 3600.0));
+                    ui.end_row();
+                }
+            });
+            ui.label(egui::RichText::new(trf("Pokryto {} h měření", &[&format!("{:.1}", tir.total() / 3600.0)])).color(egui::Color32::GRAY));
+        } else { ui.label(tr("Žádná data pro zvolené období.")); }
+        ui.separator();
+        // Dnešek se měří podle současného nastavení; minulé dny podle rozestupu, se kterým se tehdy zapisovalo
+        // (jiné nastavení nebo úspora na baterii), aby se jejich hodnocení se změnou nastavení neměnilo
+        let (interval, today) = (self.config.expected_interval_secs(), Local::now().date_naive());
+        ui.label(egui::RichText::new(tr("Kvalita dat")).strong()).on_hover_text(trf("Dnes se očekává jedno měření každých {} s mezi prvním a posledním měřením, u minulých dnů podle obvyklého rozestupu jejich měření", &[&interval]));
+        let quality: Vec<(NaiveDate, DataQuality)> = self.stats_period.days().into_iter().filter_map(|d| self.day_quality(d).map(|q| (d, q))).collect();
+        if quality.is_empty() { ui.label(tr("Žádná data pro zvolené období.")); } else {
+            egui::Grid::new("data_quality_grid").striped(true).num_columns(5).show(ui, |ui| {
+                for header in ["Den", "Přijato", "ø interval", "Max. mezera", "Chyby"] { ui.label(egui::RichText::new(tr(header)).color(egui::Color32::GRAY)); }
+                ui.end_row();
+                for (date, q) in &quality {
+                    let day_interval = if *date == today { interval } else { q.typical_interval.unwrap_or(interval) };
+                    let expected = q.expected(day_interval);
+                    let ratio = q.received as f64 / expected as f64 * 100.0;
+                    let color = if ratio >= 90.0 { egui::Color32::from_rgb(80, 200, 120) } else if ratio >= 60.0 { egui::Color32::GOLD } else { egui::Color32::from_rgb(255, 100, 100) };
+                    ui.label(date.format("%d.%m.").to_string());
+                    ui.label(egui::RichText::new(format!("{} / {}", q.received, expected)).color(color)).on_hover_text(trf("{} % očekávaných měření (jedno každých {} s)", &[&format!("{:.0}", ratio), &day_interval]));
+                    ui.label(q.avg_interval().map_or("N/A".to_string(), |secs| format!("{:.0} s", secs)));
+                    ui.label(format_duration_short(q.longest_gap));
+                    ui.label(format!("{:.1} %", q.error_rate())).on_hover_text(trf("{} nečitelných řádků v logu", &[&q.parse_errors]));
+                    ui.end_row();
+                }
+            });
+        }
+        if self.rejected_count > 0 { ui.label(egui::RichText::new(trf("Od spuštění odmítnuto {} podezřelých měření.", &[&self.rejected_count])).color(egui::Color32::GRAY)); }
+        ui.separator();
+        let base = self.config.degree_day_base as f64;
+        let unit = temp_unit();
+        ui.label(egui::RichText::new(trf("Denostupně (základ {})", &[&format_temp(base, 1)])).strong());
+        let days: Vec<(NaiveDate, DaySummary)> = self.stats_period.days().into_iter().filter_map(|d| self.day_summary(d).map(|s| (d, s))).collect();
+        if days.is_empty() { ui.label(tr("Žádná data pro zvolené období.")); return; }
+        let (mut total_hdd, mut total_cdd) = (0.0, 0.0);
+        egui::Grid::new("degree_days_grid").striped(true).num_columns(4).show(ui, |ui| {
+            for header in [tr("Den").to_string(), format!("ø {}", unit.symbol()), tr("Vytápění").to_string(), tr("Chlazení").to_string()] { ui.label(egui::RichText::new(header).color(egui::Color32::GRAY)); }
+            ui.end_row();
+            for (date, summary) in &days {
+                let (hdd, cdd) = degree_days(summary.avg_temp, base);
+                total_hdd += hdd; total_cdd += cdd;
+                ui.label(date.format("%d.%m.").to_string());
+                ui.label(format!("{:.1}", unit.convert(summary.avg_temp)));
+                ui.label(format!("{:.1}", unit.delta(hdd)));
+                ui.label(format!("{:.1}", unit.delta(cdd)));
+                ui.end_row();
+            }
+        });
+        ui.label(egui::RichText::new(trf("Celkem vytápění: {} {}·d", &[&format!("{:.1}", unit.delta(total_hdd)), &unit.symbol()])).strong());
+        ui.label(egui::RichText::new(trf("Celkem chlazení: {} {}·d", &[&format!("{:.1}", unit.delta(total_cdd)), &unit.symbol()])).strong());
+    }
+
+    // Malé okno se souhrnem vybraného úseku; zavřením zmizí i vyznačení v grafech
+    pub fn draw_range_stats_window(&mut self, ctx: &egui::Context) {
+        let Some(stats) = &self.range_stats else { return; };
+        let time = |x: f64| DateTime::from_timestamp(x as i64, 0).unwrap_or_default().with_timezone(&Local);
+        let mut open = true;
+        egui::Window::new(tr("Vybraný úsek")).open(&mut open).collapsible(false).resizable(false).anchor(egui::Align2::RIGHT_TOP, egui::vec2(-20.0, 80.0)).show(ctx, |ui| {
+            ui.label(format!("{} – {}", time(stats.from).format("%d.%m. %H:%M:%S"), time(stats.to).format("%d.%m. %H:%M:%S")));
+            ui.label(trf("Trvání: {}, měření: {}", &[&format_duration_short(stats.to - stats.from), &stats.count]));
+            egui::Grid::new("range_stats_grid").num_columns(4).show(ui, |ui| {
+                ui.label(""); ui.label(tr("Minimum")); ui.label(tr("Maximum")); ui.label(tr("Průměr"));
+                ui.end_row();
+                ui.label(tr("Teplota"));
+                for value in [stats.temp.0 as f64, stats.temp.1 as f64, stats.temp.2] { ui.label(format_temp(value, 1)); }
+                ui.end_row();
+                ui.label(tr("Vlhkost"));
+                for value in [stats.hum.0 as f64, stats.hum.1 as f64, stats.hum.2] { ui.label(format!("{:.0}%", value)); }
+                ui.end_row();
+            });
+        });
+        if !open { self.range_stats = None; }
+    }
+}
+
+fn draw_time_in_range_bar(ui: &mut egui::Ui, tir: &TimeInRange) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width(), 14.0), egui::Sense::hover());
+    let mut x = rect.left();
+    for (secs, color) in [(tir.below, egui::Color32::from_rgb(120, 180, 255)), (tir.within, egui::Color32::from_rgb(80, 200, 120)), (tir.above, egui::Color32::GOLD)] {
+        let width = (secs / tir.total()) as f32 * rect.width();
+        ui.painter().rect_filled(egui::Rect::from_min_size(egui::pos2(x, rect.top()), egui::vec2(width, rect.height())), 0.0, color);
+        x += width;
+    }
+}
@@ -0,0 +1,93 @@
+// --- Stavový řádek, konzole a oznámení ---
+use crate::{console, open_folder, Severity, TempMonitorApp};
+use crate::views::format_duration_short;
+use chrono::Local;
+use eframe::egui;
+use std::path::Path;
+use std::time::Duration;
+use log::error;
+use temp_monitor::i18n::{tr, trf};
+use temp_monitor::storage::get_daily_log_filename;
+
+// Stránka Bluetooth v nastavení soukromí macOS (otevře se stejně jako složka, příkazem open)
+const MACOS_BLUETOOTH_PRIVACY_URL: &str = "x-apple.systempreferences:com.apple.preference.security?Privacy_Bluetooth";
+
+impl TempMonitorApp {
+    // Stavový řádek: doba běhu, dnešní měření, body v paměti, dnešní log a stav skeneru
+    pub fn draw_status_bar(&mut self, ui: &mut egui::Ui) {
+        let today = Local::now().date_naive();
+        let today_count = self.recent_points.iter().filter(|p| p.timestamp.date_naive() == today).count();
+        let log_size = self.log_file_size.map_or(tr("nevytvořen").to_string(), |bytes| format!("{:.1} kB", bytes as f64 / 1024.0));
+        ui.horizontal(|ui| {
+            ui.label(trf("Běží {}", &[&format_duration_short(self.started_at.elapsed().as_secs_f64())]));
+            ui.separator();
+            ui.label(trf("Dnes měření: {}", &[&today_count]));
+            ui.separator();
+            match self.history_pager.paged_points() {
+                0 => ui.label(trf("V paměti: {} bodů", &[&self.history.len()])),
+                paged => ui.label(trf("V paměti: {} bodů (+{} z disku)", &[&self.history.len(), &paged])),
+            };
+            ui.separator();
+            ui.label(format!("{} ({})", get_daily_log_filename(), log_size));
+            ui.separator();
+            ui.label(trf("Skener: {}", &[&self.scan_status]));
+            if cfg!(target_os = "macos") && self.bluetooth_permission_missing && ui.small_button(tr("Otevřít nastavení soukromí")).clicked() {
+                if let Err(e) = open_folder(Path::new(MACOS_BLUETOOTH_PRIVACY_URL)) { error!("Nelze otevřít nastavení soukromí: {}", e); }
+            }
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.label(egui::RichText::new(trf("Autorem aplikace je {}", &[&"Soběslav Holec"])).color(egui::Color32::GRAY));
+                ui.separator();
+                let unseen = console::total().saturating_sub(self.console_seen);
+                let text = if unseen > 0 { egui::RichText::new(format!("⚠ {}", unseen)).color(egui::Color32::YELLOW) } else { egui::RichText::new("⚠") };
+                ui.toggle_value(&mut self.console_open, text).on_hover_text(tr("Varování a chyby"));
+            });
+        });
+    }
+
+    // Varování a chyby zachycené loggerem, nejnovější nahoře
+    pub fn draw_console(&mut self, ui: &mut egui::Ui) {
+        self.console_seen = console::total();
+        let entries = console::entries();
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(tr("Varování a chyby")).strong());
+            if ui.small_button(tr("Vymazat")).clicked() { console::clear(); }
+            if ui.small_button(tr("Kopírovat")).clicked() {
+                let text: Vec<String> = entries.iter().map(|e| format!("{} [{}] {}", e.timestamp.format("%d.%m.%Y %H:%M:%S"), e.level, e.message)).collect();
+                ui.ctx().output_mut(|o| o.copied_text = text.join("\n"));
+            }
+            if ui.small_button(tr("Celý protokol…")).clicked() { self.log_window_open = true; }
+        });
+        egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+            if entries.is_empty() { ui.label(egui::RichText::new(tr("Žádná varování ani chyby.")).color(egui::Color32::GRAY)); }
+            for entry in entries.iter().rev() {
+                let color = if entry.level == log::Level::Error { egui::Color32::from_rgb(255, 90, 90) } else { egui::Color32::GOLD };
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(egui::RichText::new(entry.timestamp.format("%d.%m. %H:%M:%S").to_string()).color(egui::Color32::GRAY).monospace());
+                    ui.label(egui::RichText::new(&entry.message).color(color));
+                });
+            }
+        });
+    }
+
+    // Oznámení nad sebou u spodního okraje, nejnovější dole; kliknutím se zavřou
+    pub fn draw_toasts(&mut self, ctx: &egui::Context) {
+        let (info_secs, error_secs) = (self.config.toast_secs, self.config.error_toast_secs);
+        self.toasts.retain(|t| t.created_at.elapsed().as_secs_f32() < if matches!(t.severity, Severity::Warning | Severity::Error) { error_secs } else { info_secs });
+        if self.toasts.is_empty() { return; }
+        let mut dismissed = None;
+        egui::Area::new("toast_area".into()).anchor(egui::Align2::CENTER_BOTTOM, egui::vec2(0.0, -30.0)).show(ctx, |ui| {
+            for (i, toast) in self.toasts.iter().enumerate() {
+                let color = toast.severity.color();
+                let response = egui::Frame::popup(ui.style()).stroke(egui::Stroke::new(1.5, color)).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(toast.severity.icon()).color(color).strong());
+                        ui.label(&toast.message);
+                    });
+                }).response.interact(egui::Sense::click());
+                if response.on_hover_text(tr("Kliknutím zavřete")).clicked() { dismissed = Some(i); }
+            }
+        });
+        if let Some(i) = dismissed { self.toasts.remove(i); }
+        ctx.request_repaint_after(Duration::from_millis(250));
+    }
+}
@@ -0,0 +1,121 @@
+// --- Tabulka měření ---
+// Řazení, filtr podmínkami na hodnoty a kopírování řádků do schránky.
+use crate::{Period, Severity, TableCache, TableColumn, TempMonitorApp};
+use eframe::egui;
+use log::{info, error};
+use temp_monitor::i18n::{tr, trf};
+use temp_monitor::storage::{export_readings, HistoryPoint};
+use temp_monitor::units::{format_temp, temp_unit};
+
+// Jedna podmínka filtru; teplota je uložená ve °C bez ohledu na zobrazenou jednotku
+#[derive(Clone, Copy, Debug)]
+struct Condition { column: TableColumn, op: &'static str, value: f64 }
+
+impl Condition {
+    fn matches(&self, point: &HistoryPoint) -> bool {
+        let value = match self.column { TableColumn::Temp => point.temp as f64, TableColumn::Hum => point.hum as f64, TableColumn::Rssi => match point.rssi { Some(rssi) => rssi as f64, None => return false }, TableColumn::Time => return true };
+        match self.op { "<=" => value <= self.value, ">=" => value >= self.value, "!=" => (value - self.value).abs() > 1e-6, "<" => value < self.value, ">" => value > self.value, _ => (value - self.value).abs() <= 1e-6 }
+    }
+}
+
+impl TempMonitorApp {
+    // Řádky tabulky po filtru, jen pokud odpovídají aktuální historii
+    pub fn table_selection(&self) -> Option<&[usize]> {
+        let cache = self.table_cache.as_ref()?;
+        (cache.key.2 == self.history.len() && cache.key.3 == self.history.front().map_or(0, |p| p.timestamp.timestamp())).then_some(cache.order.as_slice())
+    }
+}
+
+// Živý rozdíl dvou senzorů (A − B) a jeho průběh za posledních 24 h
+// Tabulka všech načtených měření; vykreslují se jen viditelné řádky
+pub fn draw_table_view(app: &mut TempMonitorApp, ui: &mut egui::Ui) {
+    use egui_extras::{Column, TableBuilder};
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_source("table_period").selected_text(app.table_filter.period.label()).show_ui(ui, |ui| {
+            for period in Period::ALL { ui.selectable_value(&mut app.table_filter.period, period, period.label()); }
+        });
+        ui.add(egui::TextEdit::singleline(&mut app.table_filter.conditions).hint_text(tr("např. hum > 70 a temp < 18")).desired_width(260.0))
+            .on_hover_text(tr("Veličiny temp, hum a rssi; operátory < <= > >= = !=; podmínky spojte „a“. Teplota se zadává v zobrazené jednotce."));
+        ui.checkbox(&mut app.table_highlight, tr("Zvýraznit v grafech"));
+    });
+    let (sort_column, descending) = app.table_sort;
+    let key = (sort_column, descending, app.history.len(), app.history.front().map_or(0, |p| p.timestamp.timestamp()));
+    if app.table_cache.as_ref().map(|c| (c.key, &c.filter)) != Some((key, &app.table_filter)) {
+        let start = app.table_filter.period.start();
+        let (conditions, error) = match parse_conditions(&app.table_filter.conditions) { Ok(conditions) => (conditions, None), Err(e) => (Vec::new(), Some(e)) };
+        let mut order: Vec<usize> = (0..app.history.len()).filter(|&i| {
+            let point = &app.history[i];
+            start.is_none_or(|start| point.timestamp >= start) && conditions.iter().all(|c| c.matches(point))
+        }).collect();
+        order.sort_by(|&a, &b| { let ordering = sort_column.compare(&app.history[a], &app.history[b]); if descending { ordering.reverse() } else { ordering } });
+        app.table_cache = Some(TableCache { key, filter: app.table_filter.clone(), order, error });
+    }
+    let Some(cache) = &app.table_cache else { return; };
+    let (mut export, mut copied) = (false, false);
+    ui.horizontal(|ui| {
+        ui.label(trf("Počet měření: {}", &[&cache.order.len()]));
+        if let Some(error) = &cache.error { ui.label(egui::RichText::new(error).color(egui::Color32::RED)); }
+        export = ui.add_enabled(!cache.order.is_empty(), egui::Button::new(tr("Exportovat výběr do CSV"))).clicked();
+        if ui.add_enabled(!cache.order.is_empty(), egui::Button::new(tr("Kopírovat jako TSV"))).on_hover_text(tr("Zkopíruje zobrazené řádky pro vložení do tabulky")).clicked() {
+            ui.ctx().output_mut(|o| o.copied_text = readings_tsv(cache.order.iter().map(|&i| &app.history[i])));
+            copied = true;
+        }
+    });
+    let mut toast = copied.then(|| (Severity::Success, trf("Zkopírováno {} měření (TSV).", &[&cache.order.len()])));
+    if export {
+        toast = Some(match export_readings(&app.history, &cache.order) {
+            Ok(filename) => { info!("Výběr z tabulky ({} měření) uložen do '{}'.", cache.order.len(), filename); (Severity::Success, trf("Výběr uložen do '{}'.", &[&filename])) }
+            Err(e) => { error!("Export výběru selhal: {}", e); (Severity::Error, trf("Export se nezdařil: {}", &[&e])) }
+        });
+    }
+    let mut clicked = None;
+    TableBuilder::new(ui).striped(true).column(Column::auto().at_least(160.0)).columns(Column::auto().at_least(110.0), 3)
+        .header(24.0, |mut header| {
+            for column in TableColumn::ALL {
+                header.col(|ui| {
+                    let arrow = if column != sort_column { "" } else if descending { " ⏷" } else { " ⏶" };
+                    if ui.button(format!("{}{}", column.label(), arrow)).clicked() { clicked = Some(column); }
+                });
+            }
+        })
+        .body(|body| {
+            body.rows(20.0, cache.order.len(), |mut row| {
+                let point = &app.history[cache.order[row.index()]];
+                row.col(|ui| { ui.label(point.timestamp.format("%d.%m.%Y %H:%M:%S").to_string()); });
+                row.col(|ui| { ui.label(format_temp(point.temp as f64, 1)); });
+                row.col(|ui| { ui.label(format!("{} %", point.hum)); });
+                row.col(|ui| { ui.label(point.rssi.map_or("–".to_string(), |rssi| rssi.to_string())); });
+            });
+        });
+    // Opakovaným kliknutím na stejný sloupec se obrátí směr řazení
+    if let Some(column) = clicked { app.table_sort = if column == sort_column { (column, !descending) } else { (column, column == TableColumn::Time) }; }
+    if let Some((severity, message)) = toast { app.toast(severity, message); }
+}
+
+// Podmínky oddělené „a“ / „and“ / „&&“ / „;“; desetinná čárka je povolená
+fn parse_conditions(text: &str) -> Result<Vec<Condition>, String> {
+    let text = text.to_lowercase().replace("&&", ";").replace(" and ", ";").replace(" a ", ";");
+    text.split(';').map(str::trim).filter(|clause| !clause.is_empty()).map(|clause| {
+        let op = ["<=", ">=", "!=", "<", ">", "="].into_iter().find(|op| clause.contains(op)).ok_or_else(|| trf("Neplatná podmínka „{}“", &[&clause]))?;
+        let (field, value) = clause.split_once(op).unwrap_or_default();
+        let column = match field.trim() {
+            "temp" | "t" | "teplota" => TableColumn::Temp,
+            "hum" | "h" | "vlhkost" => TableColumn::Hum,
+            "rssi" => TableColumn::Rssi,
+            other => return Err(trf("Neznámá veličina „{}“ (použijte temp, hum nebo rssi)", &[&other])),
+        };
+        let value: f64 = value.trim().replace(',', ".").parse().map_err(|_| trf("Neplatná podmínka „{}“", &[&clause]))?;
+        let value = if column == TableColumn::Temp { temp_unit().to_celsius(value) } else { value };
+        Ok(Condition { column, op, value })
+    }).collect()
+}
+
+// Měření jako text oddělený tabulátory pro vložení do tabulkového procesoru (desetinná čárka jako v logu)
+pub fn readings_tsv<'a>(points: impl Iterator<Item = &'a HistoryPoint>) -> String {
+    let unit = temp_unit();
+    let mut tsv = format!("{}\t{}\t{} ({})\t{} (%)\n", tr("Datum"), tr("Čas"), tr("Teplota"), unit.symbol(), tr("Vlhkost"));
+    for p in points {
+        tsv += &format!("{}\t{}\t{}\t{}\n", p.timestamp.format("%d.%m.%Y"), p.timestamp.format("%H:%M:%S"), format!("{:.1}", unit.convert(p.temp as f64)).replace('.', ","), p.hum);
+    }
+    tsv
+}
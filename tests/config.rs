@@ -0,0 +1,71 @@
+// Čtení a převody konfigurace bez souboru na disku
+use temp_monitor::config::{normalize_mac, parse_config, Config, CONFIG_VERSION};
+
+#[test]
+fn normalizes_mac_addresses() {
+    let cases: [(&str, Option<&str>); 21] = [
+        // Platné zápisy: dvojtečky, pomlčky, bez oddělovačů, malá písmena, mezery okolo
+        ("B8:59:CE:33:0F:93", Some("B8:59:CE:33:0F:93")),
+        ("b8:59:ce:33:0f:93", Some("B8:59:CE:33:0F:93")),
+        ("B8-59-CE-33-0F-93", Some("B8:59:CE:33:0F:93")),
+        ("b859ce330f93", Some("B8:59:CE:33:0F:93")),
+        ("  B8:59:CE:33:0F:93\n", Some("B8:59:CE:33:0F:93")),
+        // Smíšené oddělovače se přijmou, každá skupina má dvě číslice
+        ("B8:59-CE:33-0F:93", Some("B8:59:CE:33:0F:93")),
+        ("B8:59-CE:330F:93", None),
+        ("B8:5-9CE:33:0F:93", None),
+        // UUID periferie z macOS
+        ("1b2c3d4e-5f60-7182-93a4-b5c6d7e8f901", Some("1B2C3D4E-5F60-7182-93A4-B5C6D7E8F901")),
+        ("1B2C3D4E-5F60-7182-93A4-B5C6D7E8F90", None),
+        ("1B2C3D4E-5F60-7182-93A4-B5C6D7E8F9G1", None),
+        // Špatná délka
+        ("", None),
+        ("B8:59:CE:33:0F", None),
+        ("B8:59:CE:33:0F:93:00", None),
+        ("B859CE330F9", None),
+        ("B859CE330F9300", None),
+        ("B8:59:CE:33:0F:9", None),
+        ("B8:59:CE:33:0F:933", None),
+        // Nešestnáctkové znaky
+        ("B8:59:CE:33:0F:9G", None),
+        ("ZZ59CE330F93", None),
+        ("B859CE330Fé", None),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(normalize_mac(input).as_deref(), expected, "vstup {:?}", input);
+    }
+}
+
+#[test]
+fn keeps_valid_fields_when_one_is_invalid() {
+    let (config, changed) = parse_config(&format!(r#"{{"version": {}, "target_mac": "b8-59-ce-33-0f-93", "temp_warn_high": "hodně", "hum_warn_high": 75, "refresh_secs": 30}}"#, CONFIG_VERSION)).unwrap();
+    let defaults = Config::default();
+    assert_eq!(config.temp_warn_high, defaults.temp_warn_high);
+    assert_eq!((config.target_mac.as_str(), config.hum_warn_high, config.refresh_secs), ("B8:59:CE:33:0F:93", 75, 30));
+    let changed = changed.unwrap();
+    assert!(changed.contains("'temp_warn_high'"), "{}", changed);
+    assert!(!changed.contains("převedena"), "{}", changed);
+}
+
+#[test]
+fn migrates_old_versions_and_keeps_user_values() {
+    // Soubor bez čísla verze (verze 0)
+    let (config, changed) = parse_config(r#"{"target_mac": "B8:59:CE:33:0F:93", "refresh_secs": 1}"#).unwrap();
+    assert_eq!(config.version, CONFIG_VERSION);
+    assert_eq!(config.refresh_secs, 1);
+    assert!(changed.unwrap().contains(&format!("z verze 0 na {}", CONFIG_VERSION)));
+    let (config, changed) = parse_config(r#"{"version": 1, "refresh_secs": 1}"#).unwrap();
+    assert_eq!((config.version, config.refresh_secs), (CONFIG_VERSION, 1));
+    assert!(changed.is_some());
+    // Aktuální verze beze změn, novější verze se nepřevádí
+    let (config, changed) = parse_config(&format!(r#"{{"version": {}, "refresh_secs": 5}}"#, CONFIG_VERSION)).unwrap();
+    assert_eq!((config.refresh_secs, changed), (5, None));
+    let (config, changed) = parse_config(&format!(r#"{{"version": {}, "refresh_secs": 5, "budouci_volba": true}}"#, CONFIG_VERSION + 1)).unwrap();
+    assert_eq!((config.refresh_secs, changed), (5, None));
+}
+
+#[test]
+fn rejects_unreadable_files() {
+    assert!(parse_config("{\"target_mac\": ").is_err());
+    assert!(parse_config("[1, 2]").is_err());
+}
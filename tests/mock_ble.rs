@@ -0,0 +1,193 @@
+// Celá cesta měření (skener → procesor → výstupy) nad skriptovaným adaptérem: cargo test --features mock-ble
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use temp_monitor::config::{normalize_mac, Config, DeviceConfig, ScanWindow};
+use temp_monitor::error::Error;
+use temp_monitor::mock_ble::{self, tp357_advertisement, ScriptedAdvertisement};
+use temp_monitor::processing::{background_data_processor, AppMessage, BleDataPoint};
+use temp_monitor::scanner::{bluetooth_scanner, supervised_scanner};
+use temp_monitor::sinks::OutputConfig;
+use tokio::sync::{mpsc, watch};
+
+const SENSOR: &str = "B8:59:CE:33:0F:93";
+const OTHER: &str = "11:22:33:44:55:66";
+
+// Skript adaptéru je společný pro celý proces, testy proto nesmí běžet souběžně
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn test_config() -> Config {
+    Config { target_mac: SENSOR.into(), continuous_mode: true, outputs: Vec::new(), ..Config::default() }
+}
+
+fn advertisement(address: &str, temp: f32, hum: u8) -> ScriptedAdvertisement {
+    ScriptedAdvertisement { delay: Duration::from_millis(20), advertisement: tp357_advertisement(address, temp, hum, 2) }
+}
+
+// Přehraje skript, počká na jeho vyčerpání, ukončí skener i procesor a vrátí vše, co procesor poslal oknu
+fn run(config: Config, script: Vec<ScriptedAdvertisement>) -> Vec<AppMessage> {
+    mock_ble::set_script(script);
+    let (out_tx, mut out_rx) = mpsc::unbounded_channel();
+    let (scanner_tx, processor_rx) = mpsc::unbounded_channel();
+    let (_config_tx, config_rx) = watch::channel(config);
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let processor_config = config_rx.clone();
+    let processor = thread::spawn(move || background_data_processor(processor_rx, out_tx.into(), processor_config, Arc::new(Mutex::new(None))));
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let (_paused_tx, paused_rx) = watch::channel(false);
+    let scanner = rt.spawn(bluetooth_scanner(scanner_tx, config_rx, shutdown_rx, paused_rx));
+    for _ in 0..100 {
+        if mock_ble::remaining() == 0 { break; }
+        thread::sleep(Duration::from_millis(20));
+    }
+    thread::sleep(Duration::from_millis(100));
+    shutdown_tx.send(true).unwrap();
+    rt.block_on(scanner).unwrap();
+    processor.join().unwrap();
+    let mut messages = Vec::new();
+    while let Ok(message) = out_rx.try_recv() { messages.push(message); }
+    messages
+}
+
+fn readings(messages: &[AppMessage]) -> Vec<&BleDataPoint> {
+    messages.iter().filter_map(|m| match m { AppMessage::NewData(data) => Some(data), _ => None }).collect()
+}
+
+#[test]
+fn decodes_readings_of_watched_sensor_only() {
+    let _guard = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    let messages = run(test_config(), vec![advertisement(OTHER, 30.0, 80), advertisement(SENSOR, -5.3, 45)]);
+    let readings = readings(&messages);
+    assert_eq!(readings.len(), 1);
+    assert_eq!(readings[0].mac, SENSOR);
+    assert!((readings[0].temp + 5.3).abs() < 0.01);
+    assert_eq!(readings[0].hum, 45);
+    assert_eq!(readings[0].battery, Some(100));
+}
+
+#[test]
+fn drops_duplicates_within_threshold() {
+    let _guard = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    let config = Config { duplicate_threshold_secs: 30, ..test_config() };
+    let messages = run(config, vec![advertisement(SENSOR, 22.0, 50), advertisement(SENSOR, 22.1, 50), advertisement(SENSOR, 22.1, 51)]);
+    assert_eq!(readings(&messages).len(), 1);
+}
+
+#[test]
+fn rejects_implausible_values() {
+    let _guard = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    let config = Config { duplicate_threshold_secs: 0, ..test_config() };
+    let messages = run(config, vec![advertisement(SENSOR, 22.0, 50), advertisement(SENSOR, 95.0, 50)]);
+    assert_eq!(readings(&messages).len(), 1);
+    assert!(messages.iter().any(|m| matches!(m, AppMessage::ReadingRejected(_))));
+}
+
+#[test]
+fn writes_alert_once_when_limit_is_crossed() {
+    let _guard = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    let path = std::env::temp_dir().join(format!("temp_monitor_mock_ble_{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let outputs = vec![OutputConfig::JsonLines { path: path.display().to_string() }];
+    let config = Config { duplicate_threshold_secs: 0, spike_filter_enabled: false, temp_warn_high: 30.0, outputs, ..test_config() };
+    let messages = run(config, vec![advertisement(SENSOR, 25.0, 50), advertisement(SENSOR, 31.0, 50), advertisement(SENSOR, 31.2, 50)]);
+    assert_eq!(readings(&messages).len(), 3);
+    let content = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let lines: Vec<serde_json::Value> = content.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines.iter().filter(|line| line.get("alert").is_some()).count(), 1);
+}
+
+#[test]
+fn reports_missing_adapter() {
+    let _guard = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    mock_ble::set_adapter_available(false);
+    let messages = run(test_config(), Vec::new());
+    mock_ble::set_adapter_available(true);
+    assert!(messages.iter().any(|m| matches!(m, AppMessage::Error(Error::NoAdapter))));
+}
+
+#[test]
+fn matches_sensor_by_corebluetooth_uuid() {
+    let _guard = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    let uuid = normalize_mac("1b2c3d4e-5f60-7182-93a4-b5c6d7e8f901").unwrap();
+    let messages = run(Config { target_mac: uuid.clone(), ..test_config() }, vec![advertisement(&uuid, 21.5, 40)]);
+    let readings = readings(&messages);
+    assert_eq!(readings.len(), 1);
+    assert_eq!(readings[0].mac, uuid);
+}
+
+#[test]
+fn watchdog_restarts_hung_scanner() {
+    let _guard = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    mock_ble::set_adapter_hangs(true);
+    let rt = tokio::runtime::Builder::new_current_thread().enable_all().start_paused(true).build().unwrap();
+    let statuses = rt.block_on(async {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let (_config_tx, config_rx) = watch::channel(test_config());
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let (_paused_tx, paused_rx) = watch::channel(false);
+        let supervisor = tokio::spawn(supervised_scanner(tx, config_rx, shutdown_rx, paused_rx));
+        let mut statuses = Vec::new();
+        // Zaseknuté otevření adaptéru hlídač ukončí; po „opravě“ stacku nový skener normálně začne skenovat
+        while let Some(message) = rx.recv().await {
+            let AppMessage::StatusUpdate(status) = message else { continue; };
+            if statuses.is_empty() { mock_ble::set_adapter_hangs(false); }
+            statuses.push(status);
+            if statuses.len() == 2 { break; }
+        }
+        shutdown_tx.send(true).unwrap();
+        supervisor.await.unwrap();
+        statuses
+    });
+    mock_ble::set_adapter_hangs(false);
+    assert_eq!(statuses, ["Skener nereagoval, restartuji ho…", "Skenuji (kontinuální režim)..."]);
+}
+
+#[test]
+fn stays_idle_outside_scan_windows() {
+    let _guard = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    let now = chrono::Local::now().time();
+    let window = ScanWindow { start: now + chrono::Duration::hours(2), end: now + chrono::Duration::hours(3) };
+    let config = Config { scan_windows: vec![window], ..test_config() };
+    let rt = tokio::runtime::Builder::new_current_thread().enable_all().start_paused(true).build().unwrap();
+    let statuses = rt.block_on(async {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let (_config_tx, config_rx) = watch::channel(config);
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let (_paused_tx, paused_rx) = watch::channel(false);
+        let supervisor = tokio::spawn(supervised_scanner(tx, config_rx, shutdown_rx, paused_rx));
+        let mut statuses = Vec::new();
+        // Hodiny runtime běží v testu rychle, systémový čas ne: skener se budí na začátku okna a znovu usíná
+        let _ = tokio::time::timeout(Duration::from_secs(10 * 3600), async {
+            while let Some(message) = rx.recv().await {
+                if let AppMessage::StatusUpdate(status) = message { statuses.push(status); }
+            }
+        }).await;
+        shutdown_tx.send(true).unwrap();
+        supervisor.await.unwrap();
+        statuses
+    });
+    assert!(!statuses.is_empty());
+    assert!(statuses.iter().all(|status| status.starts_with("Mimo časové okno skenování")), "{:?}", statuses);
+}
+
+#[test]
+fn detects_payload_layout_per_sensor() {
+    let _guard = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    let config = Config { duplicate_threshold_secs: 0, extra_devices: vec![DeviceConfig { name: "Druhý".into(), mac: OTHER.into() }], ..test_config() };
+    // Novější firmware: celá teplota v datech, pevné ID výrobce; poslední paket nedává smysl v žádném rozložení
+    let [low, high] = 215i16.to_le_bytes();
+    let mut payload = tp357_advertisement(OTHER, 0.0, 0, 0);
+    payload.manufacturer_data = [(0x0001, vec![low, high, 40, 1])].into();
+    let mut garbage = payload.clone();
+    garbage.manufacturer_data = [(0x0001, vec![0xFF, 0x7F, 0xFF])].into();
+    let scripted = |advertisement| ScriptedAdvertisement { delay: Duration::from_millis(20), advertisement };
+    let messages = run(config, vec![advertisement(SENSOR, 19.5, 55), scripted(payload), scripted(garbage)]);
+    let readings = readings(&messages);
+    assert_eq!(readings.len(), 2);
+    assert!((readings[0].temp - 19.5).abs() < 0.01 && readings[0].hum == 55);
+    assert_eq!(readings[1].mac, OTHER);
+    assert!((readings[1].temp - 21.5).abs() < 0.01);
+    assert_eq!((readings[1].hum, readings[1].battery), (40, Some(50)));
+}
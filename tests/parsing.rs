@@ -0,0 +1,61 @@
+// Dekódování reklamních paketů TP357 obou rozložení a přepnutí rozložení po opakovaném selhání
+use temp_monitor::parsing::{decode_manufacturer_data, LayoutDetector, PayloadLayout, Tp357Reading};
+
+const SENSOR: &str = "B8:59:CE:33:0F:93";
+
+// Pakety tak, jak je senzor vysílá (ID výrobce a data "manufacturer data").
+// Původní rozložení: 23,4 °C (0x00EA) rozdělené mezi ID výrobce 0xEAC2 a data[0], vlhkost 45 %, plná baterie
+const SPLIT_23_4: (u16, &[u8]) = (0xEAC2, &[0x00, 0x2D, 0x02]);
+// Původní rozložení: −5,3 °C (0xFFCB), vlhkost 80 %, baterie napůl
+const SPLIT_MINUS_5_3: (u16, &[u8]) = (0xCBC2, &[0xFF, 0x50, 0x01]);
+// Novější firmware: 21,5 °C (0x00D7) celá v datech, vlhkost 40 %, baterie napůl
+const PAYLOAD_21_5: (u16, &[u8]) = (0x0001, &[0xD7, 0x00, 0x28, 0x01]);
+// Nedává smysl v žádném rozložení (vlhkost 255 %)
+const GARBAGE: (u16, &[u8]) = (0x0001, &[0xFF, 0x7F, 0xFF]);
+
+fn reading(temp: f32, hum: u8, battery: Option<u8>) -> Option<Tp357Reading> { Some(Tp357Reading { temp, hum, battery }) }
+
+#[test]
+fn decodes_both_layouts() {
+    let (company_id, data) = SPLIT_23_4;
+    assert_eq!(PayloadLayout::SplitTemp.decode(company_id, data), reading(23.4, 45, Some(100)));
+    assert_eq!(decode_manufacturer_data(company_id, data), reading(23.4, 45, Some(100)));
+    let (company_id, data) = SPLIT_MINUS_5_3;
+    assert_eq!(PayloadLayout::SplitTemp.decode(company_id, data), reading(-5.3, 80, Some(50)));
+    let (company_id, data) = PAYLOAD_21_5;
+    assert_eq!(PayloadLayout::PayloadTemp.decode(company_id, data), reading(21.5, 40, Some(50)));
+    // Bez bajtu baterie, resp. s neznámou hodnotou, zůstane baterie neznámá
+    assert_eq!(PayloadLayout::SplitTemp.decode(0xEAC2, &[0x00, 0x2D]), reading(23.4, 45, None));
+    assert_eq!(PayloadLayout::SplitTemp.decode(0xEAC2, &[0x00, 0x2D, 0x07]), reading(23.4, 45, None));
+    assert_eq!(PayloadLayout::SplitTemp.decode(0xEAC2, &[0x00, 0x2D, 0x00]), reading(23.4, 45, Some(1)));
+    // Krátké pakety
+    assert_eq!(PayloadLayout::SplitTemp.decode(0xEAC2, &[0x00]), None);
+    assert_eq!(PayloadLayout::PayloadTemp.decode(0x0001, &[0xD7, 0x00]), None);
+}
+
+#[test]
+fn detects_layout_per_sensor() {
+    let mut detector = LayoutDetector::new();
+    assert_eq!(detector.decode(SENSOR, SPLIT_23_4.0, SPLIT_23_4.1), reading(23.4, 45, Some(100)));
+    assert_eq!(detector.decode("11:22:33:44:55:66", PAYLOAD_21_5.0, PAYLOAD_21_5.1), reading(21.5, 40, Some(50)));
+    assert_eq!(detector.layout(SENSOR), Some(PayloadLayout::SplitTemp));
+    assert_eq!(detector.layout("11:22:33:44:55:66"), Some(PayloadLayout::PayloadTemp));
+    // Neznámý tvar se zahodí a rozložení se nezvolí
+    assert_eq!(detector.decode("AA:BB:CC:DD:EE:FF", GARBAGE.0, GARBAGE.1), None);
+    assert_eq!(detector.layout("AA:BB:CC:DD:EE:FF"), None);
+}
+
+#[test]
+fn redetects_layout_after_three_misses() {
+    let mut detector = LayoutDetector::new();
+    assert!(detector.decode(SENSOR, SPLIT_23_4.0, SPLIT_23_4.1).is_some());
+    // Ojedinělé poškozené pakety rozložení nezmění, věrohodný paket počítadlo vynuluje
+    for _ in 0..2 { assert_eq!(detector.decode(SENSOR, GARBAGE.0, GARBAGE.1), None); }
+    assert_eq!(detector.decode(SENSOR, SPLIT_MINUS_5_3.0, SPLIT_MINUS_5_3.1), reading(-5.3, 80, Some(50)));
+    for _ in 0..2 { assert_eq!(detector.decode(SENSOR, PAYLOAD_21_5.0, PAYLOAD_21_5.1), None); }
+    assert_eq!(detector.layout(SENSOR), Some(PayloadLayout::SplitTemp));
+    // Třetí nevěrohodný paket za sebou (po aktualizaci firmwaru) spustí nové hledání a paket se už použije
+    assert_eq!(detector.decode(SENSOR, PAYLOAD_21_5.0, PAYLOAD_21_5.1), reading(21.5, 40, Some(50)));
+    assert_eq!(detector.layout(SENSOR), Some(PayloadLayout::PayloadTemp));
+    assert_eq!(detector.decode(SENSOR, PAYLOAD_21_5.0, PAYLOAD_21_5.1), reading(21.5, 40, Some(50)));
+}
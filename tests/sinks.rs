@@ -0,0 +1,44 @@
+// Registr výstupů: odkládání měření při úspoře na baterii a jejich dopsání po připojení k síti
+use chrono::Local;
+use temp_monitor::config::Config;
+use temp_monitor::processing::BleDataPoint;
+use temp_monitor::sinks::{Alert, OutputConfig, SinkRegistry};
+
+const SENSOR: &str = "B8:59:CE:33:0F:93";
+
+fn reading(temp: f32) -> BleDataPoint {
+    BleDataPoint { timestamp: Local::now(), temp, hum: 50, device_id: "test".into(), mac: SENSOR.into(), rssi: None, battery: None, raw_data: Vec::new() }
+}
+
+fn lines(path: &std::path::Path) -> Vec<serde_json::Value> {
+    std::fs::read_to_string(path).unwrap_or_default().lines().map(|line| serde_json::from_str(line).unwrap()).collect()
+}
+
+#[test]
+fn defers_readings_on_battery_and_writes_them_on_ac_power() {
+    let path = std::env::temp_dir().join(format!("temp_monitor_sinks_{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let outputs = vec![OutputConfig::JsonLines { path: path.display().to_string() }];
+    let on_battery = Config { target_mac: SENSOR.into(), outputs, battery_saver: true, on_battery: true, ..Config::default() };
+    let mut sinks = SinkRegistry::new(&on_battery.outputs);
+    assert!(sinks.on_reading(&reading(21.0), &on_battery).is_empty());
+    assert!(sinks.on_reading(&reading(22.0), &on_battery).is_empty());
+    // Upozornění se neodkládá
+    assert!(sinks.on_alert(&Alert { timestamp: Local::now(), mac: SENSOR.into(), message: "Teplota nad mezí".into() }, &on_battery).is_empty());
+    assert!(sinks.flush().is_empty());
+    let written = lines(&path);
+    assert_eq!(written.len(), 1);
+    assert!(written[0].get("alert").is_some());
+    // Po připojení k síti se odložená měření dopíšou v původním pořadí před novým
+    let on_ac = Config { on_battery: false, ..on_battery.clone() };
+    assert!(sinks.on_reading(&reading(23.0), &on_ac).is_empty());
+    assert!(sinks.flush().is_empty());
+    let temps: Vec<f64> = lines(&path).iter().filter_map(|line| line.get("temp")?.as_f64()).collect();
+    assert_eq!(temps, [21.0, 22.0, 23.0]);
+    // Odložená měření se dopíšou i při ukončení na baterii
+    assert!(sinks.on_reading(&reading(24.0), &on_battery).is_empty());
+    assert!(sinks.close(&on_battery).is_empty());
+    let written = lines(&path);
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(written.last().and_then(|line| line.get("temp")?.as_f64()), Some(24.0));
+}